@@ -0,0 +1,65 @@
+//! Benchmarks of direct against fft-based convolution, which `ConvolutionBackend::Automatic` picks between.
+//!
+//! A full `StandardLenia` iteration is measured, as the growth and update steps cost the same for both backends.
+//! The cost of the fft path does not depend on the size of the kernel, so each `fft_*` bench is the baseline for
+//! the `direct_*` benches of the same world size. `DIRECT_CONVOLUTION_MAX_COST` is set from where they break even.
+//!
+//! Run with `cargo bench --bench convolution`.
+
+#![feature(test)]
+
+extern crate test;
+
+use road_to_lenia::lenias::StandardLenia;
+use road_to_lenia::{ConvolutionBackend, Simulator};
+use test::Bencher;
+
+fn simulator(
+    size: usize,
+    kernel_size: usize,
+    backend: ConvolutionBackend,
+) -> Simulator<StandardLenia> {
+    let shape = [size, size];
+    let mut simulator = Simulator::<StandardLenia>::new(&shape);
+    simulator.set_kernel(
+        ndarray::ArrayD::from_elem(vec![kernel_size, kernel_size], 1.0),
+        0,
+    );
+    simulator.set_convolution_backend(backend, 0);
+    simulator.fill_channel(
+        &ndarray::ArrayD::from_shape_fn(shape.as_slice(), |index| {
+            ((index[0] * 7 + index[1] * 13) % 10) as f64 / 10.0
+        }),
+        0,
+    );
+    simulator
+}
+
+macro_rules! backend_bench {
+    ($name:ident, $size:expr, $kernel_size:expr, $backend:expr) => {
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let mut simulator = simulator($size, $kernel_size, $backend);
+            b.iter(|| simulator.iterate());
+        }
+    };
+}
+
+backend_bench!(fft_64, 64, 3, ConvolutionBackend::Fft);
+backend_bench!(direct_64_kernel_3, 64, 3, ConvolutionBackend::Direct);
+backend_bench!(direct_64_kernel_5, 64, 5, ConvolutionBackend::Direct);
+backend_bench!(direct_64_kernel_7, 64, 7, ConvolutionBackend::Direct);
+backend_bench!(direct_64_kernel_9, 64, 9, ConvolutionBackend::Direct);
+backend_bench!(direct_64_kernel_11, 64, 11, ConvolutionBackend::Direct);
+backend_bench!(fft_256, 256, 3, ConvolutionBackend::Fft);
+backend_bench!(direct_256_kernel_3, 256, 3, ConvolutionBackend::Direct);
+backend_bench!(direct_256_kernel_5, 256, 5, ConvolutionBackend::Direct);
+backend_bench!(direct_256_kernel_7, 256, 7, ConvolutionBackend::Direct);
+backend_bench!(direct_256_kernel_9, 256, 9, ConvolutionBackend::Direct);
+backend_bench!(direct_256_kernel_11, 256, 11, ConvolutionBackend::Direct);
+backend_bench!(fft_1024, 1024, 3, ConvolutionBackend::Fft);
+backend_bench!(direct_1024_kernel_3, 1024, 3, ConvolutionBackend::Direct);
+backend_bench!(direct_1024_kernel_5, 1024, 5, ConvolutionBackend::Direct);
+backend_bench!(direct_1024_kernel_7, 1024, 7, ConvolutionBackend::Direct);
+backend_bench!(direct_1024_kernel_9, 1024, 9, ConvolutionBackend::Direct);
+backend_bench!(direct_1024_kernel_11, 1024, 11, ConvolutionBackend::Direct);
@@ -0,0 +1,89 @@
+//! Benchmarks of a single `Simulator::iterate()` call at various world sizes.
+//!
+//! Run with `cargo bench`.
+
+#![feature(test)]
+
+extern crate test;
+
+use ndarray::Dimension;
+use road_to_lenia::lenias::{ExpandedLenia, StandardLenia};
+use road_to_lenia::{growth_functions, kernels, Simulator};
+use test::Bencher;
+
+/// Deterministic pseudo-random starting pattern, so that every run benchmarks the same work.
+fn seed(shape: &[usize]) -> ndarray::ArrayD<f64> {
+    ndarray::ArrayD::from_shape_fn(shape, |index| {
+        let mut hash: usize = 17;
+        for i in index.slice() {
+            hash = hash.wrapping_mul(31).wrapping_add(*i);
+        }
+        ((hash.wrapping_mul(2654435761) >> 8) % 1000) as f64 / 1000.0
+    })
+}
+
+fn standard_lenia(size: usize) -> Simulator<StandardLenia> {
+    let shape = [size, size];
+    let mut simulator = Simulator::<StandardLenia>::new(&shape);
+    simulator.fill_channel(&seed(&shape), 0);
+    simulator
+}
+
+/// The two-soliton ruleset from the `ExpandedLenia` documentation.
+fn expanded_lenia(size: usize) -> Simulator<ExpandedLenia> {
+    let shape = [size, size];
+    let mut simulator = Simulator::<ExpandedLenia>::new(&shape);
+    simulator.set_channels(2);
+    simulator.set_convolution_channels(3);
+    simulator.set_convolution_channel_source(0, 0);
+    simulator.set_convolution_channel_source(1, 1);
+    simulator.set_convolution_channel_source(2, 1);
+    simulator.set_kernel(kernels::gaussian_donut_2d(14, 0.15), 0);
+    simulator.set_kernel(kernels::polynomial_nd(25, 2, &[4.0, 1.0, 0.333]), 1);
+    simulator.set_kernel(kernels::polynomial_nd(21, 2, &[4.0, 0.0, 1.0]), 2);
+    simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.02), 0);
+    simulator.set_growth_function(growth_functions::Polynomial::new(0.25, 0.03, 4.0), 1);
+    simulator.set_growth_function(growth_functions::Polynomial::new(0.07, 0.026, 4.0), 2);
+    simulator.set_weights(0, &[2.0 / 3.0, 0.0, 1.0 / 3.0]);
+    simulator.set_weights(1, &[0.0, 1.0, 0.0]);
+    simulator.set_dt(0.1);
+    simulator.fill_channel(&seed(&shape), 0);
+    simulator.fill_channel(&seed(&shape), 1);
+    simulator
+}
+
+#[bench]
+fn standard_lenia_64(b: &mut Bencher) {
+    let mut simulator = standard_lenia(64);
+    b.iter(|| simulator.iterate());
+}
+
+#[bench]
+fn standard_lenia_256(b: &mut Bencher) {
+    let mut simulator = standard_lenia(256);
+    b.iter(|| simulator.iterate());
+}
+
+#[bench]
+fn standard_lenia_1024(b: &mut Bencher) {
+    let mut simulator = standard_lenia(1024);
+    b.iter(|| simulator.iterate());
+}
+
+#[bench]
+fn expanded_lenia_64(b: &mut Bencher) {
+    let mut simulator = expanded_lenia(64);
+    b.iter(|| simulator.iterate());
+}
+
+#[bench]
+fn expanded_lenia_256(b: &mut Bencher) {
+    let mut simulator = expanded_lenia(256);
+    b.iter(|| simulator.iterate());
+}
+
+#[bench]
+fn expanded_lenia_1024(b: &mut Bencher) {
+    let mut simulator = expanded_lenia(1024);
+    b.iter(|| simulator.iterate());
+}
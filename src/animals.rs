@@ -0,0 +1,942 @@
+//! Species of the original Lenia animal library, read from and written to its JSON format.
+//!
+//! Each species of the library is a JSON object with the parameters of its ruleset and its cells:
+//!
+//! ```json
+//! {
+//!     "code": "O2u",
+//!     "name": "Orbium unicaudatus",
+//!     "params": { "R": 13, "T": 10, "b": "1", "m": 0.15, "s": 0.015, "kn": 1, "gn": 1 },
+//!     "cells": "7.MD6.qL$6.pKqEqFURpApBRAqQ$ ... !"
+//! }
+//! ```
+//!
+//! The ruleset is turned into `Rules` of a single channel and convolution channel. Kernels with the polynomial core
+//! are made by `kernels::polynomial_nd()` and match the original exactly. Kernels with the exponential core are
+//! approximated by the gaussian rings of `kernels::multi_gaussian_donut_nd()`. Growth functions of every type match
+//! the original exactly.
+//!
+//! Going the other way, `Simulator::species()` describes a simulation as a species, such that the creatures found
+//! here can be shared with other Lenia tools. Only simulations that the format can express are described, with a
+//! single channel and convolution channel, a kernel with the polynomial or exponential core and a growth function
+//! of one of the three types.
+//!
+//! The cells are run-length encoded, with rows ending in `$`, planes in `%`, 3d blocks in `#` and any higher
+//! dimensional blocks in `@A`, `@B` and so on. The state of a cell is `.` for `0`, `A` to `X` for `1` to `24`, and
+//! `pA` to `yO` for `25` to `255`, out of a maximum of `255`.
+
+use super::*;
+use num_traits::ToPrimitive;
+use rules::{
+    ChannelRules, ConvolutionChannelRules, GrowthFunctionRules, KernelRules, Registry, Rules,
+};
+use std::path::Path;
+
+/// Standard deviation of each gaussian ring approximating a ring of the exponential kernel core, relative to the
+/// width of the ring. Fitted by least squares against `exp(4 - 1 / (r * (1 - r)))`.
+const EXPONENTIAL_CORE_STDDEV: f64 = 0.15;
+
+/// Largest difference from `EXPONENTIAL_CORE_STDDEV`, and of the values of a kernel from its single ring
+/// approximation, for a kernel to be described as having the exponential core.
+const EXPONENTIAL_CORE_TOLERANCE: f64 = 0.01;
+
+/// Largest denominator of the fractions that kernel peaks are written as.
+const MAX_DENOMINATOR: u32 = 12;
+
+/// Largest number of cells, and of the lists holding them, that the cells of a species may decode to,
+/// such that corrupted run lengths fail instead of exhausting memory.
+const MAX_CELLS: usize = 1 << 24;
+
+/// A species of the Lenia animal library.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Species {
+    /// Short code of the species, such as `"O2u"`.
+    #[serde(default)]
+    pub code: String,
+    /// Name of the species, such as `"Orbium unicaudatus"`.
+    #[serde(default)]
+    pub name: String,
+    /// Parameters of the ruleset of the species.
+    pub params: SpeciesParameters,
+    /// The run-length encoded cells of the species, see the module documentation.
+    pub cells: String,
+}
+
+/// Parameters of the ruleset of a species in the Lenia animal library.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesParameters {
+    /// Radius of the kernel `R`.
+    #[serde(rename = "R")]
+    pub radius: usize,
+    /// Time resolution `T`, the reciprocal of the integration step `dt`.
+    #[serde(rename = "T")]
+    pub time_resolution: f64,
+    /// Peak heights `b` of the rings of the kernel, from the innermost ring outwards.
+    ///
+    /// Written as a comma-separated list of numbers and fractions, such as `"1,1/3"`.
+    #[serde(
+        rename = "b",
+        deserialize_with = "deserialize_peaks",
+        serialize_with = "serialize_peaks"
+    )]
+    pub peaks: Vec<f64>,
+    /// Mean `m` of the growth function.
+    #[serde(rename = "m")]
+    pub mu: f64,
+    /// Width `s` of the growth function.
+    #[serde(rename = "s")]
+    pub sigma: f64,
+    /// Core of each ring of the kernel `kn`, `0` for polynomial, `1` for exponential, `2` for step and
+    /// `3` for staircase.
+    #[serde(rename = "kn", default = "default_core")]
+    pub kernel_core: u8,
+    /// Type of the growth function `gn`, `0` for polynomial, `1` for exponential (gaussian) and `2` for step.
+    #[serde(rename = "gn", default = "default_core")]
+    pub growth_type: u8,
+}
+
+fn default_core() -> u8 {
+    1
+}
+
+/// Peak heights as written in the library, either as text or as plain numbers.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Peaks {
+    Text(String),
+    Values(Vec<f64>),
+}
+
+fn deserialize_peaks<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<f64>, D::Error> {
+    match Peaks::deserialize(deserializer)? {
+        Peaks::Values(values) => Ok(values),
+        Peaks::Text(text) => text
+            .split(',')
+            .map(|peak| parse_fraction(peak.trim()))
+            .collect::<Option<Vec<f64>>>()
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid kernel peaks \"{}\"", text))),
+    }
+}
+
+fn serialize_peaks<S: serde::Serializer>(peaks: &[f64], serializer: S) -> Result<S::Ok, S::Error> {
+    let text: Vec<String> = peaks.iter().map(|peak| format_fraction(*peak)).collect();
+    serializer.serialize_str(&text.join(","))
+}
+
+/// Write a number as a fraction if it is one with a small denominator, such as `"1/3"`, as a decimal otherwise.
+fn format_fraction(value: f64) -> String {
+    for denominator in 1..=MAX_DENOMINATOR {
+        let numerator = value * denominator as f64;
+        if (numerator - numerator.round()).abs() < 1e-9 {
+            return if denominator == 1 {
+                format!("{}", numerator.round())
+            } else {
+                format!("{}/{}", numerator.round(), denominator)
+            };
+        }
+    }
+    value.to_string()
+}
+
+/// Parse a number written either as a decimal or as a fraction, such as `"1/3"`.
+fn parse_fraction(text: &str) -> Option<f64> {
+    match text.split_once('/') {
+        Some((numerator, denominator)) => {
+            let denominator: f64 = denominator.trim().parse().ok()?;
+            if denominator == 0.0 {
+                return None;
+            }
+            Some(numerator.trim().parse::<f64>().ok()? / denominator)
+        }
+        None => text.parse().ok(),
+    }
+}
+
+impl Species {
+    /// Parse a single species from JSON.
+    pub fn from_json(json: &str) -> Result<Self, LeniaError> {
+        serde_json::from_str(json)
+            .map_err(|error| LeniaError::Format(format!("Invalid species: {}.", error)))
+    }
+
+    /// Write the species as JSON.
+    pub fn to_json(&self) -> Result<String, LeniaError> {
+        serde_json::to_string(self)
+            .map_err(|error| LeniaError::Format(format!("Could not write species: {}.", error)))
+    }
+
+    /// Get the ruleset of the species, simulated in channels of the given shape.
+    ///
+    /// ### Parameters
+    ///
+    /// * `shape` - The shape of the channels, which sets the dimensionality of the kernel.
+    ///
+    /// ### Errors
+    ///
+    /// * `LeniaError::Unsupported` if the kernel has the step or staircase core, or the growth function is of
+    /// an unknown type.
+    pub fn rules(&self, shape: &[usize]) -> Result<Rules, LeniaError> {
+        let params = &self.params;
+        if params.peaks.is_empty() {
+            return Err(LeniaError::Format(format!(
+                "Species \"{}\" has no kernel peaks.",
+                self.name
+            )));
+        }
+        let rings = params.peaks.len() as f64;
+        let kernel = match params.kernel_core {
+            0 => KernelRules::Generated {
+                generator: "polynomial_nd".to_string(),
+                radius: params.radius,
+                parameters: std::iter::once(4.0)
+                    .chain(params.peaks.iter().copied())
+                    .collect(),
+            },
+            1 => KernelRules::Generated {
+                generator: "multi_gaussian_donut_nd".to_string(),
+                radius: params.radius,
+                parameters: params
+                    .peaks
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, peak)| {
+                        [
+                            (i as f64 + 0.5) / rings,
+                            *peak,
+                            EXPONENTIAL_CORE_STDDEV / rings,
+                        ]
+                    })
+                    .collect(),
+            },
+            core => {
+                return Err(LeniaError::Unsupported(format!(
+                    "Kernel core {} of species \"{}\" has no equivalent in the kernels module!",
+                    core, self.name
+                )))
+            }
+        };
+        let growth_function = match params.growth_type {
+            0 => GrowthFunctionRules {
+                name: "polynomial".to_string(),
+                parameters: vec![params.mu, params.sigma, 4.0],
+            },
+            1 => GrowthFunctionRules {
+                name: "standard_lenia".to_string(),
+                parameters: vec![params.mu, params.sigma],
+            },
+            // A polynomial bump of power 0 is a step of half-width `3 * sigma`.
+            2 => GrowthFunctionRules {
+                name: "polynomial".to_string(),
+                parameters: vec![params.mu, params.sigma / 3.0, 0.0],
+            },
+            growth_type => {
+                return Err(LeniaError::Unsupported(format!(
+                    "Growth type {} of species \"{}\" has no equivalent in the growth_functions module!",
+                    growth_type, self.name
+                )))
+            }
+        };
+        Ok(Rules {
+            shape: shape.to_vec(),
+            dt: 1.0 / params.time_resolution,
+            boundaries: Vec::new(),
+            integrator: Integrator::default(),
+            channels: vec![ChannelRules {
+                weights: vec![1.0],
+                update_mode: UpdateMode::default(),
+            }],
+            convolution_channels: vec![ConvolutionChannelRules {
+                source: 0,
+                backend: ConvolutionBackend::default(),
+                kernel,
+                growth_function,
+            }],
+        })
+    }
+
+    /// Decode the cells of the species into a pattern, to be placed into a channel.
+    ///
+    /// The first axis of the pattern is the outermost level of the encoding, such that a 2d pattern is indexed
+    /// by row and then by column. Rows and planes shorter than the longest one are padded with `0.0`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `dimensions` - Dimensionality of the pattern, which may be more than that of the encoding.
+    ///
+    /// ### Errors
+    ///
+    /// * `LeniaError::Format` if the cells hold an invalid state, or a level deeper than `dimensions`.
+    pub fn pattern(&self, dimensions: usize) -> Result<ndarray::ArrayD<f64>, LeniaError> {
+        decode_cells(&self.cells, dimensions)
+    }
+}
+
+/// Parse every species of an animal library, skipping the entries that only group species together.
+pub fn parse_library(json: &str) -> Result<Vec<Species>, LeniaError> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(json)
+        .map_err(|error| LeniaError::Format(format!("Invalid animal library: {}.", error)))?;
+    entries
+        .into_iter()
+        .filter(|entry| entry.get("params").is_some() && entry.get("cells").is_some())
+        .map(|entry| {
+            serde_json::from_value(entry)
+                .map_err(|error| LeniaError::Format(format!("Invalid species: {}.", error)))
+        })
+        .collect()
+}
+
+/// Read every species of an animal library file, see `parse_library()`.
+///
+/// ### Parameters
+///
+/// * `file_path` - Path to the animal library, such as `animals.json`.
+pub fn load_library<P: AsRef<Path>>(file_path: P) -> Result<Vec<Species>, LeniaError> {
+    parse_library(&std::fs::read_to_string(file_path)?)
+}
+
+/// Write species as an animal library, one species per line.
+pub fn library_to_json(species: &[Species]) -> Result<String, LeniaError> {
+    let entries = species
+        .iter()
+        .map(Species::to_json)
+        .collect::<Result<Vec<String>, LeniaError>>()?;
+    Ok(format!("[\n{}\n]\n", entries.join(",\n")))
+}
+
+/// Write species to an animal library file, see `library_to_json()`.
+///
+/// ### Parameters
+///
+/// * `file_path` - Path to the animal library, which is overwritten if it exists.
+///
+/// * `species` - The species of the library.
+pub fn save_library<P: AsRef<Path>>(file_path: P, species: &[Species]) -> Result<(), LeniaError> {
+    std::fs::write(file_path, library_to_json(species)?)?;
+    Ok(())
+}
+
+/// Run-length encode a pattern into the cells of a species, the reverse of `Species::pattern()`.
+///
+/// Values are clamped to `0.0..1.0` and rounded to the nearest of the `256` states of a cell.
+///
+/// ### Parameters
+///
+/// * `pattern` - The pattern to encode, indexed by row and then by column in 2d.
+pub fn encode_cells(pattern: &ndarray::ArrayD<f64>) -> String {
+    let mut tokens = Vec::new();
+    encode_level(pattern.view(), &mut tokens);
+    let mut cells = String::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let run = tokens[i..]
+            .iter()
+            .take_while(|token| **token == tokens[i])
+            .count();
+        if run > 1 {
+            cells.push_str(&run.to_string());
+        }
+        cells.push_str(&tokens[i]);
+        i += run;
+    }
+    cells.push('!');
+    cells
+}
+
+/// Append the tokens of each cell and delimiter of a level, leaving out the empty cells at the end of each row.
+fn encode_level(pattern: ndarray::ArrayViewD<f64>, tokens: &mut Vec<String>) {
+    let dimensions = pattern.ndim();
+    if dimensions <= 1 {
+        let row: Vec<String> = pattern
+            .iter()
+            .map(|value| state_token((value.max(0.0).min(1.0) * 255.0).round() as u32))
+            .collect();
+        let len = row
+            .iter()
+            .rposition(|token| token != ".")
+            .map_or(0, |i| i + 1);
+        tokens.extend(row.into_iter().take(len));
+    } else {
+        for (i, level) in pattern.outer_iter().enumerate() {
+            if i > 0 {
+                tokens.push(delimiter(dimensions - 1));
+            }
+            encode_level(level, tokens);
+        }
+    }
+}
+
+/// Token of a cell state out of `255`, the reverse of `cell_state()`.
+fn state_token(state: u32) -> String {
+    match state {
+        0 => ".".to_string(),
+        1..=24 => ((b'A' + (state - 1) as u8) as char).to_string(),
+        _ => format!(
+            "{}{}",
+            (b'p' + ((state - 25) / 24) as u8) as char,
+            (b'A' + ((state - 25) % 24) as u8) as char
+        ),
+    }
+}
+
+/// A decoded level of the cells, either the state of a single cell or a list of lower levels.
+enum Cells {
+    Value(f64),
+    List(Vec<Cells>),
+}
+
+/// Character, or two characters, that ends a level of the cells.
+fn delimiter(level: usize) -> String {
+    match level {
+        1 => "$".to_string(),
+        2 => "%".to_string(),
+        3 => "#".to_string(),
+        _ => format!("@{}", (b'A' + (level - 4) as u8) as char),
+    }
+}
+
+/// Level that `token` ends, if it is a delimiter.
+fn delimiter_level(token: &str) -> Option<usize> {
+    match token {
+        "$" => Some(1),
+        "%" => Some(2),
+        "#" => Some(3),
+        _ => {
+            let mut chars = token.chars();
+            match (chars.next(), chars.next(), chars.next()) {
+                (Some('@'), Some(level @ 'A'..='Z'), None) => {
+                    Some(level as usize - 'A' as usize + 4)
+                }
+                _ => None,
+            }
+        }
+    }
+}
+
+/// State of a cell, out of `255`.
+fn cell_state(token: &str) -> Option<u32> {
+    let mut chars = token.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some('.'), None, None) | (Some('b'), None, None) => Some(0),
+        (Some('o'), None, None) => Some(255),
+        (Some(state @ 'A'..='X'), None, None) => Some(state as u32 - 'A' as u32 + 1),
+        (Some(prefix @ 'p'..='y'), Some(state @ 'A'..='X'), None) => {
+            let state = (prefix as u32 - 'p' as u32) * 24 + (state as u32 - 'A' as u32) + 25;
+            if state <= 255 {
+                Some(state)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+fn decode_cells(cells: &str, dimensions: usize) -> Result<ndarray::ArrayD<f64>, LeniaError> {
+    if dimensions == 0 {
+        return Err(LeniaError::InvalidParameter(
+            "A pattern must have at least 1 dimension!".to_string(),
+        ));
+    }
+    let mut stacks: Vec<Vec<Cells>> = (0..dimensions).map(|_| Vec::new()).collect();
+    let mut prefix = String::new();
+    let mut count = String::new();
+    let mut decoded: usize = 0;
+    let end = if dimensions > 1 {
+        delimiter(dimensions - 1)
+    } else {
+        String::new()
+    };
+    let cells = cells.split('!').next().unwrap_or("");
+    for character in cells.chars().chain(end.chars()) {
+        if character.is_whitespace() {
+            continue;
+        }
+        if character.is_ascii_digit() {
+            count.push(character);
+            continue;
+        }
+        if "pqrstuvwxy@".contains(character) {
+            prefix = character.to_string();
+            continue;
+        }
+        let token = format!("{}{}", prefix, character);
+        let repeats: usize = if count.is_empty() {
+            1
+        } else {
+            count.parse().map_err(|_| {
+                LeniaError::Format(format!("Invalid run length {} in the cells.", count))
+            })?
+        };
+        let level = delimiter_level(&token);
+        decoded = decoded.saturating_add(repeats.saturating_mul(level.unwrap_or(1)));
+        if decoded > MAX_CELLS {
+            return Err(too_many_cells());
+        }
+        if let Some(level) = level {
+            if level >= dimensions {
+                return Err(LeniaError::Format(format!(
+                    "The cells have more than {} dimensions.",
+                    dimensions
+                )));
+            }
+            for lower in 0..level {
+                let list = std::mem::take(&mut stacks[lower]);
+                stacks[lower + 1].push(Cells::List(list));
+                for _ in 1..repeats {
+                    stacks[lower + 1].push(Cells::List(Vec::new()));
+                }
+            }
+        } else {
+            let state = cell_state(&token).ok_or_else(|| {
+                LeniaError::Format(format!("Invalid cell state \"{}\" in the cells.", token))
+            })?;
+            let value = state as f64 / 255.0;
+            for _ in 0..repeats {
+                stacks[0].push(Cells::Value(value));
+            }
+        }
+        prefix.clear();
+        count.clear();
+    }
+    let cells = stacks.pop().unwrap();
+    let mut shape = vec![0; dimensions];
+    measure(&cells, 0, &mut shape);
+    if shape
+        .iter()
+        .try_fold(1usize, |len, axis| len.checked_mul(*axis))
+        .map_or(true, |len| len > MAX_CELLS)
+    {
+        return Err(too_many_cells());
+    }
+    let mut pattern = ndarray::ArrayD::zeros(shape);
+    let mut index = vec![0; dimensions];
+    fill(&cells, 0, &mut index, &mut pattern);
+    Ok(pattern)
+}
+
+/// Error for cells that decode to more than `MAX_CELLS` cells.
+fn too_many_cells() -> LeniaError {
+    LeniaError::Format(format!(
+        "The cells decode to more than {} cells.",
+        MAX_CELLS
+    ))
+}
+
+/// Find the length of the longest list at each level.
+fn measure(cells: &[Cells], depth: usize, shape: &mut [usize]) {
+    shape[depth] = shape[depth].max(cells.len());
+    for cell in cells {
+        if let Cells::List(list) = cell {
+            measure(list, depth + 1, shape);
+        }
+    }
+}
+
+fn fill(cells: &[Cells], depth: usize, index: &mut [usize], pattern: &mut ndarray::ArrayD<f64>) {
+    for (i, cell) in cells.iter().enumerate() {
+        index[depth] = i;
+        match cell {
+            Cells::Value(value) => pattern[&*index] = *value,
+            Cells::List(list) => fill(list, depth + 1, index, pattern),
+        }
+    }
+    index[depth] = 0;
+}
+
+impl<L: NewLenia> Simulator<L> {
+    /// Initialize a Lenia simulator with the ruleset of a species from the Lenia animal library.
+    ///
+    /// The cells of the species are not placed into the channel, get them with `Species::pattern()`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `species` - The species to simulate.
+    ///
+    /// * `shape` - The shape of the channels, which must fit the kernel of the species.
+    ///
+    /// ### Errors
+    ///
+    /// The same as `Species::rules()` and `Simulator::from_rules()`.
+    pub fn from_species(species: &Species, shape: &[usize]) -> Result<Self, LeniaError> {
+        Self::from_rules(&species.rules(shape)?, &Registry::new())
+    }
+}
+
+impl<L: Lenia> Simulator<L> {
+    /// Describe the simulation as a species of the Lenia animal library, along with the cells of a region of the
+    /// channel.
+    ///
+    /// The code and name of the species are left empty. Kernels made from rules by `kernels::polynomial_nd()` with a
+    /// power of `4.0` are described exactly. Kernels of gaussian rings are described as having the exponential core
+    /// if they are made from rules by `kernels::multi_gaussian_donut_nd()` with the rings of `Species::rules()`, or
+    /// if they are close to its single ring, such as `kernels::gaussian_donut_2d()` with a standard deviation of
+    /// `0.15`. Empty cells at the end of each row are left out of the encoding.
+    ///
+    /// ### Parameters
+    ///
+    /// * `region` - The range of the cells of the species along each axis of the channel.
+    ///
+    /// ### Errors
+    ///
+    /// * `LeniaError::Unsupported` if the simulation has no equivalent in the format, such as with more than one
+    /// channel, a kernel or growth function with no equivalent, or a growth parameter map.
+    ///
+    /// * `LeniaError::DimensionalityMismatch` or `LeniaError::InvalidParameter` if `region` does not fit the channel.
+    pub fn species(&self, region: &[std::ops::Range<usize>]) -> Result<Species, LeniaError> {
+        if self.channels() != 1 || self.convolution_channels() != 1 {
+            return Err(LeniaError::Unsupported(format!("The Lenia animal library format holds a single channel and convolution channel, not {} channels and {} convolution channels!", self.channels(), self.convolution_channels())));
+        }
+        if self.update_mode(0) != UpdateMode::Additive
+            || self.integrator() != Integrator::Euler
+            || self.is_adaptive_dt()
+            || self.stochasticity().is_some()
+            || self.value_range(0) != ValueRange::default()
+            || self
+                .boundary_conditions()
+                .iter()
+                .any(|boundary| *boundary != BoundaryCondition::Periodic)
+        {
+            return Err(LeniaError::Unsupported("The Lenia animal library format only holds additive updates by the Euler method with a fixed dt, periodic boundaries and values clamped to 0.0..1.0!".to_string()));
+        }
+        let (radius, peaks, kernel_core) = self.species_kernel()?;
+        let growth_function = self.growth_function(0);
+        for parameter in growth_function.parameter_names() {
+            if self.growth_parameter_map(0, &parameter).is_some() {
+                return Err(LeniaError::Unsupported(format!("Parameter \"{}\" of the growth function has a parameter map, which has no equivalent in the Lenia animal library format!", parameter)));
+            }
+        }
+        let parameters: Vec<f64> = growth_function
+            .parameters()
+            .iter()
+            .map(|value| value.to_f64().unwrap())
+            .collect();
+        let (mu, sigma, growth_type) = match (growth_function.name(), parameters.as_slice()) {
+            ("standard_lenia", [mu, sigma]) => (*mu, *sigma, 1),
+            ("polynomial", [mu, sigma, alpha]) if *alpha == 4.0 => (*mu, *sigma, 0),
+            ("polynomial", [mu, sigma, alpha]) if *alpha == 0.0 => (*mu, *sigma * 3.0, 2),
+            (name, _) => {
+                return Err(LeniaError::Unsupported(format!("Growth function \"{}\" with parameters {:?} has no equivalent in the Lenia animal library format!", name, parameters)))
+            }
+        };
+        if region.len() != self.shape().len() {
+            return Err(LeniaError::DimensionalityMismatch {
+                expected: self.shape().len(),
+                found: region.len(),
+            });
+        }
+        for (axis, range) in region.iter().enumerate() {
+            if range.start > range.end || range.end > self.shape()[axis] {
+                return Err(LeniaError::InvalidParameter(format!(
+                    "Region {:?} does not fit axis {} of length {}!",
+                    range,
+                    axis,
+                    self.shape()[axis]
+                )));
+            }
+        }
+        let pattern = self
+            .get_channel_as_ref(0)
+            .slice_each_axis(|axis| ndarray::Slice::from(region[axis.axis.index()].clone()))
+            .mapv(|value| value.to_f64().unwrap());
+        Ok(Species {
+            code: String::new(),
+            name: String::new(),
+            params: SpeciesParameters {
+                radius,
+                time_resolution: 1.0 / self.dt().to_f64().unwrap(),
+                peaks,
+                mu,
+                sigma,
+                kernel_core,
+                growth_type,
+            },
+            cells: encode_cells(&pattern),
+        })
+    }
+
+    /// Find the radius, ring peaks and core of the kernel, as described in `species()`.
+    fn species_kernel(&self) -> Result<(usize, Vec<f64>, u8), LeniaError> {
+        if let Some(Some(KernelRules::Generated {
+            generator,
+            radius,
+            parameters,
+        })) = self.kernel_rules.first()
+        {
+            match generator.as_str() {
+                "polynomial_nd" if parameters.len() > 1 && parameters[0] == 4.0 => {
+                    return Ok((*radius, parameters[1..].to_vec(), 0));
+                }
+                "multi_gaussian_donut_2d" | "multi_gaussian_donut_nd"
+                    if !parameters.is_empty() && parameters.len() % 3 == 0 =>
+                {
+                    let rings = (parameters.len() / 3) as f64;
+                    let exponential = parameters.chunks(3).enumerate().all(|(i, ring)| {
+                        (ring[0] - (i as f64 + 0.5) / rings).abs() < 1e-9
+                            && (ring[2] * rings - EXPONENTIAL_CORE_STDDEV).abs()
+                                <= EXPONENTIAL_CORE_TOLERANCE
+                    });
+                    if exponential {
+                        return Ok((
+                            *radius,
+                            parameters.chunks(3).map(|ring| ring[1]).collect(),
+                            1,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        }
+        let base = &self.get_kernel_as_ref(0).base;
+        let radius = base.shape()[0] / 2;
+        if radius > 0 && base.shape().iter().all(|len| *len == radius * 2) {
+            let ring = kernels::multi_gaussian_donut_nd(
+                radius,
+                base.ndim(),
+                &[0.5],
+                &[1.0],
+                &[EXPONENTIAL_CORE_STDDEV],
+            );
+            let close = base.iter().zip(ring.iter()).all(|(value, ring)| {
+                (value.to_f64().unwrap() - ring).abs() <= EXPONENTIAL_CORE_TOLERANCE
+            });
+            if close {
+                return Ok((radius, vec![1.0], 1));
+            }
+        }
+        Err(LeniaError::Unsupported("The kernel has no equivalent in the Lenia animal library format, which only holds kernels with the polynomial or exponential core!".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::ExpandedLenia;
+
+    /// The parameters of Orbium unicaudatus as written in the animal library, with small made-up cells that
+    /// use runs of cells, runs of rows and both single and two-character states.
+    const ORBIUM: &str = r#"{
+        "code": "O2u",
+        "name": "Orbium unicaudatus",
+        "params": { "R": 13, "T": 10, "b": "1", "m": 0.15, "s": 0.015, "kn": 1, "gn": 1 },
+        "cells": "2.AqL$2$yOpA2.X!"
+    }"#;
+
+    fn state(state: u32) -> f64 {
+        state as f64 / 255.0
+    }
+
+    #[test]
+    fn decodes_the_cells_of_a_species() {
+        let species = Species::from_json(ORBIUM).unwrap();
+        let pattern = species.pattern(2).unwrap();
+        let expected = ndarray::arr2(&[
+            [0.0, 0.0, state(1), state(60), 0.0],
+            [0.0; 5],
+            [0.0; 5],
+            [state(255), state(25), 0.0, 0.0, state(24)],
+        ])
+        .into_dyn();
+        assert_eq!(pattern, expected);
+
+        // A pattern can have more dimensions than its encoding.
+        let pattern = species.pattern(3).unwrap();
+        assert_eq!(pattern.shape(), &[1, 4, 5]);
+        assert_eq!(pattern.index_axis(Axis(0), 0), expected);
+    }
+
+    #[test]
+    fn decodes_every_level_of_the_cells() {
+        let pattern = decode_cells("A$B%C#D@AE!", 5).unwrap();
+        assert_eq!(pattern.shape(), &[2, 2, 2, 2, 1]);
+        assert_eq!(pattern[[0, 0, 0, 0, 0]], state(1));
+        assert_eq!(pattern[[0, 0, 0, 1, 0]], state(2));
+        assert_eq!(pattern[[0, 0, 1, 0, 0]], state(3));
+        assert_eq!(pattern[[0, 1, 0, 0, 0]], state(4));
+        assert_eq!(pattern[[1, 0, 0, 0, 0]], state(5));
+        assert_eq!(pattern.sum(), state(15));
+
+        // A run of delimiters repeats the empty entries at every level it ends, such that the first plane
+        // gets an empty second row.
+        let pattern = decode_cells("A2%B!", 3).unwrap();
+        assert_eq!(pattern.shape(), &[3, 2, 1]);
+        assert_eq!(
+            pattern.iter().copied().collect::<Vec<f64>>(),
+            [state(1), 0.0, 0.0, 0.0, state(2), 0.0]
+        );
+    }
+
+    #[test]
+    fn invalid_cells_are_an_error() {
+        for cells in [
+            "zA!",
+            "yP!",
+            "pZ!",
+            "A?B!",
+            "A@AB!",
+            "999999999999o!",
+            "99999999999999999999999A!",
+            "9999A$9999$!",
+        ] {
+            assert!(
+                matches!(decode_cells(cells, 2), Err(LeniaError::Format(_))),
+                "{}",
+                cells
+            );
+        }
+        assert!(matches!(
+            decode_cells("99999999%!", 3),
+            Err(LeniaError::Format(_))
+        ));
+    }
+
+    #[test]
+    fn kernel_cores_and_growth_types_map_onto_the_kernels_and_growth_functions() {
+        let mut species = Species::from_json(ORBIUM).unwrap();
+        species.params.peaks = vec![1.0, 1.0 / 3.0];
+        species.params.kernel_core = 0;
+        species.params.growth_type = 0;
+        let simulator = Simulator::<ExpandedLenia>::from_species(&species, &[64, 64]).unwrap();
+        assert_eq!(
+            simulator.get_kernel_as_ref(0).base,
+            kernels::polynomial_nd(13, 2, &[4.0, 1.0, 1.0 / 3.0])
+        );
+        let growth_function = simulator.growth_function(0);
+        assert_eq!(growth_function.name(), "polynomial");
+        assert_eq!(growth_function.parameters(), [0.15, 0.015, 4.0]);
+        assert_eq!(simulator.dt(), 0.1);
+
+        species.params.kernel_core = 1;
+        species.params.growth_type = 2;
+        let simulator = Simulator::<ExpandedLenia>::from_species(&species, &[64, 64]).unwrap();
+        assert_eq!(
+            simulator.get_kernel_as_ref(0).base,
+            kernels::multi_gaussian_donut_nd(
+                13,
+                2,
+                &[0.25, 0.75],
+                &[1.0, 1.0 / 3.0],
+                &[EXPONENTIAL_CORE_STDDEV / 2.0; 2]
+            )
+        );
+        let growth_function = simulator.growth_function(0);
+        assert_eq!(growth_function.name(), "polynomial");
+        assert_eq!(growth_function.parameters(), [0.15, 0.005, 0.0]);
+
+        species.params.growth_type = 1;
+        let simulator = Simulator::<ExpandedLenia>::from_species(&species, &[64, 64]).unwrap();
+        assert_eq!(simulator.growth_function(0).name(), "standard_lenia");
+        assert_eq!(simulator.growth_function(0).parameters(), [0.15, 0.015]);
+
+        species.params.kernel_core = 2;
+        assert!(matches!(
+            species.rules(&[64, 64]),
+            Err(LeniaError::Unsupported(_))
+        ));
+        species.params.kernel_core = 1;
+        species.params.growth_type = 3;
+        assert!(matches!(
+            species.rules(&[64, 64]),
+            Err(LeniaError::Unsupported(_))
+        ));
+    }
+
+    /// Deterministic pattern of valid cell states, with empty cells at the ends of rows and an empty row.
+    fn pattern(shape: &[usize]) -> ndarray::ArrayD<f64> {
+        let mut pattern = test_util::field_with(shape, 1, |value| match value % 4 {
+            0 => 0.0,
+            _ => state((value >> 8) as u32 % 256),
+        });
+        pattern.index_axis_mut(Axis(0), 1).fill(0.0);
+        pattern
+            .slice_each_axis_mut(|axis| {
+                if axis.axis.index() == 0 {
+                    ndarray::Slice::from(0..1)
+                } else {
+                    ndarray::Slice::from(axis.len - 1..)
+                }
+            })
+            .fill(1.0);
+        pattern
+    }
+
+    #[test]
+    fn encoded_cells_decode_to_the_same_pattern() {
+        for shape in [&[9, 14][..], &[5, 6, 7], &[3, 4, 2, 5], &[3, 2, 3, 2, 4]] {
+            let pattern = pattern(shape);
+            let species = Species {
+                code: String::new(),
+                name: String::new(),
+                params: Species::from_json(ORBIUM).unwrap().params,
+                cells: encode_cells(&pattern),
+            };
+            let decoded = Species::from_json(&species.to_json().unwrap())
+                .unwrap()
+                .pattern(shape.len())
+                .unwrap();
+            assert_eq!(decoded, pattern, "{:?}", shape);
+        }
+    }
+
+    #[test]
+    fn simulations_round_trip_through_species() {
+        let mut species = Species::from_json(ORBIUM).unwrap();
+        species.params.peaks = vec![1.0, 1.0 / 3.0];
+        for (kernel_core, growth_type) in [(0, 0), (1, 1), (1, 2)] {
+            species.params.kernel_core = kernel_core;
+            species.params.growth_type = growth_type;
+            let mut simulator =
+                Simulator::<ExpandedLenia>::from_species(&species, &[64, 64]).unwrap();
+            let cells = pattern(&[20, 24]);
+            simulator
+                .get_channel_as_mut_ref(0)
+                .slice_mut(ndarray::s![10..30, 5..29])
+                .assign(&cells);
+            let described = simulator.species(&[10..30, 5..29]).unwrap();
+            assert_eq!(described.params, species.params);
+            assert_eq!(described.pattern(2).unwrap(), cells);
+        }
+    }
+
+    #[test]
+    fn simulations_without_an_equivalent_are_unsupported() {
+        let species = Species::from_json(ORBIUM).unwrap();
+        let region = [0..64, 0..64];
+        let simulator = || Simulator::<ExpandedLenia>::from_species(&species, &[64, 64]).unwrap();
+
+        let mut channels = simulator();
+        channels.set_channels(2);
+        let mut convolution_channels = simulator();
+        convolution_channels.set_convolution_channels(2);
+        let mut closure = simulator();
+        closure.set_growth_function(|x: f64| 2.0 * x - 1.0, 0);
+        let mut parameter_map = simulator();
+        parameter_map.set_growth_parameter_map(
+            0,
+            "mu",
+            ndarray::ArrayD::from_elem(vec![64, 64], 0.15),
+        );
+        let mut boundaries = simulator();
+        boundaries.set_boundary_conditions(&[BoundaryCondition::Zero; 2]);
+        let mut integrator = simulator();
+        integrator.set_integrator(Integrator::Heun);
+        let mut kernel = simulator();
+        kernel.set_kernel(kernels::smoothlife(13, 2, 0.5), 0);
+
+        for simulator in [
+            channels,
+            convolution_channels,
+            closure,
+            parameter_map,
+            boundaries,
+            integrator,
+            kernel,
+        ] {
+            assert!(matches!(
+                simulator.species(&region),
+                Err(LeniaError::Unsupported(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,1302 @@
+//! Simulation of many worlds of the same shape at once.
+
+use super::*;
+use lenias::{integrate, ChannelUpdate, Integrable};
+use num_complex::Complex;
+
+/// A batch of Lenia worlds, simulated at once.
+///
+/// All worlds share the same shape, channels, convolution channels, kernels, weights and integration step, in
+/// the manner of `ExpandedLenia`. They differ in the data of their channels, and optionally in the growth
+/// function of any convolution channel (see `set_world_growth_function()`), such as when running the same rules
+/// from many different seeds, or sweeping over growth function parameters.
+///
+/// The channels of all worlds are stacked along a leading batch axis. The ffts are planned once for the whole
+/// batch, the kernels are transformed once and shared by every world, and every step processes all of the worlds
+/// in parallel. This is much cheaper than a `Simulator` per world, each with its own ffts and kernels.
+///
+/// ### Example of initializing a `BatchSimulator`.
+/// Runs 64 worlds from different seeds, each with a different mu of the growth function.
+///
+/// ```
+/// # use road_to_lenia::{batch::BatchSimulator, growth_functions, kernels};
+/// # fn display(_: ndarray::ArrayViewD<f64>) {}
+/// let seeds = vec![ndarray::ArrayD::<f64>::zeros(vec![128, 128]); 64]; // fill with your data
+/// let mut batch = BatchSimulator::<f64>::new(64, &[128, 128]);
+/// batch.set_kernel(kernels::gaussian_donut_2d(13, 0.15), 0);
+/// batch.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+/// for (world, seed) in seeds.iter().enumerate() {
+///     batch.fill_channel(world, seed, 0);
+///     let mu = 0.12 + 0.001 * world as f64;
+///     batch.set_world_growth_function(world, growth_functions::StandardLenia::new(mu, 0.015), 0);
+/// }
+/// for _ in 0..10 {
+///     batch.iterate();
+///     display(batch.get_channel_as_ref(0, 0));
+/// }
+/// ```
+pub struct BatchSimulator<F: LeniaFloat = f64> {
+    worlds: usize,
+    shape: Vec<usize>,
+    dt: F,
+    // Simulated time, the sum of the `dt`s of all iterations.
+    time: F,
+    integrator: Integrator,
+    boundaries: Vec<BoundaryCondition>,
+    // Padding of each axis of the worlds before fft, needed for non-periodic boundaries.
+    // Large enough for the largest kernel along each axis.
+    padding: Vec<(usize, usize)>,
+    // The fields of the channels and convolution channels hold every world, stacked along the batch axis.
+    channels: Vec<Channel<F>>,
+    deltas: Vec<ndarray::ArrayD<F>>,
+    conv_channels: Vec<ConvolutionChannel<F>>,
+    convolutions: Vec<ndarray::ArrayD<F>>,
+    // Growth function of each world of each convolution channel, if it overrides the shared one.
+    growth_overrides: Vec<Vec<Option<Arc<dyn GrowthFunction<F>>>>>,
+    // Planned once for the whole batch, and used for every channel and convolution channel.
+    forward_fft: fft::ParPlannedFFTND<F>,
+    inverse_fft: fft::ParPlannedFFTND<F>,
+    // One spectrum per channel, shared by all convolution channels using it as a source.
+    spectra: Vec<ndarray::ArrayD<Complex<F>>>,
+    convolution_spectrum: ndarray::ArrayD<Complex<F>>,
+}
+
+impl<F: LeniaFloat> BatchSimulator<F> {
+    /// Initialize a batch of worlds, each with a single channel and a single convolution channel.
+    ///
+    /// The default kernel is a unit size and the default growth function is a "pass" function, as in `ExpandedLenia`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `worlds` - Number of worlds in the batch.
+    ///
+    /// * `channel_shape` - The shape of the channels of every world.
+    ///
+    /// ### Panics
+    ///
+    /// * If `worlds` is `0`.
+    ///
+    /// * If `channel_shape` is empty, or any of its axes has a length of `0`.
+    pub fn new(worlds: usize, channel_shape: &[usize]) -> Self {
+        match Self::try_new(worlds, channel_shape) {
+            Ok(batch) => batch,
+            Err(error) => panic!("BatchSimulator::new() - {}", error),
+        }
+    }
+
+    /// Same as `new()`, but returns an error instead of panicking.
+    pub fn try_new(worlds: usize, channel_shape: &[usize]) -> Result<Self, LeniaError> {
+        if worlds == 0 {
+            return Err(LeniaError::InvalidShape(
+                "The batch must have at least 1 world!".to_string(),
+            ));
+        }
+        if channel_shape.is_empty() {
+            return Err(LeniaError::InvalidShape(
+                "The channels must have at least 1 axis!".to_string(),
+            ));
+        }
+        check_shape(channel_shape)?;
+        let batch_shape = batched(worlds, channel_shape);
+        let spectrum = ndarray::ArrayD::from_elem(
+            fft::spectrum_shape(&batch_shape),
+            Complex::new(F::zero(), F::zero()),
+        );
+        Ok(BatchSimulator {
+            worlds,
+            shape: channel_shape.to_vec(),
+            dt: float(0.1),
+            time: F::zero(),
+            integrator: Integrator::Euler,
+            boundaries: vec![BoundaryCondition::Periodic; channel_shape.len()],
+            padding: vec![(0, 0); channel_shape.len()],
+            channels: vec![Channel {
+                field: ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero()),
+                weights: vec![F::one()],
+                weight_sum_reciprocal: F::one(),
+                update_mode: UpdateMode::Additive,
+                value_range: ValueRange::default(),
+            }],
+            deltas: vec![ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero())],
+            conv_channels: vec![ConvolutionChannel {
+                input_channel: 0,
+                field: ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero()),
+                kernel: Kernel::from(kernels::pass(channel_shape.len()), channel_shape),
+                growth: Arc::new(growth_functions::Pass::new(F::one())),
+                parameter_maps: Vec::new(),
+                backend: ConvolutionBackend::Automatic,
+            }],
+            convolutions: vec![ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero())],
+            growth_overrides: vec![vec![None; worlds]],
+            forward_fft: fft::ParPlannedFFTND::new_real_batched(&batch_shape, false),
+            inverse_fft: fft::ParPlannedFFTND::new_real_batched(&batch_shape, true),
+            spectra: vec![spectrum.clone()],
+            convolution_spectrum: spectrum,
+        })
+    }
+
+    /// Set the number of channels of every world.
+    ///
+    /// Works as `Simulator::set_channels()`, newly created channels are `0.0` in every world.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channels` - The number of channels every world should have.
+    ///
+    /// ### Panics
+    ///
+    /// * If `channels` is `0`.
+    ///
+    /// * If a convolution channel uses a dropped channel as its source.
+    pub fn set_channels(&mut self, channels: usize) {
+        if let Err(error) = self.try_set_channels(channels) {
+            panic!("BatchSimulator::set_channels: {}", error);
+        }
+    }
+
+    /// Same as `set_channels()`, but returns an error instead of panicking.
+    pub fn try_set_channels(&mut self, channels: usize) -> Result<(), LeniaError> {
+        if channels == 0 {
+            return Err(LeniaError::InvalidParameter(
+                "Attempting to set the number of channels to 0. This is not allowed.".to_string(),
+            ));
+        }
+        if channels <= self.channels.len() {
+            if let Some(conv_channel) = self
+                .conv_channels
+                .iter()
+                .find(|conv_channel| conv_channel.input_channel >= channels)
+            {
+                return Err(LeniaError::ChannelOutOfRange {
+                    channel: conv_channel.input_channel,
+                    channels,
+                });
+            }
+            self.channels.truncate(channels);
+            self.deltas.truncate(channels);
+            self.spectra.truncate(channels);
+            return Ok(());
+        }
+        let batch_shape = self.batch_shape();
+        for _ in self.channels.len()..channels {
+            self.channels.push(Channel {
+                field: ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero()),
+                weights: vec![F::zero(); self.conv_channels.len()],
+                weight_sum_reciprocal: F::zero(),
+                update_mode: UpdateMode::Additive,
+                value_range: ValueRange::default(),
+            });
+            self.deltas
+                .push(ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero()));
+            self.spectra.push(self.convolution_spectrum.clone());
+        }
+        Ok(())
+    }
+
+    /// Set the number of convolution channels of every world.
+    ///
+    /// Works as `Simulator::set_convolution_channels()`. Dropped convolution channels take their per-world
+    /// growth functions with them.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channels` - The number of convolution channels every world should have.
+    ///
+    /// ### Panics
+    ///
+    /// If `convolution_channels` is `0`.
+    pub fn set_convolution_channels(&mut self, convolution_channels: usize) {
+        if let Err(error) = self.try_set_convolution_channels(convolution_channels) {
+            panic!("BatchSimulator::set_convolution_channels: {}", error);
+        }
+    }
+
+    /// Same as `set_convolution_channels()`, but returns an error instead of panicking.
+    pub fn try_set_convolution_channels(
+        &mut self,
+        convolution_channels: usize,
+    ) -> Result<(), LeniaError> {
+        if convolution_channels == 0 {
+            return Err(LeniaError::InvalidParameter(
+                "Attempting to set the number of convolution channels to 0. This is not allowed."
+                    .to_string(),
+            ));
+        }
+        if convolution_channels <= self.conv_channels.len() {
+            self.conv_channels.truncate(convolution_channels);
+            self.convolutions.truncate(convolution_channels);
+            self.growth_overrides.truncate(convolution_channels);
+            for channel in &mut self.channels {
+                channel.weights.truncate(convolution_channels);
+                let sum: F = channel.weights.iter().copied().sum();
+                channel.weight_sum_reciprocal = F::one() / sum;
+            }
+            self.update_padding();
+            return Ok(());
+        }
+        let batch_shape = self.batch_shape();
+        let fft_shape = self.fft_shape();
+        for _ in self.conv_channels.len()..convolution_channels {
+            self.conv_channels.push(ConvolutionChannel {
+                input_channel: 0,
+                field: ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero()),
+                kernel: Kernel::from_padded(
+                    kernels::pass(self.shape.len()),
+                    &self.shape,
+                    &fft_shape,
+                ),
+                growth: Arc::new(growth_functions::Pass::new(F::zero())),
+                parameter_maps: Vec::new(),
+                backend: ConvolutionBackend::Automatic,
+            });
+            self.convolutions
+                .push(ndarray::ArrayD::from_elem(batch_shape.clone(), F::zero()));
+            self.growth_overrides.push(vec![None; self.worlds]);
+        }
+        for channel in &mut self.channels {
+            channel.weights.resize(convolution_channels, F::zero());
+        }
+        Ok(())
+    }
+
+    /// Set the source channel a given convolution channel should act on.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channel` - The convolution channel which will have its source changed.
+    ///
+    /// * `source_channel` - The channel that the convolution channel should use as its source
+    /// for convoluting.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the specified `source_channel` does not exist.
+    pub fn set_convolution_channel_source(
+        &mut self,
+        convolution_channel: usize,
+        source_channel: usize,
+    ) {
+        if let Err(error) =
+            self.try_set_convolution_channel_source(convolution_channel, source_channel)
+        {
+            panic!("BatchSimulator::set_convolution_channel_source: {}", error);
+        }
+    }
+
+    /// Same as `set_convolution_channel_source()`, but returns an error instead of panicking.
+    pub fn try_set_convolution_channel_source(
+        &mut self,
+        convolution_channel: usize,
+        source_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        self.check_channel(source_channel)?;
+        self.conv_channels[convolution_channel].input_channel = source_channel;
+        Ok(())
+    }
+
+    /// Set and make the kernel of the specified convolution channel, shared by every world.
+    ///
+    /// ### Parameters
+    ///
+    /// * `kernel` - n-dimensional array (`ndarray::ArrayD`), where the number of
+    /// dimensions / axes must match the number of dimensions / axes of the channels.
+    ///
+    /// * `convolution_channel` - The convolution channel to which the new kernel is to be assigned to.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the dimensionality of the kernel is not the same as the channels'
+    ///
+    /// * If the kernel is larger than the channels along any axis.
+    pub fn set_kernel(&mut self, kernel: ndarray::ArrayD<F>, convolution_channel: usize) {
+        if let Err(error) = self.try_set_kernel(kernel, convolution_channel) {
+            panic!("BatchSimulator::set_kernel: {}", error);
+        }
+    }
+
+    /// Same as `set_kernel()`, but returns an error instead of panicking.
+    pub fn try_set_kernel(
+        &mut self,
+        kernel: ndarray::ArrayD<F>,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        self.conv_channels[convolution_channel].kernel =
+            Kernel::try_from_padded(kernel, &self.shape, &self.fft_shape())?;
+        self.update_padding();
+        Ok(())
+    }
+
+    /// Set the growth function of the specified convolution channel, used by every world
+    /// that does not have a growth function of its own.
+    ///
+    /// ### Parameters
+    ///
+    /// * `growth_function` - Growth function to use, along with its parameters.
+    ///
+    /// * `convolution_channel` - The convoltution channel to which the new growth function is to be assigned.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the parameters of `growth_function` are invalid.
+    pub fn set_growth_function<G: GrowthFunction<F> + 'static>(
+        &mut self,
+        growth_function: G,
+        convolution_channel: usize,
+    ) {
+        if let Err(error) = self.try_set_growth_function(growth_function, convolution_channel) {
+            panic!("BatchSimulator::set_growth_function: {}", error);
+        }
+    }
+
+    /// Same as `set_growth_function()`, but returns an error instead of panicking.
+    pub fn try_set_growth_function<G: GrowthFunction<F> + 'static>(
+        &mut self,
+        growth_function: G,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        check_growth_function(&growth_function)?;
+        self.conv_channels[convolution_channel].growth = Arc::new(growth_function);
+        Ok(())
+    }
+
+    /// Override the growth function of the specified convolution channel for a single world.
+    ///
+    /// ### Parameters
+    ///
+    /// * `world` - Index of the world whose growth function is overridden.
+    ///
+    /// * `growth_function` - Growth function to use in the world, along with its parameters.
+    ///
+    /// * `convolution_channel` - The convoltution channel to which the new growth function is to be assigned.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `world` or `convolution_channel` does not exist.
+    ///
+    /// * If the parameters of `growth_function` are invalid.
+    pub fn set_world_growth_function<G: GrowthFunction<F> + 'static>(
+        &mut self,
+        world: usize,
+        growth_function: G,
+        convolution_channel: usize,
+    ) {
+        if let Err(error) =
+            self.try_set_world_growth_function(world, growth_function, convolution_channel)
+        {
+            panic!("BatchSimulator::set_world_growth_function: {}", error);
+        }
+    }
+
+    /// Same as `set_world_growth_function()`, but returns an error instead of panicking.
+    pub fn try_set_world_growth_function<G: GrowthFunction<F> + 'static>(
+        &mut self,
+        world: usize,
+        growth_function: G,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_world(world)?;
+        self.check_convolution_channel(convolution_channel)?;
+        check_growth_function(&growth_function)?;
+        self.growth_overrides[convolution_channel][world] = Some(Arc::new(growth_function));
+        Ok(())
+    }
+
+    /// Make a single world use the shared growth function of the specified convolution channel again.
+    ///
+    /// ### Parameters
+    ///
+    /// * `world` - Index of the world whose growth function override is removed.
+    ///
+    /// * `convolution_channel` - The convolution channel whose growth function override is removed.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `world` or `convolution_channel` does not exist.
+    pub fn clear_world_growth_function(&mut self, world: usize, convolution_channel: usize) {
+        if let Err(error) = self.try_clear_world_growth_function(world, convolution_channel) {
+            panic!("BatchSimulator::clear_world_growth_function: {}", error);
+        }
+    }
+
+    /// Same as `clear_world_growth_function()`, but returns an error instead of panicking.
+    pub fn try_clear_world_growth_function(
+        &mut self,
+        world: usize,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_world(world)?;
+        self.check_convolution_channel(convolution_channel)?;
+        self.growth_overrides[convolution_channel][world] = None;
+        Ok(())
+    }
+
+    /// Set the way the convolution of the specified convolution channel is computed.
+    ///
+    /// ### Parameters
+    ///
+    /// * `backend` - Convolution method to use.
+    ///
+    /// * `convolution_channel` - The convolution channel whose convolution method is set.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn set_convolution_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        convolution_channel: usize,
+    ) {
+        if let Err(error) = self.try_set_convolution_backend(backend, convolution_channel) {
+            panic!("BatchSimulator::set_convolution_backend: {}", error);
+        }
+    }
+
+    /// Same as `set_convolution_backend()`, but returns an error instead of panicking.
+    pub fn try_set_convolution_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        self.conv_channels[convolution_channel].backend = backend;
+        Ok(())
+    }
+
+    /// Set the boundary condition of each axis of the channels of every world.
+    ///
+    /// ### Parameters
+    ///
+    /// * `boundaries` - Boundary condition for each axis of the channels.
+    ///
+    /// ### Panics
+    ///
+    /// If the length of `boundaries` does not match the dimensionality of the channels.
+    pub fn set_boundary_conditions(&mut self, boundaries: &[BoundaryCondition]) {
+        if let Err(error) = self.try_set_boundary_conditions(boundaries) {
+            panic!("BatchSimulator::set_boundary_conditions: {}", error);
+        }
+    }
+
+    /// Same as `set_boundary_conditions()`, but returns an error instead of panicking.
+    pub fn try_set_boundary_conditions(
+        &mut self,
+        boundaries: &[BoundaryCondition],
+    ) -> Result<(), LeniaError> {
+        if boundaries.len() != self.shape.len() {
+            return Err(LeniaError::DimensionalityMismatch {
+                expected: self.shape.len(),
+                found: boundaries.len(),
+            });
+        }
+        self.boundaries = boundaries.to_vec();
+        self.update_padding();
+        Ok(())
+    }
+
+    /// Set the scheme used to advance the channels by one integration step.
+    ///
+    /// ### Parameters
+    ///
+    /// * `integrator` - The integration scheme to use.
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        self.integrator = integrator;
+    }
+
+    /// Set the way the weighted sum of the convolution channels updates a channel.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - Index of the channel to change the update mode of.
+    ///
+    /// * `mode` - The new update mode of the channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn set_update_mode(&mut self, channel: usize, mode: UpdateMode) {
+        if let Err(error) = self.try_set_update_mode(channel, mode) {
+            panic!("BatchSimulator::set_update_mode: {}", error);
+        }
+    }
+
+    /// Same as `set_update_mode()`, but returns an error instead of panicking.
+    pub fn try_set_update_mode(
+        &mut self,
+        channel: usize,
+        mode: UpdateMode,
+    ) -> Result<(), LeniaError> {
+        self.check_channel(channel)?;
+        self.channels[channel].update_mode = mode;
+        Ok(())
+    }
+
+    /// Set the range of values that a channel is kept within.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - Index of the channel to change the range of.
+    ///
+    /// * `range` - The new range of values of the channel.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `channel` does not exist.
+    ///
+    /// * If `range` is invalid, see `Simulator::set_value_range()`.
+    pub fn set_value_range(&mut self, channel: usize, range: ValueRange<F>) {
+        if let Err(error) = self.try_set_value_range(channel, range) {
+            panic!("BatchSimulator::set_value_range: {}", error);
+        }
+    }
+
+    /// Same as `set_value_range()`, but returns an error instead of panicking.
+    pub fn try_set_value_range(
+        &mut self,
+        channel: usize,
+        range: ValueRange<F>,
+    ) -> Result<(), LeniaError> {
+        self.check_channel(channel)?;
+        check_value_range(&range)?;
+        self.channels[channel].value_range = range;
+        Ok(())
+    }
+
+    /// Set the convolution channel weights for a specific channel.
+    ///
+    /// Works as `Simulator::set_weights()`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - The channel, which the new weights will be assigned to.
+    ///
+    /// * `weights` - The weights to assign. Index in the array corresponds to
+    /// the index of the convoution channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn set_weights(&mut self, channel: usize, weights: &[F]) {
+        if let Err(error) = self.try_set_weights(channel, weights) {
+            panic!("BatchSimulator::set_weights: {}", error);
+        }
+    }
+
+    /// Same as `set_weights()`, but returns an error instead of panicking.
+    pub fn try_set_weights(&mut self, channel: usize, weights: &[F]) -> Result<(), LeniaError> {
+        self.check_channel(channel)?;
+        let mut weights = weights.to_vec();
+        weights.resize(self.conv_channels.len(), F::zero());
+        let sum = weights
+            .iter()
+            .fold(F::zero(), |sum, weight| sum + weight.abs());
+        self.channels[channel].weights = weights;
+        self.channels[channel].weight_sum_reciprocal = F::one() / sum;
+        Ok(())
+    }
+
+    /// Set the integration step (a.k.a. timestep) parameter `dt` of every world.
+    pub fn set_dt(&mut self, dt: F) {
+        self.dt = dt;
+    }
+
+    /// Set the simulated time, such as when restarting a simulation from a saved state.
+    pub fn set_time(&mut self, time: F) {
+        self.time = time;
+    }
+
+    /// Performs a single iteration of every world.
+    pub fn iterate(&mut self) {
+        integrate(self, self.integrator, self.dt);
+        self.time += self.dt;
+    }
+
+    /// Fills a channel of a single world with user data.
+    ///
+    /// ### Parameters
+    ///
+    /// * `world` - Index of the world whose channel is filled.
+    ///
+    /// * `data` - Reference to the n-dimensional array (`ndarray::ArrayD`) of data to fill the channel with.
+    ///
+    /// * `channel` - Index of the channel to fill.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `world` or `channel` does not exist.
+    ///
+    /// * If the shape of `data` is not the same as the shape of the channels.
+    pub fn fill_channel(&mut self, world: usize, data: &ndarray::ArrayD<F>, channel: usize) {
+        if let Err(error) = self.try_fill_channel(world, data, channel) {
+            panic!("BatchSimulator::fill_channel: {}", error);
+        }
+    }
+
+    /// Same as `fill_channel()`, but returns an error instead of panicking.
+    pub fn try_fill_channel(
+        &mut self,
+        world: usize,
+        data: &ndarray::ArrayD<F>,
+        channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_world(world)?;
+        self.check_channel(channel)?;
+        if data.shape() != self.shape.as_slice() {
+            return Err(LeniaError::ShapeMismatch {
+                expected: self.shape.clone(),
+                found: data.shape().to_vec(),
+            });
+        }
+        self.channels[channel]
+            .field
+            .index_axis_mut(Axis(0), world)
+            .assign(data);
+        Ok(())
+    }
+
+    /// Retrieve a view of a single world's channel data.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `world` or `channel` does not exist.
+    pub fn get_channel_as_ref(&self, world: usize, channel: usize) -> ndarray::ArrayViewD<F> {
+        match self.try_get_channel_as_ref(world, channel) {
+            Ok(view) => view,
+            Err(error) => panic!("BatchSimulator::get_channel_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_channel_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_channel_as_ref(
+        &self,
+        world: usize,
+        channel: usize,
+    ) -> Result<ndarray::ArrayViewD<F>, LeniaError> {
+        self.check_world(world)?;
+        self.check_channel(channel)?;
+        Ok(self.channels[channel].field.index_axis(Axis(0), world))
+    }
+
+    /// Retrieve a mutable view of a single world's channel data.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `world` or `channel` does not exist.
+    pub fn get_channel_as_mut_ref(
+        &mut self,
+        world: usize,
+        channel: usize,
+    ) -> ndarray::ArrayViewMutD<F> {
+        match self.try_get_channel_as_mut_ref(world, channel) {
+            Ok(view) => view,
+            Err(error) => panic!("BatchSimulator::get_channel_as_mut_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_channel_as_mut_ref()`, but returns an error instead of panicking.
+    pub fn try_get_channel_as_mut_ref(
+        &mut self,
+        world: usize,
+        channel: usize,
+    ) -> Result<ndarray::ArrayViewMutD<F>, LeniaError> {
+        self.check_world(world)?;
+        self.check_channel(channel)?;
+        Ok(self.channels[channel].field.index_axis_mut(Axis(0), world))
+    }
+
+    /// Retrieve a reference to the data of a channel of every world, stacked along the leading batch axis.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn get_batch_as_ref(&self, channel: usize) -> &ndarray::ArrayD<F> {
+        match self.try_get_batch_as_ref(channel) {
+            Ok(data) => data,
+            Err(error) => panic!("BatchSimulator::get_batch_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_batch_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_batch_as_ref(&self, channel: usize) -> Result<&ndarray::ArrayD<F>, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(&self.channels[channel].field)
+    }
+
+    /// Retrieve a view of a single world's channel "deltas", see `Simulator::get_deltas_as_ref()`.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `world` or `channel` does not exist.
+    pub fn get_deltas_as_ref(&self, world: usize, channel: usize) -> ndarray::ArrayViewD<F> {
+        match self.try_get_deltas_as_ref(world, channel) {
+            Ok(view) => view,
+            Err(error) => panic!("BatchSimulator::get_deltas_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_deltas_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_deltas_as_ref(
+        &self,
+        world: usize,
+        channel: usize,
+    ) -> Result<ndarray::ArrayViewD<F>, LeniaError> {
+        self.check_world(world)?;
+        self.check_channel(channel)?;
+        Ok(self.deltas[channel].index_axis(Axis(0), world))
+    }
+
+    /// Retrieve a view of a single world's convolution result of the specified convolution channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `world` or `convolution_channel` does not exist.
+    pub fn get_convolved_as_ref(
+        &self,
+        world: usize,
+        convolution_channel: usize,
+    ) -> ndarray::ArrayViewD<F> {
+        match self.try_get_convolved_as_ref(world, convolution_channel) {
+            Ok(view) => view,
+            Err(error) => panic!("BatchSimulator::get_convolved_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_convolved_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_convolved_as_ref(
+        &self,
+        world: usize,
+        convolution_channel: usize,
+    ) -> Result<ndarray::ArrayViewD<F>, LeniaError> {
+        self.check_world(world)?;
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.convolutions[convolution_channel].index_axis(Axis(0), world))
+    }
+
+    /// Retrieve a view of a single world's "activations" of the specified convolution channel,
+    /// the convolution results passed through the growth function.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `world` or `convolution_channel` does not exist.
+    pub fn get_activated_as_ref(
+        &self,
+        world: usize,
+        convolution_channel: usize,
+    ) -> ndarray::ArrayViewD<F> {
+        match self.try_get_activated_as_ref(world, convolution_channel) {
+            Ok(view) => view,
+            Err(error) => panic!("BatchSimulator::get_activated_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_activated_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_activated_as_ref(
+        &self,
+        world: usize,
+        convolution_channel: usize,
+    ) -> Result<ndarray::ArrayViewD<F>, LeniaError> {
+        self.check_world(world)?;
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.conv_channels[convolution_channel]
+            .field
+            .index_axis(Axis(0), world))
+    }
+
+    /// Retrieve the growth function a single world uses for the specified convolution channel,
+    /// its own if it has one, the shared one otherwise.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `world` or `convolution_channel` does not exist.
+    pub fn growth_function(
+        &self,
+        world: usize,
+        convolution_channel: usize,
+    ) -> &dyn GrowthFunction<F> {
+        match self.try_growth_function(world, convolution_channel) {
+            Ok(growth_function) => growth_function,
+            Err(error) => panic!("BatchSimulator::growth_function: {}", error),
+        }
+    }
+
+    /// Same as `growth_function()`, but returns an error instead of panicking.
+    pub fn try_growth_function(
+        &self,
+        world: usize,
+        convolution_channel: usize,
+    ) -> Result<&dyn GrowthFunction<F>, LeniaError> {
+        self.check_world(world)?;
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(match &self.growth_overrides[convolution_channel][world] {
+            Some(growth) => &**growth,
+            None => &*self.conv_channels[convolution_channel].growth,
+        })
+    }
+
+    /// Retrieve the kernel shared by every world for the specified convolution channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn get_kernel_as_ref(&self, convolution_channel: usize) -> &Kernel<F> {
+        match self.try_get_kernel_as_ref(convolution_channel) {
+            Ok(kernel) => kernel,
+            Err(error) => panic!("BatchSimulator::get_kernel_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_kernel_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_kernel_as_ref(
+        &self,
+        convolution_channel: usize,
+    ) -> Result<&Kernel<F>, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(&self.conv_channels[convolution_channel].kernel)
+    }
+
+    /// Get the way the convolution of the specified convolution channel is computed.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn convolution_backend(&self, convolution_channel: usize) -> ConvolutionBackend {
+        match self.try_convolution_backend(convolution_channel) {
+            Ok(backend) => backend,
+            Err(error) => panic!("BatchSimulator::convolution_backend: {}", error),
+        }
+    }
+
+    /// Same as `convolution_backend()`, but returns an error instead of panicking.
+    pub fn try_convolution_backend(
+        &self,
+        convolution_channel: usize,
+    ) -> Result<ConvolutionBackend, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.conv_channels[convolution_channel].backend)
+    }
+
+    /// Get the boundary condition of each axis of the channels.
+    pub fn boundary_conditions(&self) -> &[BoundaryCondition] {
+        &self.boundaries
+    }
+
+    /// Get the scheme used to advance the channels by one integration step.
+    pub fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    /// Get the way the weighted sum of the convolution channels updates a channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn update_mode(&self, channel: usize) -> UpdateMode {
+        match self.try_update_mode(channel) {
+            Ok(mode) => mode,
+            Err(error) => panic!("BatchSimulator::update_mode: {}", error),
+        }
+    }
+
+    /// Same as `update_mode()`, but returns an error instead of panicking.
+    pub fn try_update_mode(&self, channel: usize) -> Result<UpdateMode, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.channels[channel].update_mode)
+    }
+
+    /// Get the range of values that a channel is kept within.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn value_range(&self, channel: usize) -> ValueRange<F> {
+        match self.try_value_range(channel) {
+            Ok(range) => range,
+            Err(error) => panic!("BatchSimulator::value_range: {}", error),
+        }
+    }
+
+    /// Same as `value_range()`, but returns an error instead of panicking.
+    pub fn try_value_range(&self, channel: usize) -> Result<ValueRange<F>, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.channels[channel].value_range)
+    }
+
+    /// Get the simulated time, the sum of the integration steps of all iterations so far.
+    pub fn time(&self) -> F {
+        self.time
+    }
+
+    /// Get the integration step (a.k.a. timestep) parameter `dt`.
+    pub fn dt(&self) -> F {
+        self.dt
+    }
+
+    /// Get the shape of the channels of every world.
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// Get the number of worlds in the batch.
+    pub fn worlds(&self) -> usize {
+        self.worlds
+    }
+
+    /// Get the number of channels of every world.
+    pub fn channels(&self) -> usize {
+        self.channels.len()
+    }
+
+    /// Get the number of convolution channels of every world.
+    pub fn convolution_channels(&self) -> usize {
+        self.conv_channels.len()
+    }
+
+    /// Shape of the channels of every world, with the batch axis in front.
+    fn batch_shape(&self) -> Vec<usize> {
+        batched(self.worlds, &self.shape)
+    }
+
+    /// Shape of the channels of a single world after padding.
+    fn fft_shape(&self) -> Vec<usize> {
+        self.shape
+            .iter()
+            .zip(&self.padding)
+            .map(|(n, (before, after))| n + before + after)
+            .collect()
+    }
+
+    /// Re-plans the ffts and re-makes the kernels if the kernels or the boundary conditions
+    /// require a different padding of the channels.
+    fn update_padding(&mut self) {
+        let mut kernel_shape = vec![1; self.shape.len()];
+        for conv_channel in &self.conv_channels {
+            for (max, len) in kernel_shape
+                .iter_mut()
+                .zip(conv_channel.kernel.base.shape())
+            {
+                *max = (*max).max(*len);
+            }
+        }
+        let padding = fft_padding(&kernel_shape, &self.boundaries);
+        if padding == self.padding {
+            return;
+        }
+        self.padding = padding;
+        let fft_shape = self.fft_shape();
+        let batch_fft_shape = batched(self.worlds, &fft_shape);
+        self.forward_fft = fft::ParPlannedFFTND::new_real_batched(&batch_fft_shape, false);
+        self.inverse_fft = fft::ParPlannedFFTND::new_real_batched(&batch_fft_shape, true);
+        self.convolution_spectrum = ndarray::ArrayD::from_elem(
+            fft::spectrum_shape(&batch_fft_shape),
+            Complex::new(F::zero(), F::zero()),
+        );
+        for spectrum in &mut self.spectra {
+            *spectrum = self.convolution_spectrum.clone();
+        }
+        for conv_channel in &mut self.conv_channels {
+            conv_channel.kernel =
+                Kernel::from_padded(conv_channel.kernel.base.clone(), &self.shape, &fft_shape);
+        }
+    }
+
+    fn check_world(&self, world: usize) -> Result<(), LeniaError> {
+        if world >= self.worlds {
+            return Err(LeniaError::WorldOutOfRange {
+                world,
+                worlds: self.worlds,
+            });
+        }
+        Ok(())
+    }
+
+    fn check_channel(&self, channel: usize) -> Result<(), LeniaError> {
+        check_channel_index(channel, self.channels.len())
+    }
+
+    fn check_convolution_channel(&self, convolution_channel: usize) -> Result<(), LeniaError> {
+        check_convolution_channel_index(convolution_channel, self.conv_channels.len())
+    }
+}
+
+impl<F: LeniaFloat> Integrable<F> for BatchSimulator<F> {
+    fn update_deltas(&mut self) {
+        // The batch axis is neither padded nor convolved along.
+        let padding = batched((0, 0), &self.padding);
+        let boundaries = batched(BoundaryCondition::Periodic, &self.boundaries);
+        let channel_size = self.shape.iter().product();
+        let is_direct: Vec<bool> = self
+            .conv_channels
+            .iter()
+            .map(|conv_channel| {
+                conv_channel
+                    .backend
+                    .is_direct(&conv_channel.kernel, channel_size)
+            })
+            .collect();
+
+        // Forward fft each channel used as a source only once, for all worlds at once.
+        let mut is_source = vec![false; self.channels.len()];
+        for (conv_channel, is_direct) in self.conv_channels.iter().zip(&is_direct) {
+            if !*is_direct {
+                is_source[conv_channel.input_channel] = true;
+            }
+        }
+        for ((channel, spectrum), is_source) in self
+            .channels
+            .iter()
+            .zip(self.spectra.iter_mut())
+            .zip(&is_source)
+        {
+            if *is_source {
+                transform_padded(
+                    &mut self.forward_fft,
+                    &channel.field,
+                    &padding,
+                    &boundaries,
+                    spectrum,
+                );
+            }
+        }
+
+        for (((conv_channel, convolution), overrides), is_direct) in self
+            .conv_channels
+            .iter_mut()
+            .zip(self.convolutions.iter_mut())
+            .zip(&self.growth_overrides)
+            .zip(&is_direct)
+        {
+            if *is_direct {
+                let kernel = conv_channel
+                    .kernel
+                    .normalized
+                    .view()
+                    .insert_axis(Axis(0))
+                    .to_owned();
+                direct::convolve(
+                    &self.channels[conv_channel.input_channel].field,
+                    &kernel,
+                    convolution,
+                    &boundaries,
+                );
+            } else {
+                // Multiply the spectrum of the source channel of every world with the shared kernel
+                let kernel = conv_channel
+                    .kernel
+                    .transformed
+                    .broadcast(self.convolution_spectrum.raw_dim())
+                    .unwrap();
+                ndarray::Zip::from(&mut self.convolution_spectrum)
+                    .and(&self.spectra[conv_channel.input_channel])
+                    .and(kernel)
+                    .par_for_each(|a, b, c| {
+                        a.re = (b.re * c.re) - (b.im * c.im);
+                        a.im = (b.re * c.im) + (b.im * c.re);
+                    });
+                transform_padded_inverse(
+                    &mut self.inverse_fft,
+                    &mut self.convolution_spectrum,
+                    &padding,
+                    convolution,
+                );
+            }
+
+            // Apply the growth function of each world
+            let shared = &*conv_channel.growth;
+            ndarray::Zip::from(conv_channel.field.axis_iter_mut(Axis(0)))
+                .and(convolution.axis_iter(Axis(0)))
+                .and(overrides.as_slice())
+                .par_for_each(|mut grown, convolved, growth| {
+                    let growth = growth.as_deref().unwrap_or(shared);
+                    ndarray::Zip::from(&mut grown)
+                        .and(&convolved)
+                        .for_each(|a, b| {
+                            *a = growth.growth(*b);
+                        });
+                });
+        }
+
+        // Weighted sums of the convolution channels
+        let conv_channels = &self.conv_channels;
+        for (channel, deltas) in self.channels.iter().zip(self.deltas.iter_mut()) {
+            deltas.fill(F::zero());
+            for (weight, convolution_channel) in channel.weights.iter().zip(conv_channels) {
+                ndarray::Zip::from(&mut *deltas)
+                    .and(&convolution_channel.field)
+                    .par_for_each(|a, b| {
+                        *a += *b * *weight;
+                    });
+            }
+        }
+    }
+
+    fn fields_and_deltas(&mut self) -> Vec<ChannelUpdate<'_, F>> {
+        self.channels
+            .iter_mut()
+            .zip(self.deltas.iter())
+            .map(|(channel, deltas)| {
+                (
+                    &mut channel.field,
+                    deltas,
+                    channel.update_mode,
+                    channel.value_range,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Prepends the batch axis entry `first` to the per-world `rest`.
+fn batched<T: Clone>(first: T, rest: &[T]) -> Vec<T> {
+    let mut out = Vec::with_capacity(rest.len() + 1);
+    out.push(first);
+    out.extend_from_slice(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::ExpandedLenia;
+    use crate::test_util::field;
+
+    const WORLDS: usize = 3;
+
+    /// Deterministic array with values in `0.0..1.0`, about a quarter of which are `0.0`.
+    /// Growth function of the second convolution channel of a world, overridden for all but the first world.
+    fn world_growth(world: usize) -> growth_functions::StandardLenia<f64> {
+        growth_functions::StandardLenia::new(0.2 + 0.02 * world as f64, 0.03)
+    }
+
+    fn batch(
+        shape: &[usize],
+        boundaries: &[BoundaryCondition],
+        backend: ConvolutionBackend,
+    ) -> BatchSimulator<f64> {
+        let mut batch = BatchSimulator::<f64>::new(WORLDS, shape);
+        batch.set_channels(2);
+        batch.set_convolution_channels(2);
+        batch.set_convolution_channel_source(1, 1);
+        batch.set_kernel(kernels::gaussian_donut_2d(5, 0.15), 0);
+        batch.set_kernel(field(&[3, 4], 99) + 0.01, 1);
+        batch.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+        batch.set_growth_function(world_growth(0), 1);
+        batch.set_weights(0, &[1.0, 0.5]);
+        batch.set_weights(1, &[0.5, 1.0]);
+        batch.set_boundary_conditions(boundaries);
+        batch.set_integrator(Integrator::Heun);
+        batch.set_dt(0.2);
+        for conv_channel in 0..2 {
+            batch.set_convolution_backend(backend, conv_channel);
+        }
+        for world in 0..WORLDS {
+            if world > 0 {
+                batch.set_world_growth_function(world, world_growth(world), 1);
+            }
+            for channel in 0..2 {
+                batch.fill_channel(world, &field(shape, (world * 2 + channel) as u64), channel);
+            }
+        }
+        batch
+    }
+
+    fn simulator(
+        shape: &[usize],
+        boundaries: &[BoundaryCondition],
+        backend: ConvolutionBackend,
+        world: usize,
+    ) -> Simulator<ExpandedLenia> {
+        let mut simulator = Simulator::<ExpandedLenia>::new(shape);
+        simulator.set_channels(2);
+        simulator.set_convolution_channels(2);
+        simulator.set_convolution_channel_source(1, 1);
+        simulator.set_kernel(kernels::gaussian_donut_2d(5, 0.15), 0);
+        simulator.set_kernel(field(&[3, 4], 99) + 0.01, 1);
+        simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+        simulator.set_growth_function(world_growth(world), 1);
+        simulator.set_weights(0, &[1.0, 0.5]);
+        simulator.set_weights(1, &[0.5, 1.0]);
+        simulator.set_boundary_conditions(boundaries);
+        simulator.set_integrator(Integrator::Heun);
+        simulator.set_dt(0.2);
+        for conv_channel in 0..2 {
+            simulator.set_convolution_backend(backend, conv_channel);
+        }
+        for channel in 0..2 {
+            simulator.fill_channel(&field(shape, (world * 2 + channel) as u64), channel);
+        }
+        simulator
+    }
+
+    #[test]
+    fn batch_matches_separate_simulators() {
+        let shape = [16, 15];
+        let boundaries = [
+            [BoundaryCondition::Periodic; 2],
+            [BoundaryCondition::Zero, BoundaryCondition::Mirror],
+        ];
+        for backend in [ConvolutionBackend::Fft, ConvolutionBackend::Direct] {
+            for boundaries in &boundaries {
+                let mut batch = batch(&shape, boundaries, backend);
+                let mut simulators: Vec<_> = (0..WORLDS)
+                    .map(|world| simulator(&shape, boundaries, backend, world))
+                    .collect();
+                for _ in 0..5 {
+                    batch.iterate();
+                    for simulator in &mut simulators {
+                        simulator.iterate();
+                    }
+                }
+                for (world, simulator) in simulators.iter().enumerate() {
+                    for channel in 0..2 {
+                        let batched = batch.get_channel_as_ref(world, channel);
+                        let separate = simulator.get_channel_as_ref(channel);
+                        for (a, b) in batched.iter().zip(separate) {
+                            assert!(
+                                (a - b).abs() < 1e-12,
+                                "{:?} backend with {:?} boundaries, world {}, channel {}: {} != {}",
+                                backend,
+                                boundaries,
+                                world,
+                                channel,
+                                a,
+                                b
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shrinking_channels_keeps_the_sources_of_convolution_channels_valid() {
+        for backend in [ConvolutionBackend::Fft, ConvolutionBackend::Direct] {
+            let mut batch = BatchSimulator::<f64>::new(3, &[16, 16]);
+            batch.set_convolution_backend(backend, 0);
+            batch.set_channels(3);
+            batch.set_convolution_channel_source(0, 2);
+            assert!(matches!(
+                batch.try_set_channels(1),
+                Err(LeniaError::ChannelOutOfRange {
+                    channel: 2,
+                    channels: 1
+                })
+            ));
+            assert_eq!(batch.channels(), 3);
+            batch.iterate();
+
+            batch.set_convolution_channel_source(0, 0);
+            batch.set_channels(1);
+            batch.iterate();
+            assert_eq!(batch.channels(), 1);
+        }
+    }
+}
@@ -0,0 +1,817 @@
+//! Binary checkpoints of the complete state of a `Simulator`, for stopping and resuming long simulations bit-for-bit.
+//!
+//! A checkpoint starts with a header of the magic bytes `LENIACKP`, the version of the checkpoint format, the version
+//! of the crate that wrote it, the size of the floating-point type in bytes and the shape of the channels. The state
+//! of the `Simulator` and its `Lenia` instance follows, written by `Lenia::write_state()`. All numbers are
+//! little-endian, and floating-point numbers are stored at the precision they were simulated at.
+//!
+//! Kernels are stored by their base, and re-made along with the ffts when restoring. Growth functions are stored by
+//! their name and parameters, and re-made by a `rules::Registry` when restoring, such that closures can not be restored.
+
+use super::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use rules::{KernelRules, Registry};
+use std::convert::TryInto;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of every checkpoint.
+const MAGIC: &[u8; 8] = b"LENIACKP";
+
+/// Version of the checkpoint format, increased whenever the format changes.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Largest number of elements allocated up front when reading a sequence, such that a corrupted length
+/// fails on the end of the checkpoint instead of on allocation.
+const MAX_PREALLOCATION: usize = 1 << 16;
+
+/// Writes the state of a `Simulator` and its `Lenia` instance into a checkpoint.
+pub struct StateWriter<'a> {
+    writer: &'a mut dyn Write,
+}
+
+impl<'a> StateWriter<'a> {
+    /// Write the checkpoint into `writer`.
+    pub fn new(writer: &'a mut dyn Write) -> Self {
+        StateWriter { writer }
+    }
+
+    /// Write raw bytes, without their length.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), LeniaError> {
+        self.writer.write_all(bytes)?;
+        Ok(())
+    }
+
+    /// Write a single byte.
+    pub fn write_u8(&mut self, value: u8) -> Result<(), LeniaError> {
+        self.write_bytes(&[value])
+    }
+
+    /// Write a `u32` in little-endian order.
+    pub fn write_u32(&mut self, value: u32) -> Result<(), LeniaError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    /// Write a `u64` in little-endian order.
+    pub fn write_u64(&mut self, value: u64) -> Result<(), LeniaError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    /// Write a `usize` as a `u64`, independent of the platform.
+    pub fn write_usize(&mut self, value: usize) -> Result<(), LeniaError> {
+        self.write_u64(value as u64)
+    }
+
+    /// Write a `bool` as a single byte.
+    pub fn write_bool(&mut self, value: bool) -> Result<(), LeniaError> {
+        self.write_u8(value as u8)
+    }
+
+    /// Write a string, preceded by its length in bytes.
+    pub fn write_str(&mut self, value: &str) -> Result<(), LeniaError> {
+        self.write_usize(value.len())?;
+        self.write_bytes(value.as_bytes())
+    }
+
+    /// Write a floating-point number at its own precision.
+    pub fn write_float<F: LeniaFloat>(&mut self, value: F) -> Result<(), LeniaError> {
+        if std::mem::size_of::<F>() == 4 {
+            self.write_bytes(&value.to_f32().unwrap().to_le_bytes())
+        } else {
+            self.write_bytes(&value.to_f64().unwrap().to_le_bytes())
+        }
+    }
+
+    /// Write a slice of floating-point numbers, preceded by its length.
+    pub fn write_floats<F: LeniaFloat>(&mut self, values: &[F]) -> Result<(), LeniaError> {
+        self.write_usize(values.len())?;
+        for value in values {
+            self.write_float(*value)?;
+        }
+        Ok(())
+    }
+
+    /// Write a shape, preceded by its number of axes.
+    pub fn write_shape(&mut self, shape: &[usize]) -> Result<(), LeniaError> {
+        self.write_usize(shape.len())?;
+        for len in shape {
+            self.write_usize(*len)?;
+        }
+        Ok(())
+    }
+
+    /// Write an n-dimensional array, its shape followed by its values in row-major order.
+    pub fn write_array<F: LeniaFloat>(
+        &mut self,
+        array: &ndarray::ArrayD<F>,
+    ) -> Result<(), LeniaError> {
+        self.write_shape(array.shape())?;
+        for value in array.iter() {
+            self.write_float(*value)?;
+        }
+        Ok(())
+    }
+
+    /// Write the boundary conditions of every axis, preceded by the number of axes.
+    pub fn write_boundaries(&mut self, boundaries: &[BoundaryCondition]) -> Result<(), LeniaError> {
+        self.write_usize(boundaries.len())?;
+        for boundary in boundaries {
+            self.write_u8(match boundary {
+                BoundaryCondition::Periodic => 0,
+                BoundaryCondition::Zero => 1,
+                BoundaryCondition::Mirror => 2,
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Write an integrator as a single byte.
+    pub fn write_integrator(&mut self, integrator: Integrator) -> Result<(), LeniaError> {
+        self.write_u8(match integrator {
+            Integrator::Euler => 0,
+            Integrator::Midpoint => 1,
+            Integrator::Heun => 2,
+            Integrator::RungeKutta4 => 3,
+        })
+    }
+
+    /// Write the data, weights, update mode and value range of a channel.
+    pub fn write_channel<F: LeniaFloat>(&mut self, channel: &Channel<F>) -> Result<(), LeniaError> {
+        self.write_array(&channel.field)?;
+        self.write_floats(&channel.weights)?;
+        self.write_float(channel.weight_sum_reciprocal)?;
+        self.write_u8(match channel.update_mode {
+            UpdateMode::Additive => 0,
+            UpdateMode::Asymptotic => 1,
+        })?;
+        self.write_float(channel.value_range.min)?;
+        self.write_float(channel.value_range.max)?;
+        self.write_u8(match channel.value_range.saturation {
+            Saturation::Clamp => 0,
+            Saturation::Sigmoid => 1,
+            Saturation::Tanh => 2,
+        })
+    }
+
+    /// Write the source, backend, kernel base, growth function, parameter maps and data of a convolution channel.
+    pub fn write_convolution_channel<F: LeniaFloat>(
+        &mut self,
+        convolution_channel: &ConvolutionChannel<F>,
+    ) -> Result<(), LeniaError> {
+        self.write_usize(convolution_channel.input_channel)?;
+        self.write_u8(match convolution_channel.backend {
+            ConvolutionBackend::Automatic => 0,
+            ConvolutionBackend::Fft => 1,
+            ConvolutionBackend::Direct => 2,
+        })?;
+        self.write_array(&convolution_channel.kernel.base)?;
+        self.write_str(convolution_channel.growth.name())?;
+        self.write_floats(&convolution_channel.growth.parameters())?;
+        self.write_usize(convolution_channel.parameter_maps.len())?;
+        for map in &convolution_channel.parameter_maps {
+            self.write_bool(map.is_some())?;
+            if let Some(map) = map {
+                self.write_array(map)?;
+            }
+        }
+        self.write_array(&convolution_channel.field)
+    }
+
+    /// Write the stochastic terms of the update step, if any, and the exact position of the random number generator.
+    pub fn write_stochasticity<F: LeniaFloat>(
+        &mut self,
+        stochasticity: &Option<Stochasticity<F>>,
+        rng: &ChaCha12Rng,
+    ) -> Result<(), LeniaError> {
+        self.write_bool(stochasticity.is_some())?;
+        if let Some(stochasticity) = stochasticity {
+            self.write_float(stochasticity.noise)?;
+            self.write_float(stochasticity.update_probability)?;
+            self.write_u64(stochasticity.seed)?;
+        }
+        self.write_bytes(&rng.get_seed())?;
+        self.write_u64(rng.get_stream())?;
+        self.write_bytes(&rng.get_word_pos().to_le_bytes())
+    }
+}
+
+/// Reads the state of a `Simulator` and its `Lenia` instance from a checkpoint.
+///
+/// Every function returns `LeniaError::Format` if the checkpoint ends early or holds an invalid value.
+pub struct StateReader<'a> {
+    reader: &'a mut dyn Read,
+}
+
+impl<'a> StateReader<'a> {
+    /// Read the checkpoint from `reader`.
+    pub fn new(reader: &'a mut dyn Read) -> Self {
+        StateReader { reader }
+    }
+
+    /// Read exactly as many raw bytes as fit in `bytes`.
+    pub fn read_bytes(&mut self, bytes: &mut [u8]) -> Result<(), LeniaError> {
+        self.reader.read_exact(bytes).map_err(|error| {
+            if error.kind() == std::io::ErrorKind::UnexpectedEof {
+                LeniaError::Format(
+                    "The checkpoint ends unexpectedly, it may be truncated.".to_string(),
+                )
+            } else {
+                LeniaError::Io(error)
+            }
+        })
+    }
+
+    /// Read a single byte.
+    pub fn read_u8(&mut self) -> Result<u8, LeniaError> {
+        let mut bytes = [0; 1];
+        self.read_bytes(&mut bytes)?;
+        Ok(bytes[0])
+    }
+
+    /// Read a `u32` in little-endian order.
+    pub fn read_u32(&mut self) -> Result<u32, LeniaError> {
+        let mut bytes = [0; 4];
+        self.read_bytes(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    /// Read a `u64` in little-endian order.
+    pub fn read_u64(&mut self) -> Result<u64, LeniaError> {
+        let mut bytes = [0; 8];
+        self.read_bytes(&mut bytes)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    /// Read a `usize` written as a `u64`, failing if it does not fit on this platform.
+    pub fn read_usize(&mut self) -> Result<usize, LeniaError> {
+        let value = self.read_u64()?;
+        value.try_into().map_err(|_| {
+            LeniaError::Format(format!(
+                "The checkpoint holds a length ({}) too large for this platform.",
+                value
+            ))
+        })
+    }
+
+    /// Read a `bool` written as a single byte.
+    pub fn read_bool(&mut self) -> Result<bool, LeniaError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(invalid("boolean", value)),
+        }
+    }
+
+    /// Read a string written by `StateWriter::write_str()`.
+    pub fn read_string(&mut self) -> Result<String, LeniaError> {
+        let len = self.read_usize()?;
+        let mut bytes = Vec::with_capacity(len.min(MAX_PREALLOCATION));
+        self.reader
+            .take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(LeniaError::Io)?;
+        if bytes.len() != len {
+            return Err(LeniaError::Format(
+                "The checkpoint ends unexpectedly, it may be truncated.".to_string(),
+            ));
+        }
+        String::from_utf8(bytes)
+            .map_err(|_| LeniaError::Format("The checkpoint holds an invalid string.".to_string()))
+    }
+
+    /// Read a floating-point number written at the precision of `F`.
+    pub fn read_float<F: LeniaFloat>(&mut self) -> Result<F, LeniaError> {
+        if std::mem::size_of::<F>() == 4 {
+            let mut bytes = [0; 4];
+            self.read_bytes(&mut bytes)?;
+            Ok(F::from(f32::from_le_bytes(bytes)).unwrap())
+        } else {
+            let mut bytes = [0; 8];
+            self.read_bytes(&mut bytes)?;
+            Ok(F::from(f64::from_le_bytes(bytes)).unwrap())
+        }
+    }
+
+    /// Read a slice of floating-point numbers written by `StateWriter::write_floats()`.
+    pub fn read_floats<F: LeniaFloat>(&mut self) -> Result<Vec<F>, LeniaError> {
+        let len = self.read_usize()?;
+        let mut values = Vec::with_capacity(len.min(MAX_PREALLOCATION));
+        for _ in 0..len {
+            values.push(self.read_float()?);
+        }
+        Ok(values)
+    }
+
+    /// Read a shape written by `StateWriter::write_shape()`.
+    pub fn read_shape(&mut self) -> Result<Vec<usize>, LeniaError> {
+        let axes = self.read_usize()?;
+        let mut shape = Vec::with_capacity(axes.min(MAX_PREALLOCATION));
+        for _ in 0..axes {
+            shape.push(self.read_usize()?);
+        }
+        Ok(shape)
+    }
+
+    /// Read an n-dimensional array written by `StateWriter::write_array()`.
+    pub fn read_array<F: LeniaFloat>(&mut self) -> Result<ndarray::ArrayD<F>, LeniaError> {
+        let shape = self.read_shape()?;
+        let len = shape
+            .iter()
+            .try_fold(1_usize, |len, axis| len.checked_mul(*axis))
+            .ok_or_else(|| {
+                LeniaError::Format(
+                    "The checkpoint holds an array too large for this platform.".to_string(),
+                )
+            })?;
+        let mut values = Vec::with_capacity(len.min(MAX_PREALLOCATION));
+        for _ in 0..len {
+            values.push(self.read_float()?);
+        }
+        Ok(ndarray::ArrayD::from_shape_vec(shape, values).unwrap())
+    }
+
+    /// Read an n-dimensional array that must have the given shape.
+    pub fn read_array_of_shape<F: LeniaFloat>(
+        &mut self,
+        shape: &[usize],
+    ) -> Result<ndarray::ArrayD<F>, LeniaError> {
+        let array = self.read_array()?;
+        if array.shape() != shape {
+            return Err(LeniaError::ShapeMismatch {
+                expected: shape.to_vec(),
+                found: array.shape().to_vec(),
+            });
+        }
+        Ok(array)
+    }
+
+    /// Read boundary conditions written by `StateWriter::write_boundaries()`.
+    pub fn read_boundaries(&mut self) -> Result<Vec<BoundaryCondition>, LeniaError> {
+        let len = self.read_usize()?;
+        let mut boundaries = Vec::with_capacity(len.min(MAX_PREALLOCATION));
+        for _ in 0..len {
+            boundaries.push(match self.read_u8()? {
+                0 => BoundaryCondition::Periodic,
+                1 => BoundaryCondition::Zero,
+                2 => BoundaryCondition::Mirror,
+                value => return Err(invalid("boundary condition", value)),
+            });
+        }
+        Ok(boundaries)
+    }
+
+    /// Read an integrator written by `StateWriter::write_integrator()`.
+    pub fn read_integrator(&mut self) -> Result<Integrator, LeniaError> {
+        match self.read_u8()? {
+            0 => Ok(Integrator::Euler),
+            1 => Ok(Integrator::Midpoint),
+            2 => Ok(Integrator::Heun),
+            3 => Ok(Integrator::RungeKutta4),
+            value => Err(invalid("integrator", value)),
+        }
+    }
+
+    /// Read the data, weights, update mode and value range of a channel into `channel`, whose data must
+    /// already have the right shape.
+    pub fn read_channel<F: LeniaFloat>(
+        &mut self,
+        channel: &mut Channel<F>,
+    ) -> Result<(), LeniaError> {
+        channel.field = self.read_array_of_shape(channel.field.shape())?;
+        channel.weights = self.read_floats()?;
+        channel.weight_sum_reciprocal = self.read_float()?;
+        channel.update_mode = match self.read_u8()? {
+            0 => UpdateMode::Additive,
+            1 => UpdateMode::Asymptotic,
+            value => return Err(invalid("update mode", value)),
+        };
+        channel.value_range.min = self.read_float()?;
+        channel.value_range.max = self.read_float()?;
+        channel.value_range.saturation = match self.read_u8()? {
+            0 => Saturation::Clamp,
+            1 => Saturation::Sigmoid,
+            2 => Saturation::Tanh,
+            value => return Err(invalid("saturation", value)),
+        };
+        channel.value_range.validate().map_err(|error| {
+            LeniaError::Format(format!(
+                "The checkpoint holds an invalid value range: {}.",
+                error
+            ))
+        })
+    }
+
+    /// Read a convolution channel into `convolution_channel`, whose data must already have the right shape.
+    ///
+    /// The kernel is not made, as its padding depends on the `Lenia` instance, and its base is returned instead.
+    pub fn read_convolution_channel<F: LeniaFloat>(
+        &mut self,
+        convolution_channel: &mut ConvolutionChannel<F>,
+        registry: &Registry<F>,
+    ) -> Result<ndarray::ArrayD<F>, LeniaError> {
+        convolution_channel.input_channel = self.read_usize()?;
+        convolution_channel.backend = match self.read_u8()? {
+            0 => ConvolutionBackend::Automatic,
+            1 => ConvolutionBackend::Fft,
+            2 => ConvolutionBackend::Direct,
+            value => return Err(invalid("convolution backend", value)),
+        };
+        let kernel = self.read_array()?;
+        let growth_function = rules::GrowthFunctionRules {
+            name: self.read_string()?,
+            parameters: self
+                .read_floats::<F>()?
+                .iter()
+                .map(|value| value.to_f64().unwrap())
+                .collect(),
+        };
+        convolution_channel.growth = registry.make_growth_function(&growth_function)?;
+        let maps = self.read_usize()?;
+        let parameters = convolution_channel.growth.parameters().len();
+        if maps > parameters {
+            return Err(LeniaError::Format(format!(
+                "The checkpoint holds {} parameter maps for growth function \"{}\", which has {} parameters.",
+                maps,
+                convolution_channel.growth.name(),
+                parameters
+            )));
+        }
+        convolution_channel.parameter_maps = Vec::with_capacity(maps.min(MAX_PREALLOCATION));
+        for _ in 0..maps {
+            let map = if self.read_bool()? {
+                Some(self.read_array_of_shape(convolution_channel.field.shape())?)
+            } else {
+                None
+            };
+            convolution_channel.parameter_maps.push(map);
+        }
+        convolution_channel.field = self.read_array_of_shape(convolution_channel.field.shape())?;
+        Ok(kernel)
+    }
+
+    /// Read the stochastic terms of the update step, if any, and the exact position of the random number generator.
+    pub fn read_stochasticity<F: LeniaFloat>(
+        &mut self,
+    ) -> Result<(Option<Stochasticity<F>>, ChaCha12Rng), LeniaError> {
+        let stochasticity = if self.read_bool()? {
+            Some(Stochasticity {
+                noise: self.read_float()?,
+                update_probability: self.read_float()?,
+                seed: self.read_u64()?,
+            })
+        } else {
+            None
+        };
+        let mut seed = [0; 32];
+        self.read_bytes(&mut seed)?;
+        let mut rng = ChaCha12Rng::from_seed(seed);
+        rng.set_stream(self.read_u64()?);
+        let mut word_pos = [0; 16];
+        self.read_bytes(&mut word_pos)?;
+        rng.set_word_pos(u128::from_le_bytes(word_pos));
+        Ok((stochasticity, rng))
+    }
+
+    /// Read a tag written by `StateWriter::write_str()`, which must be `expected`.
+    pub fn expect_tag(&mut self, expected: &str) -> Result<(), LeniaError> {
+        let found = self.read_string()?;
+        if found != expected {
+            return Err(LeniaError::Format(format!(
+                "The checkpoint holds a \"{}\" where \"{}\" was expected.",
+                found, expected
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn invalid(kind: &str, value: u8) -> LeniaError {
+    LeniaError::Format(format!(
+        "The checkpoint holds an invalid {} ({}).",
+        kind, value
+    ))
+}
+
+/// Name of the floating-point type of the given size in bytes.
+fn precision_name(size: u8) -> String {
+    match size {
+        4 => "f32".to_string(),
+        8 => "f64".to_string(),
+        size => format!("{}-byte floating-point", size),
+    }
+}
+
+/// Reads the header of a checkpoint, returning the shape of the channels.
+fn read_header<F: LeniaFloat>(reader: &mut StateReader) -> Result<Vec<usize>, LeniaError> {
+    let mut magic = [0; 8];
+    reader.read_bytes(&mut magic)?;
+    if &magic != MAGIC {
+        return Err(LeniaError::Format("Not a Lenia checkpoint.".to_string()));
+    }
+    let version = reader.read_u32()?;
+    let crate_version = reader.read_string()?;
+    if version != FORMAT_VERSION {
+        return Err(LeniaError::VersionMismatch {
+            expected: FORMAT_VERSION,
+            found: version,
+            written_by: crate_version,
+        });
+    }
+    let precision = reader.read_u8()?;
+    if precision as usize != std::mem::size_of::<F>() {
+        return Err(LeniaError::PrecisionMismatch {
+            expected: precision_name(std::mem::size_of::<F>() as u8),
+            found: precision_name(precision),
+        });
+    }
+    reader.read_shape()
+}
+
+impl<L: NewLenia> Simulator<L> {
+    /// Initialize a Lenia simulator from a checkpoint file written by `save_checkpoint()`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the checkpoint file.
+    ///
+    /// * `registry` - Growth functions of the simulation, `rules::Registry::new()` unless custom growth functions
+    /// were used.
+    ///
+    /// ### Errors
+    ///
+    /// * `LeniaError::PrecisionMismatch` if the checkpoint was saved at a different precision than `L::Float`.
+    ///
+    /// * `LeniaError::VersionMismatch` if the checkpoint was saved in a different version of the checkpoint format.
+    ///
+    /// * `LeniaError::Format` if the checkpoint was saved from a different type of Lenia, or is not a valid checkpoint.
+    pub fn load_checkpoint<P: AsRef<Path>>(
+        file_path: P,
+        registry: &Registry<L::Float>,
+    ) -> Result<Self, LeniaError> {
+        let mut file = BufReader::new(std::fs::File::open(file_path)?);
+        let mut reader = StateReader::new(&mut file);
+        let shape = read_header::<L::Float>(&mut reader)?;
+        let mut simulator = Self::try_new(&shape)?;
+        simulator.read_state(&mut reader, registry)?;
+        Ok(simulator)
+    }
+}
+
+impl<L: Lenia> Simulator<L> {
+    /// Write the complete state of the simulation to a checkpoint file.
+    ///
+    /// Resuming from the checkpoint with `load_checkpoint()` or `restore_checkpoint()` continues the simulation
+    /// exactly as if it had never stopped.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the checkpoint file, which is overwritten if it exists.
+    pub fn save_checkpoint<P: AsRef<Path>>(&self, file_path: P) -> Result<(), LeniaError> {
+        let mut file = BufWriter::new(std::fs::File::create(file_path)?);
+        self.write_checkpoint(&mut file)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Same as `save_checkpoint()`, but writes the checkpoint into any writer.
+    pub fn write_checkpoint(&self, writer: &mut dyn Write) -> Result<(), LeniaError> {
+        let mut writer = StateWriter::new(writer);
+        writer.write_bytes(MAGIC)?;
+        writer.write_u32(FORMAT_VERSION)?;
+        writer.write_str(env!("CARGO_PKG_VERSION"))?;
+        writer.write_u8(std::mem::size_of::<L::Float>() as u8)?;
+        writer.write_shape(self.shape())?;
+        writer.write_float(self.time)?;
+        writer.write_u64(self.steps)?;
+        writer.write_bool(self.adaptive_dt.is_some())?;
+        if let Some(adaptive_dt) = self.adaptive_dt {
+            writer.write_float(adaptive_dt.tolerance)?;
+            writer.write_float(adaptive_dt.min_dt)?;
+            writer.write_float(adaptive_dt.max_dt)?;
+        }
+        writer.write_usize(self.kernel_rules.len())?;
+        for kernel_rules in &self.kernel_rules {
+            writer.write_bool(kernel_rules.is_some())?;
+            if let Some(kernel_rules) = kernel_rules {
+                writer.write_str(&serde_json::to_string(kernel_rules).unwrap())?;
+            }
+        }
+        self.sim.write_state(&mut writer)
+    }
+
+    /// Restore the complete state of the simulation from a checkpoint file written by `save_checkpoint()`.
+    ///
+    /// Allows restoring into a `Simulator` of a `Box<dyn Lenia>`, which must be of the same type of Lenia and shape
+    /// as the one the checkpoint was saved from. If restoring fails, the simulation is left in an unspecified state.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the checkpoint file.
+    ///
+    /// * `registry` - Growth functions of the simulation, `rules::Registry::new()` unless custom growth functions
+    /// were used.
+    ///
+    /// ### Errors
+    ///
+    /// The same as `load_checkpoint()`, and `LeniaError::ShapeMismatch` if the checkpoint was saved from channels
+    /// of a different shape.
+    pub fn restore_checkpoint<P: AsRef<Path>>(
+        &mut self,
+        file_path: P,
+        registry: &Registry<L::Float>,
+    ) -> Result<(), LeniaError> {
+        let mut file = BufReader::new(std::fs::File::open(file_path)?);
+        self.read_checkpoint(&mut file, registry)
+    }
+
+    /// Same as `restore_checkpoint()`, but reads the checkpoint from any reader.
+    pub fn read_checkpoint(
+        &mut self,
+        reader: &mut dyn Read,
+        registry: &Registry<L::Float>,
+    ) -> Result<(), LeniaError> {
+        let mut reader = StateReader::new(reader);
+        let shape = read_header::<L::Float>(&mut reader)?;
+        if shape != self.shape() {
+            return Err(LeniaError::ShapeMismatch {
+                expected: self.shape().to_vec(),
+                found: shape,
+            });
+        }
+        self.read_state(&mut reader, registry)
+    }
+
+    /// Reads everything after the header of a checkpoint.
+    fn read_state(
+        &mut self,
+        reader: &mut StateReader,
+        registry: &Registry<L::Float>,
+    ) -> Result<(), LeniaError> {
+        self.time = reader.read_float()?;
+        self.steps = reader.read_u64()?;
+        self.adaptive_dt = if reader.read_bool()? {
+            Some(AdaptiveDt {
+                tolerance: reader.read_float()?,
+                min_dt: reader.read_float()?,
+                max_dt: reader.read_float()?,
+            })
+        } else {
+            None
+        };
+        let kernels = reader.read_usize()?;
+        let mut kernel_rules = Vec::with_capacity(kernels.min(MAX_PREALLOCATION));
+        for _ in 0..kernels {
+            kernel_rules.push(if reader.read_bool()? {
+                let kernel: KernelRules =
+                    serde_json::from_str(&reader.read_string()?).map_err(|error| {
+                        LeniaError::Format(format!(
+                            "Invalid kernel rules in the checkpoint: {}.",
+                            error
+                        ))
+                    })?;
+                Some(kernel)
+            } else {
+                None
+            });
+        }
+        self.kernel_rules = kernel_rules;
+        self.sim.read_state(reader, registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::ExpandedLenia;
+
+    /// Two channels feeding each other through two kernels, integrated with RK4 and stochastic terms.
+    fn simulator() -> Simulator<ExpandedLenia> {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[32, 32]);
+        simulator.set_channels(2);
+        simulator.set_convolution_channels(2);
+        simulator.set_convolution_channel_source(1, 1);
+        simulator.set_kernel(kernels::gaussian_donut_2d(7, 0.15), 0);
+        simulator.set_kernel(kernels::gaussian_donut_2d(5, 0.2), 1);
+        simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+        simulator.set_growth_function(growth_functions::StandardLenia::new(0.2, 0.03), 1);
+        simulator.set_weights(0, &[1.0, 0.5]);
+        simulator.set_weights(1, &[0.5, 1.0]);
+        simulator.set_integrator(Integrator::RungeKutta4);
+        simulator.set_stochasticity(0.01, 0.8, 7);
+        simulator.set_dt(0.1);
+        for channel in 0..2 {
+            let seed = ndarray::ArrayD::from_shape_fn(vec![32, 32], |index| {
+                ((index[0] * 31 + index[1] * 17 + channel * 7) % 13) as f64 / 13.0
+            });
+            simulator.fill_channel(&seed, channel);
+        }
+        simulator
+    }
+
+    fn checkpoint(simulator: &Simulator<ExpandedLenia>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        simulator.write_checkpoint(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn resuming_from_a_checkpoint_is_bit_for_bit() {
+        let mut original = simulator();
+        for _ in 0..3 {
+            original.iterate();
+        }
+        let path =
+            std::env::temp_dir().join(format!("lenia_checkpoint_{}.ckp", std::process::id()));
+        original.save_checkpoint(&path).unwrap();
+        let mut loaded =
+            Simulator::<ExpandedLenia>::load_checkpoint(&path, &Registry::new()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut restored = Simulator::<ExpandedLenia>::new(&[32, 32]);
+        restored
+            .read_checkpoint(&mut checkpoint(&original).as_slice(), &Registry::new())
+            .unwrap();
+
+        for _ in 0..5 {
+            original.iterate();
+            loaded.iterate();
+            restored.iterate();
+        }
+        for resumed in [&loaded, &restored] {
+            assert_eq!(resumed.time().to_bits(), original.time().to_bits());
+            assert_eq!(resumed.steps(), original.steps());
+            for channel in 0..2 {
+                let expected = original.get_channel_as_ref(channel);
+                let found = resumed.get_channel_as_ref(channel);
+                assert!(expected
+                    .iter()
+                    .zip(found)
+                    .all(|(a, b)| a.to_bits() == b.to_bits()));
+            }
+        }
+    }
+
+    #[test]
+    fn restoring_at_a_different_precision_is_an_error() {
+        let bytes = checkpoint(&simulator());
+        let mut restored = Simulator::<ExpandedLenia<f32>>::new(&[32, 32]);
+        assert!(matches!(
+            restored.read_checkpoint(&mut bytes.as_slice(), &Registry::new()),
+            Err(LeniaError::PrecisionMismatch { expected, found }) if expected == "f32" && found == "f64"
+        ));
+    }
+
+    #[test]
+    fn restoring_a_different_format_version_is_an_error() {
+        let mut bytes = checkpoint(&simulator());
+        bytes[MAGIC.len()..MAGIC.len() + 4].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let mut restored = Simulator::<ExpandedLenia>::new(&[32, 32]);
+        assert!(matches!(
+            restored.read_checkpoint(&mut bytes.as_slice(), &Registry::new()),
+            Err(LeniaError::VersionMismatch { expected, found, .. })
+                if expected == FORMAT_VERSION && found == FORMAT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn restoring_a_truncated_checkpoint_is_an_error() {
+        let bytes = checkpoint(&simulator());
+        for length in [4, 40, bytes.len() / 2, bytes.len() - 1] {
+            let mut restored = Simulator::<ExpandedLenia>::new(&[32, 32]);
+            assert!(matches!(
+                restored.read_checkpoint(&mut &bytes[..length], &Registry::new()),
+                Err(LeniaError::Format(_))
+            ));
+        }
+
+        // A parameter map of the sigma of a growth function that turns out to have no parameters.
+        let mut mapped = simulator();
+        mapped.set_growth_parameter_map(0, "sigma", ndarray::ArrayD::from_elem(vec![32, 32], 0.02));
+        let bytes = checkpoint(&mapped);
+        let mut registry = Registry::new();
+        registry.register_growth_function("standard_lenia", |_| {
+            Ok(Arc::new(growth_functions::ConwayGameOfLife) as Arc<dyn GrowthFunction<f64>>)
+        });
+        let mut restored = Simulator::<ExpandedLenia>::new(&[32, 32]);
+        assert!(matches!(
+            restored.read_checkpoint(&mut bytes.as_slice(), &registry),
+            Err(LeniaError::Format(message)) if message.contains("parameter maps")
+        ));
+
+        // A value range whose bounds are reversed.
+        let mut ranged = simulator();
+        ranged.set_value_range(1, ValueRange::new(-0.375, 1.625, Saturation::Sigmoid));
+        let mut bytes = checkpoint(&ranged);
+        let bounds = [(-0.375f64).to_le_bytes(), 1.625f64.to_le_bytes()].concat();
+        let start = bytes
+            .windows(bounds.len())
+            .position(|window| window == bounds.as_slice())
+            .unwrap();
+        bytes[start + 8..start + 16].copy_from_slice(&(-1.0f64).to_le_bytes());
+        let mut restored = Simulator::<ExpandedLenia>::new(&[32, 32]);
+        assert!(matches!(
+            restored.read_checkpoint(&mut bytes.as_slice(), &Registry::new()),
+            Err(LeniaError::Format(message)) if message.contains("value range")
+        ));
+    }
+}
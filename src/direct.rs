@@ -0,0 +1,237 @@
+//! Contains the required functionality for performing n-dimensional direct (spatial) convolutions.
+//!
+//! Direct convolution is cheaper than the fft-based convolution for kernels with only a handful
+//! of elements, such as the Moore neighborhood of Conway's game of life.
+
+use crate::{pad, BoundaryCondition, LeniaFloat};
+use rayon::prelude::*;
+
+/// Convolves `input` with `kernel` directly, treating the edges of `input` according to `boundaries`.
+///
+/// The result is the same as that of the fft-based convolution with a `Kernel`, which centers the
+/// kernel on each point, the center of an even-length axis of the kernel being at index `len / 2`.
+///
+/// ### Parameters
+///
+/// * `input` - Data to convolve.
+///
+/// * `kernel` - Kernel to convolve `input` with, usually the `normalized` version of a `Kernel`.
+///
+/// * `output` - Array into which the result is written. Must have the same shape as `input`.
+///
+/// * `boundaries` - Boundary condition of each axis of `input`.
+///
+/// ### Panics
+///
+/// * If the number of axes of `input`, `kernel` and `output` are not equal.
+///
+/// * If any axis of `kernel` is longer than the corresponding axis of `input`.
+pub fn convolve<F: LeniaFloat>(
+    input: &ndarray::ArrayD<F>,
+    kernel: &ndarray::ArrayD<F>,
+    output: &mut ndarray::ArrayD<F>,
+    boundaries: &[BoundaryCondition],
+) {
+    if input.shape().len() != kernel.shape().len() || input.shape() != output.shape() {
+        panic!("direct::convolve() - Shapes of input, kernel and output do not match!");
+    }
+    for (i, (k, n)) in kernel.shape().iter().zip(input.shape()).enumerate() {
+        if k > n {
+            panic!(
+                "direct::convolve() - Kernel is larger than the input in axis {}!",
+                i
+            );
+        }
+    }
+
+    if boundaries
+        .iter()
+        .all(|boundary| *boundary == BoundaryCondition::Periodic)
+    {
+        convolve_periodic(input, kernel, output);
+        return;
+    }
+
+    // Pad the input according to the boundary conditions, such that every element the kernel covers is in-bounds.
+    let padding: Vec<(usize, usize)> = kernel
+        .shape()
+        .iter()
+        .map(|k| (k - 1 - k / 2, k / 2))
+        .collect();
+    let padded = pad(input, &padding, boundaries);
+    let padded = padded.as_standard_layout();
+    let padded_data = padded.as_slice().unwrap();
+    let padded_strides: Vec<usize> = padded.strides().iter().map(|s| *s as usize).collect();
+
+    // Offsets into the padded data of the window covered by every non-zero kernel element.
+    let taps: Vec<(usize, F)> = kernel
+        .indexed_iter()
+        .filter(|(_, weight)| **weight != F::zero())
+        .map(|(index, weight)| {
+            let mut offset = 0;
+            for (axis, stride) in padded_strides.iter().enumerate() {
+                offset += (kernel.shape()[axis] - 1 - index[axis]) * stride;
+            }
+            (offset, *weight)
+        })
+        .collect();
+
+    // Accumulate the contributions row by row, rows being contiguous along the last axis.
+    let shape = input.shape();
+    let row_length = shape[shape.len() - 1];
+    output
+        .as_slice_mut()
+        .expect("direct::convolve() - Output is not in standard layout!")
+        .par_chunks_mut(row_length)
+        .enumerate()
+        .for_each(|(row, out)| {
+            let mut row_offset = 0;
+            let mut remainder = row;
+            for axis in (0..shape.len() - 1).rev() {
+                row_offset += (remainder % shape[axis]) * padded_strides[axis];
+                remainder /= shape[axis];
+            }
+            out.fill(F::zero());
+            for (offset, weight) in &taps {
+                let start = row_offset + offset;
+                for (a, b) in out.iter_mut().zip(&padded_data[start..start + row_length]) {
+                    *a += *weight * *b;
+                }
+            }
+        });
+}
+
+/// Same as `convolve()` with periodic boundaries on every axis, wrapping the indices around instead of padding
+/// the input, which saves copying the whole input on every call.
+fn convolve_periodic<F: LeniaFloat>(
+    input: &ndarray::ArrayD<F>,
+    kernel: &ndarray::ArrayD<F>,
+    output: &mut ndarray::ArrayD<F>,
+) {
+    let input = input.as_standard_layout();
+    let input_data = input.as_slice().unwrap();
+    let shape = input.shape();
+    let last_axis = shape.len() - 1;
+    let row_length = shape[last_axis];
+    let strides: Vec<usize> = input.strides().iter().map(|s| *s as usize).collect();
+
+    // Offset of the element each non-zero kernel element multiplies, relative to the output element.
+    let taps: Vec<(Vec<isize>, F)> = kernel
+        .indexed_iter()
+        .filter(|(_, weight)| **weight != F::zero())
+        .map(|(index, weight)| {
+            let offsets = (0..kernel.ndim())
+                .map(|axis| (kernel.shape()[axis] / 2) as isize - index[axis] as isize)
+                .collect();
+            (offsets, *weight)
+        })
+        .collect();
+
+    output
+        .as_slice_mut()
+        .expect("direct::convolve() - Output is not in standard layout!")
+        .par_chunks_mut(row_length)
+        .enumerate()
+        .for_each(|(row, out)| {
+            let mut position = vec![0; last_axis];
+            let mut remainder = row;
+            for axis in (0..last_axis).rev() {
+                position[axis] = (remainder % shape[axis]) as isize;
+                remainder /= shape[axis];
+            }
+            out.fill(F::zero());
+            for (offsets, weight) in &taps {
+                let mut start = 0;
+                for axis in 0..last_axis {
+                    start += (position[axis] + offsets[axis]).rem_euclid(shape[axis] as isize)
+                        as usize
+                        * strides[axis];
+                }
+                let source = &input_data[start..start + row_length];
+                // Element `i` of the row reads element `i + shift` of the source row, wrapping around.
+                let shift = offsets[last_axis].rem_euclid(row_length as isize) as usize;
+                let (head, tail) = out.split_at_mut(row_length - shift);
+                for (a, b) in head.iter_mut().zip(&source[shift..]) {
+                    *a += *weight * *b;
+                }
+                for (a, b) in tail.iter_mut().zip(&source[..shift]) {
+                    *a += *weight * *b;
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lenias::ExpandedLenia;
+    use crate::test_util::field;
+    use crate::{BoundaryCondition, ConvolutionBackend, Simulator};
+
+    /// Deterministic array with values in `0.0..1.0`, about a quarter of which are `0.0`.
+    fn convolved(
+        channel: &ndarray::ArrayD<f64>,
+        kernel: &ndarray::ArrayD<f64>,
+        boundaries: &[BoundaryCondition],
+        backend: ConvolutionBackend,
+    ) -> ndarray::ArrayD<f64> {
+        let mut simulator = Simulator::<ExpandedLenia>::new(channel.shape());
+        simulator.set_kernel(kernel.clone(), 0);
+        simulator.set_boundary_conditions(boundaries);
+        simulator.set_convolution_backend(backend, 0);
+        simulator.fill_channel(channel, 0);
+        simulator.iterate();
+        simulator.get_convolved_as_ref(0).clone()
+    }
+
+    #[test]
+    fn direct_convolution_matches_fft_convolution() {
+        let boundaries = [
+            BoundaryCondition::Periodic,
+            BoundaryCondition::Zero,
+            BoundaryCondition::Mirror,
+        ];
+        let cases: [(&[usize], &[usize]); 7] = [
+            (&[17], &[3]),
+            (&[16], &[4]),
+            (&[16, 15], &[3, 3]),
+            (&[15, 15], &[5, 4]),
+            (&[12, 13], &[2, 7]),
+            (&[9, 10, 11], &[3, 3, 3]),
+            (&[8, 9, 10], &[4, 5, 2]),
+        ];
+        for (seed, (shape, kernel_shape)) in cases.iter().enumerate() {
+            let channel = field(shape, seed as u64);
+            let kernel = field(kernel_shape, seed as u64 + 100) + 0.01;
+            for boundary in boundaries {
+                let boundaries = vec![boundary; shape.len()];
+                let direct = convolved(&channel, &kernel, &boundaries, ConvolutionBackend::Direct);
+                let fft = convolved(&channel, &kernel, &boundaries, ConvolutionBackend::Fft);
+                for (a, b) in direct.iter().zip(&fft) {
+                    assert!(
+                        (a - b).abs() < 1e-12,
+                        "{:?} with a {:?} kernel and {:?} boundaries: {} != {}",
+                        shape,
+                        kernel_shape,
+                        boundary,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn direct_convolution_skips_zero_kernel_elements() {
+        let channel = field(&[11, 12], 7);
+        let mut kernel = ndarray::ArrayD::from_elem(vec![5, 5], 0.0);
+        kernel[[0, 4]] = 1.0;
+        kernel[[2, 2]] = 0.5;
+        let boundaries = [BoundaryCondition::Periodic; 2];
+        let direct = convolved(&channel, &kernel, &boundaries, ConvolutionBackend::Direct);
+        let fft = convolved(&channel, &kernel, &boundaries, ConvolutionBackend::Fft);
+        for (a, b) in direct.iter().zip(&fft) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
@@ -3,7 +3,9 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+use crate::{LeniaError, LeniaFloat};
 use rayon::prelude::*;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
 use rustfft::num_complex::Complex;
 use rustfft::{Fft, FftDirection, FftPlanner};
 use std::iter::FromIterator;
@@ -12,22 +14,24 @@ use std::{fmt, sync::Arc};
 /// Holds all the relevant data for a pre-planned FFT, which is to say, once initialized,
 /// it can perform efficient FFT-s on data of the initially specified length.
 #[derive(Clone)]
-pub struct PlannedFFT {
-    fft: Arc<dyn Fft<f64>>,
-    scratch_space: Vec<Complex<f64>>,
+pub struct PlannedFFT<F: LeniaFloat> {
+    fft: Arc<dyn Fft<F>>,
+    scratch_space: Vec<Complex<F>>,
+    // Holds a copy of a non-contiguous lane while it is transformed.
+    lane_buffer: Vec<Complex<F>>,
 }
 
-impl fmt::Debug for PlannedFFT {
+impl<F: LeniaFloat> fmt::Debug for PlannedFFT<F> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PreplannedFFT")
             .field(
                 "scratch_space",
-                &format!("Vec<Complex<f64>>, len: {}", self.scratch_space.len()),
+                &format!("Vec<Complex<F>>, len: {}", self.scratch_space.len()),
             )
             .field(
                 "fft",
                 &format!(
-                    "Arc<dyn rustfft::Fft<f64>> => len: {}, direction: {}",
+                    "Arc<dyn rustfft::Fft<F>> => len: {}, direction: {}",
                     self.fft.len(),
                     self.fft.fft_direction()
                 ),
@@ -36,23 +40,28 @@ impl fmt::Debug for PlannedFFT {
     }
 }
 
-impl PlannedFFT {
+impl<F: LeniaFloat> PlannedFFT<F> {
     pub fn new(length: usize, inverse: bool) -> Self {
         if length == 0 {
             panic!("PlannedFFT::new() - Provided length was 0. Length must be at least 1!");
         }
-        let mut planner = FftPlanner::new();
+        let mut planner = FftPlanner::<F>::new();
         let direction: FftDirection;
         let direction = match inverse {
             true => FftDirection::Inverse,
             false => FftDirection::Forward,
         };
         let fft = planner.plan_fft(length, direction);
-        let scratch_space: Vec<Complex<f64>> = Vec::from_iter(
-            std::iter::repeat(Complex::new(0.0, 0.0)).take(fft.get_inplace_scratch_len()),
+        let scratch_space: Vec<Complex<F>> = Vec::from_iter(
+            std::iter::repeat(Complex::new(F::zero(), F::zero()))
+                .take(fft.get_inplace_scratch_len()),
         );
 
-        PlannedFFT { fft, scratch_space }
+        PlannedFFT {
+            fft,
+            scratch_space,
+            lane_buffer: vec![Complex::new(F::zero(), F::zero()); length],
+        }
     }
 
     pub fn inverse(&self) -> bool {
@@ -63,139 +72,717 @@ impl PlannedFFT {
         self.fft.len()
     }
 
-    pub fn transform(&mut self, data: &mut [Complex<f64>]) {
+    pub fn transform(&mut self, data: &mut [Complex<F>]) {
         self.fft.process_with_scratch(data, &mut self.scratch_space);
         if self.inverse() {
             // I fekin' forgot this AGAIN...
-            let inverse_len = 1.0 / data.len() as f64;
+            let inverse_len = F::one() / crate::float(data.len() as f64);
             for v in data.iter_mut() {
                 v.re *= inverse_len;
                 v.im *= inverse_len;
             }
         }
     }
+
+    /// Same as `transform()`, but for a lane of an n-dimensional array, which need not be contiguous.
+    ///
+    /// Non-contiguous lanes are copied through a buffer owned by the instance, so no memory is allocated.
+    pub fn transform_lane(&mut self, mut lane: ndarray::ArrayViewMut1<Complex<F>>) {
+        if let Some(data) = lane.as_slice_mut() {
+            self.transform(data);
+            return;
+        }
+        let mut buffer = std::mem::take(&mut self.lane_buffer);
+        for (a, b) in buffer.iter_mut().zip(lane.iter()) {
+            *a = *b;
+        }
+        self.transform(&mut buffer);
+        lane.assign(&ndarray::ArrayView1::from(&buffer));
+        self.lane_buffer = buffer;
+    }
+}
+
+#[derive(Clone)]
+enum RealFFTInstance<F: LeniaFloat> {
+    Forward(Arc<dyn RealToComplex<F>>),
+    Inverse(Arc<dyn ComplexToReal<F>>),
+}
+
+/// Holds all the relevant data for a pre-planned real-to-complex (forward) or
+/// complex-to-real (inverse) FFT.
+///
+/// Only the non-redundant half of the spectrum (`length / 2 + 1` values) of a real signal
+/// is produced by, or consumed by, the transform.
+#[derive(Clone)]
+pub struct PlannedRealFFT<F: LeniaFloat> {
+    fft: RealFFTInstance<F>,
+    length: usize,
+    // The real signal, which the transforms overwrite, so the input is copied here first.
+    input_buffer: Vec<F>,
+    // The half-spectrum of a lane, used by the lane transforms.
+    spectrum_buffer: Vec<Complex<F>>,
+    scratch_space: Vec<Complex<F>>,
+}
+
+impl<F: LeniaFloat> fmt::Debug for PlannedRealFFT<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PlannedRealFFT")
+            .field(
+                "scratch_space",
+                &format!("Vec<Complex<F>>, len: {}", self.scratch_space.len()),
+            )
+            .field(
+                "fft",
+                &format!(
+                    "realfft => len: {}, spectrum len: {}, inverse: {}",
+                    self.length,
+                    self.spectrum_length(),
+                    self.inverse()
+                ),
+            )
+            .finish()
+    }
+}
+
+impl<F: LeniaFloat> PlannedRealFFT<F> {
+    pub fn new(length: usize, inverse: bool) -> Self {
+        if length == 0 {
+            panic!("PlannedRealFFT::new() - Provided length was 0. Length must be at least 1!");
+        }
+        let mut planner = RealFftPlanner::<F>::new();
+        let (fft, scratch_len) = if inverse {
+            let fft = planner.plan_fft_inverse(length);
+            let scratch_len = fft.get_scratch_len();
+            (RealFFTInstance::Inverse(fft), scratch_len)
+        } else {
+            let fft = planner.plan_fft_forward(length);
+            let scratch_len = fft.get_scratch_len();
+            (RealFFTInstance::Forward(fft), scratch_len)
+        };
+
+        PlannedRealFFT {
+            fft,
+            length,
+            input_buffer: vec![F::zero(); length],
+            spectrum_buffer: vec![Complex::new(F::zero(), F::zero()); length / 2 + 1],
+            scratch_space: vec![Complex::new(F::zero(), F::zero()); scratch_len],
+        }
+    }
+
+    pub fn inverse(&self) -> bool {
+        matches!(self.fft, RealFFTInstance::Inverse(_))
+    }
+
+    /// Length of the real signal.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Length of the half-spectrum of the real signal.
+    pub fn spectrum_length(&self) -> usize {
+        self.length / 2 + 1
+    }
+
+    /// Forward-transforms the real `input` into the half-spectrum `output`.
+    ///
+    /// ### Panics
+    ///
+    /// * If the instance was planned as an inverse transform.
+    ///
+    /// * If the lengths of `input` or `output` do not match `length()` and `spectrum_length()`.
+    pub fn transform_real(&mut self, input: &[F], output: &mut [Complex<F>]) {
+        let fft = match &self.fft {
+            RealFFTInstance::Forward(fft) => fft,
+            RealFFTInstance::Inverse(_) => {
+                panic!("PlannedRealFFT::transform_real() - Instance is planned for the inverse transform!")
+            }
+        };
+        self.input_buffer.copy_from_slice(input);
+        fft.process_with_scratch(&mut self.input_buffer, output, &mut self.scratch_space)
+            .unwrap();
+    }
+
+    /// Inverse-transforms the half-spectrum `input` into the real `output`.
+    ///
+    /// The contents of `input` should be considered garbage after the call.
+    ///
+    /// ### Panics
+    ///
+    /// * If the instance was planned as a forward transform.
+    ///
+    /// * If the lengths of `input` or `output` do not match `spectrum_length()` and `length()`.
+    pub fn transform_real_inverse(&mut self, input: &mut [Complex<F>], output: &mut [F]) {
+        let fft = match &self.fft {
+            RealFFTInstance::Inverse(fft) => fft,
+            RealFFTInstance::Forward(_) => {
+                panic!("PlannedRealFFT::transform_real_inverse() - Instance is planned for the forward transform!")
+            }
+        };
+        // The zero-frequency (and for even lengths, the Nyquist) component of a real signal is
+        // purely real. Rounding errors from the other axes' transforms can leave a tiny imaginary part.
+        input[0].im = F::zero();
+        if self.length % 2 == 0 {
+            input[self.length / 2].im = F::zero();
+        }
+        fft.process_with_scratch(input, output, &mut self.scratch_space)
+            .unwrap();
+        let inverse_len = F::one() / crate::float(self.length as f64);
+        for v in output.iter_mut() {
+            *v *= inverse_len;
+        }
+    }
+
+    /// Same as `transform_real()`, but between lanes of n-dimensional arrays, which need not be contiguous.
+    ///
+    /// The lanes are copied through buffers owned by the instance, so no memory is allocated.
+    pub fn transform_real_lane(
+        &mut self,
+        input: ndarray::ArrayView1<F>,
+        mut output: ndarray::ArrayViewMut1<Complex<F>>,
+    ) {
+        let fft = match &self.fft {
+            RealFFTInstance::Forward(fft) => fft,
+            RealFFTInstance::Inverse(_) => {
+                panic!("PlannedRealFFT::transform_real_lane() - Instance is planned for the inverse transform!")
+            }
+        };
+        for (a, b) in self.input_buffer.iter_mut().zip(input.iter()) {
+            *a = *b;
+        }
+        match output.as_slice_mut() {
+            Some(output) => fft
+                .process_with_scratch(&mut self.input_buffer, output, &mut self.scratch_space)
+                .unwrap(),
+            None => {
+                fft.process_with_scratch(
+                    &mut self.input_buffer,
+                    &mut self.spectrum_buffer,
+                    &mut self.scratch_space,
+                )
+                .unwrap();
+                output.assign(&ndarray::ArrayView1::from(&self.spectrum_buffer));
+            }
+        }
+    }
+
+    /// Same as `transform_real_inverse()`, but between lanes of n-dimensional arrays, which need not be contiguous.
+    ///
+    /// Unlike `transform_real_inverse()`, `input` is left untouched. The lanes are copied through buffers owned
+    /// by the instance, so no memory is allocated.
+    pub fn transform_real_inverse_lane(
+        &mut self,
+        input: ndarray::ArrayView1<Complex<F>>,
+        mut output: ndarray::ArrayViewMut1<F>,
+    ) {
+        if self.length != output.len() {
+            panic!("PlannedRealFFT::transform_real_inverse_lane() - Length of the output does not match the length of the instance!");
+        }
+        let mut spectrum = std::mem::take(&mut self.spectrum_buffer);
+        for (a, b) in spectrum.iter_mut().zip(input.iter()) {
+            *a = *b;
+        }
+        let mut signal = std::mem::take(&mut self.input_buffer);
+        match output.as_slice_mut() {
+            Some(output) => self.transform_real_inverse(&mut spectrum, output),
+            None => {
+                self.transform_real_inverse(&mut spectrum, &mut signal);
+                output.assign(&ndarray::ArrayView1::from(&signal));
+            }
+        }
+        self.spectrum_buffer = spectrum;
+        self.input_buffer = signal;
+    }
+}
+
+/// Shape of the half-spectrum of a real-valued field of the given `shape`.
+///
+/// The last axis is shortened to `n / 2 + 1`, all other axes are unchanged.
+pub fn spectrum_shape(shape: &[usize]) -> Vec<usize> {
+    let mut spectrum_shape = shape.to_vec();
+    if let Some(last) = spectrum_shape.last_mut() {
+        *last = *last / 2 + 1;
+    }
+    spectrum_shape
+}
+
+/// Checks that a shape to plan transforms for has at least 1 axis, and no axis with a length of `0`.
+fn check_fft_shape(shape: &[usize]) -> Result<(), LeniaError> {
+    if shape.is_empty() {
+        return Err(LeniaError::InvalidShape(
+            "Provided shape was empty! Needs at least 1 dimension!".to_string(),
+        ));
+    }
+    crate::check_shape(shape)
 }
 
 /// Holds all the relevant data for a pre-planned N-dimensional fast-fourier-transform. Operates only
 /// on data of the initially specified length.
+///
+/// Instances made with `new()` transform full complex fields, instances made with `new_real()`
+/// transform between real fields and their half-spectrum (see `spectrum_shape()`).
 #[derive(Debug)]
-pub struct PlannedFFTND {
+pub struct PlannedFFTND<F: LeniaFloat> {
     shape: Vec<usize>,
-    fft_instances: Vec<PlannedFFT>,
+    spectrum_shape: Vec<usize>,
+    fft_instances: Vec<PlannedFFT<F>>,
+    real_fft_instance: Option<PlannedRealFFT<F>>,
     inverse: bool,
 }
 
-impl PlannedFFTND {
+impl<F: LeniaFloat> PlannedFFTND<F> {
+    /// Plans a complex transform (or its inverse if `inverse`) for fields of `shape`.
+    ///
+    /// ### Panics
+    ///
+    /// If `shape` is empty, or any of its axes has a length of `0`.
     pub fn new(shape: &[usize], inverse: bool) -> Self {
-        if shape.is_empty() {
-            panic!("PlannedFFTND::new() - Provided shape was empty! Needs at least 1 dimension!");
+        match Self::try_new(shape, inverse) {
+            Ok(instance) => instance,
+            Err(error) => panic!("PlannedFFTND::new() - {}", error),
         }
-        let mut ffts: Vec<PlannedFFT> = Vec::with_capacity(shape.len());
+    }
+
+    /// Same as `new()`, but returns an error instead of panicking.
+    pub fn try_new(shape: &[usize], inverse: bool) -> Result<Self, LeniaError> {
+        check_fft_shape(shape)?;
+        let mut ffts: Vec<PlannedFFT<F>> = Vec::with_capacity(shape.len());
         for dim in shape {
             ffts.push(PlannedFFT::new(*dim, inverse));
         }
-        PlannedFFTND {
+        Ok(PlannedFFTND {
             shape: shape.to_vec(),
+            spectrum_shape: shape.to_vec(),
             fft_instances: ffts,
+            real_fft_instance: None,
             inverse,
+        })
+    }
+
+    /// Plans a real-to-complex (or complex-to-real if `inverse`) transform for real fields of `shape`.
+    ///
+    /// The last axis is transformed with a real FFT, such that only half of the spectrum is
+    /// stored, the rest of the axes with regular complex FFT-s.
+    ///
+    /// ### Panics
+    ///
+    /// If `shape` is empty, or any of its axes has a length of `0`.
+    pub fn new_real(shape: &[usize], inverse: bool) -> Self {
+        match Self::try_new_real(shape, inverse) {
+            Ok(instance) => instance,
+            Err(error) => panic!("PlannedFFTND::new_real() - {}", error),
         }
     }
 
+    /// Same as `new_real()`, but returns an error instead of panicking.
+    pub fn try_new_real(shape: &[usize], inverse: bool) -> Result<Self, LeniaError> {
+        check_fft_shape(shape)?;
+        let spectrum_shape = spectrum_shape(shape);
+        let mut ffts: Vec<PlannedFFT<F>> = Vec::with_capacity(shape.len() - 1);
+        for dim in &shape[..shape.len() - 1] {
+            ffts.push(PlannedFFT::new(*dim, inverse));
+        }
+        Ok(PlannedFFTND {
+            shape: shape.to_vec(),
+            spectrum_shape,
+            fft_instances: ffts,
+            real_fft_instance: Some(PlannedRealFFT::new(shape[shape.len() - 1], inverse)),
+            inverse,
+        })
+    }
+
     pub fn shape(&self) -> &[usize] {
         &self.shape
     }
 
+    /// Shape of the transformed data. Same as `shape()` unless the instance is a real transform.
+    pub fn spectrum_shape(&self) -> &[usize] {
+        &self.spectrum_shape
+    }
+
     pub fn inverse(&self) -> bool {
         self.inverse
     }
 
-    pub fn transform(&mut self, data: &mut ndarray::ArrayD<Complex<f64>>) {
+    pub fn real(&self) -> bool {
+        self.real_fft_instance.is_some()
+    }
+
+    pub fn transform(&mut self, data: &mut ndarray::ArrayD<Complex<F>>) {
+        if self.real() {
+            panic!("PlannedFFTND::transform() - Instance is planned for real transforms, use transform_real() instead!");
+        }
         if data.shape() != self.shape {
             panic!("PlannedFFTND::transform() - shape of the data to be transformed does not agree with the shape that the fft can work on!");
         }
-        let mut axis_iterator: Vec<usize> = Vec::with_capacity(self.shape.len());
+        let mut axis_iterator: Vec<usize> = (0..self.shape.len()).collect();
         if self.inverse() {
-            for i in (0..self.shape.len()).rev() {
-                axis_iterator.push(i);
-            }
-        } else {
-            for i in 0..self.shape.len() {
-                axis_iterator.push(i);
-            }
+            axis_iterator.reverse();
         }
         for axis in axis_iterator {
-            for mut lane in data.lanes_mut(ndarray::Axis(axis)) {
-                let mut buf = lane.to_vec();
-                self.fft_instances[axis].transform(&mut buf);
-                for i in 0..lane.len() {
-                    lane[i] = buf[i];
-                }
-            }
+            self.transform_axis(data, axis);
+        }
+    }
+
+    /// Forward-transforms the real `input` field into its half-spectrum `output`.
+    pub fn transform_real(
+        &mut self,
+        input: &ndarray::ArrayD<F>,
+        output: &mut ndarray::ArrayD<Complex<F>>,
+    ) {
+        if self.inverse() || !self.real() {
+            panic!("PlannedFFTND::transform_real() - Instance is not planned for forward real transforms!");
+        }
+        if input.shape() != self.shape || output.shape() != self.spectrum_shape {
+            panic!("PlannedFFTND::transform_real() - shape of the data to be transformed does not agree with the shape that the fft can work on!");
+        }
+        let last_axis = ndarray::Axis(self.shape.len() - 1);
+        let real_fft = self.real_fft_instance.as_mut().unwrap();
+        for (input_lane, output_lane) in input
+            .lanes(last_axis)
+            .into_iter()
+            .zip(output.lanes_mut(last_axis))
+        {
+            real_fft.transform_real_lane(input_lane, output_lane);
+        }
+        for axis in 0..self.shape.len() - 1 {
+            self.transform_axis(output, axis);
+        }
+    }
+
+    /// Inverse-transforms the half-spectrum `input` into the real field `output`.
+    ///
+    /// The contents of `input` should be considered garbage after the call.
+    pub fn transform_real_inverse(
+        &mut self,
+        input: &mut ndarray::ArrayD<Complex<F>>,
+        output: &mut ndarray::ArrayD<F>,
+    ) {
+        if !self.inverse() || !self.real() {
+            panic!("PlannedFFTND::transform_real_inverse() - Instance is not planned for inverse real transforms!");
+        }
+        if input.shape() != self.spectrum_shape || output.shape() != self.shape {
+            panic!("PlannedFFTND::transform_real_inverse() - shape of the data to be transformed does not agree with the shape that the fft can work on!");
+        }
+        for axis in (0..self.shape.len() - 1).rev() {
+            self.transform_axis(input, axis);
+        }
+        let last_axis = ndarray::Axis(self.shape.len() - 1);
+        let real_fft = self.real_fft_instance.as_mut().unwrap();
+        for (input_lane, output_lane) in input
+            .lanes(last_axis)
+            .into_iter()
+            .zip(output.lanes_mut(last_axis))
+        {
+            real_fft.transform_real_inverse_lane(input_lane, output_lane);
+        }
+    }
+
+    fn transform_axis(&mut self, data: &mut ndarray::ArrayD<Complex<F>>, axis: usize) {
+        for lane in data.lanes_mut(ndarray::Axis(axis)) {
+            self.fft_instances[axis].transform_lane(lane);
         }
     }
 }
 
 /// Parallel version (multithreaded) of the PlannedFFTND.
 #[derive(Debug)]
-pub struct ParPlannedFFTND {
+pub struct ParPlannedFFTND<F: LeniaFloat> {
     shape: Vec<usize>,
-    fft_instances: Vec<PlannedFFT>,
+    spectrum_shape: Vec<usize>,
+    fft_instances: Vec<PlannedFFT<F>>,
+    real_fft_instance: Option<PlannedRealFFT<F>>,
     inverse: bool,
+    // Number of leading axes that are not transformed, each index along them being a separate transform.
+    batch_axes: usize,
 }
 
-impl ParPlannedFFTND {
+impl<F: LeniaFloat> ParPlannedFFTND<F> {
+    /// Parallel version of `PlannedFFTND::new()`.
+    ///
+    /// ### Panics
+    ///
+    /// If `shape` is empty, or any of its axes has a length of `0`.
     pub fn new(shape: &[usize], inverse: bool) -> Self {
-        if shape.is_empty() {
-            panic!(
-                "ParPlannedFFTND::new() - Provided shape was empty! Needs at least 1 dimension!"
-            );
+        match Self::try_new(shape, inverse) {
+            Ok(instance) => instance,
+            Err(error) => panic!("ParPlannedFFTND::new() - {}", error),
         }
-        let mut ffts: Vec<PlannedFFT> = Vec::with_capacity(shape.len());
+    }
+
+    /// Same as `new()`, but returns an error instead of panicking.
+    pub fn try_new(shape: &[usize], inverse: bool) -> Result<Self, LeniaError> {
+        check_fft_shape(shape)?;
+        let mut ffts: Vec<PlannedFFT<F>> = Vec::with_capacity(shape.len());
         for dim in shape {
             ffts.push(PlannedFFT::new(*dim, inverse));
         }
-        ParPlannedFFTND {
+        Ok(ParPlannedFFTND {
             shape: shape.to_vec(),
+            spectrum_shape: shape.to_vec(),
             fft_instances: ffts,
+            real_fft_instance: None,
             inverse,
+            batch_axes: 0,
+        })
+    }
+
+    /// Parallel version of `PlannedFFTND::new_real()`.
+    ///
+    /// ### Panics
+    ///
+    /// If `shape` is empty, or any of its axes has a length of `0`.
+    pub fn new_real(shape: &[usize], inverse: bool) -> Self {
+        match Self::try_new_real(shape, inverse) {
+            Ok(instance) => instance,
+            Err(error) => panic!("ParPlannedFFTND::new_real() - {}", error),
         }
     }
 
+    /// Same as `new_real()`, but returns an error instead of panicking.
+    pub fn try_new_real(shape: &[usize], inverse: bool) -> Result<Self, LeniaError> {
+        check_fft_shape(shape)?;
+        let spectrum_shape = spectrum_shape(shape);
+        let mut ffts: Vec<PlannedFFT<F>> = Vec::with_capacity(shape.len() - 1);
+        for dim in &shape[..shape.len() - 1] {
+            ffts.push(PlannedFFT::new(*dim, inverse));
+        }
+        Ok(ParPlannedFFTND {
+            shape: shape.to_vec(),
+            spectrum_shape,
+            fft_instances: ffts,
+            real_fft_instance: Some(PlannedRealFFT::new(shape[shape.len() - 1], inverse)),
+            inverse,
+            batch_axes: 0,
+        })
+    }
+
+    /// Batched version of `new_real()`, where the first axis of `shape` is the batch axis.
+    ///
+    /// Each index along the batch axis is transformed separately, all of them in parallel.
+    ///
+    /// ### Panics
+    ///
+    /// If `shape` has no axis besides the batch axis, or any of its axes has a length of `0`.
+    pub fn new_real_batched(shape: &[usize], inverse: bool) -> Self {
+        match Self::try_new_real_batched(shape, inverse) {
+            Ok(instance) => instance,
+            Err(error) => panic!("ParPlannedFFTND::new_real_batched() - {}", error),
+        }
+    }
+
+    /// Same as `new_real_batched()`, but returns an error instead of panicking.
+    pub fn try_new_real_batched(shape: &[usize], inverse: bool) -> Result<Self, LeniaError> {
+        if shape.len() < 2 {
+            return Err(LeniaError::InvalidShape(
+                "The shape needs at least 1 axis besides the batch axis!".to_string(),
+            ));
+        }
+        check_fft_shape(shape)?;
+        let mut instance = Self::try_new_real(&shape[1..], inverse)?;
+        instance.shape = shape.to_vec();
+        instance.spectrum_shape.insert(0, shape[0]);
+        instance.batch_axes = 1;
+        Ok(instance)
+    }
+
     pub fn shape(&self) -> &[usize] {
         &self.shape
     }
 
+    /// Shape of the transformed data. Same as `shape()` unless the instance is a real transform.
+    pub fn spectrum_shape(&self) -> &[usize] {
+        &self.spectrum_shape
+    }
+
     pub fn inverse(&self) -> bool {
         self.inverse
     }
 
-    pub fn transform(&mut self, data: &mut ndarray::ArrayD<Complex<f64>>) {
+    pub fn real(&self) -> bool {
+        self.real_fft_instance.is_some()
+    }
+
+    pub fn transform(&mut self, data: &mut ndarray::ArrayD<Complex<F>>) {
+        if self.real() {
+            panic!("ParPlannedFFTND::transform() - Instance is planned for real transforms, use transform_real() instead!");
+        }
         if data.shape() != self.shape {
             panic!("ParPlannedFFTND::transform() - shape of the data to be transformed does not agree with the shape that the fft can work on!");
         }
-        let mut axis_iterator: Vec<usize> = Vec::with_capacity(self.shape.len());
+        let mut axis_iterator: Vec<usize> = (0..self.shape.len()).collect();
         if self.inverse() {
-            for i in (0..self.shape.len()).rev() {
-                axis_iterator.push(i);
-            }
-        } else {
-            for i in 0..self.shape.len() {
-                axis_iterator.push(i);
-            }
+            axis_iterator.reverse();
         }
         for axis in axis_iterator {
-            let data_lane = data.lanes_mut(ndarray::Axis(axis));
-            let fft_lane = &mut self.fft_instances[axis];
-            ndarray::Zip::from(data_lane).into_par_iter().for_each_with(
-                self.fft_instances[axis].clone(),
-                |fft, mut row| {
-                    let mut lane = row.0.to_vec();
-                    fft.transform(&mut lane);
-                    for i in 0..row.0.len() {
-                        row.0[i] = lane[i];
-                    }
+            self.transform_axis(data, axis);
+        }
+    }
+
+    /// Parallel version of `PlannedFFTND::transform_real()`.
+    pub fn transform_real(
+        &mut self,
+        input: &ndarray::ArrayD<F>,
+        output: &mut ndarray::ArrayD<Complex<F>>,
+    ) {
+        if self.inverse() || !self.real() {
+            panic!("ParPlannedFFTND::transform_real() - Instance is not planned for forward real transforms!");
+        }
+        if input.shape() != self.shape || output.shape() != self.spectrum_shape {
+            panic!("ParPlannedFFTND::transform_real() - shape of the data to be transformed does not agree with the shape that the fft can work on!");
+        }
+        let last_axis = ndarray::Axis(self.shape.len() - 1);
+        ndarray::Zip::from(input.lanes(last_axis))
+            .and(output.lanes_mut(last_axis))
+            .into_par_iter()
+            .for_each_with(
+                self.real_fft_instance.clone().unwrap(),
+                |fft, (input_lane, output_lane)| fft.transform_real_lane(input_lane, output_lane),
+            );
+        for axis in self.batch_axes..self.shape.len() - 1 {
+            self.transform_axis(output, axis);
+        }
+    }
+
+    /// Parallel version of `PlannedFFTND::transform_real_inverse()`.
+    pub fn transform_real_inverse(
+        &mut self,
+        input: &mut ndarray::ArrayD<Complex<F>>,
+        output: &mut ndarray::ArrayD<F>,
+    ) {
+        if !self.inverse() || !self.real() {
+            panic!("ParPlannedFFTND::transform_real_inverse() - Instance is not planned for inverse real transforms!");
+        }
+        if input.shape() != self.spectrum_shape || output.shape() != self.shape {
+            panic!("ParPlannedFFTND::transform_real_inverse() - shape of the data to be transformed does not agree with the shape that the fft can work on!");
+        }
+        for axis in (self.batch_axes..self.shape.len() - 1).rev() {
+            self.transform_axis(input, axis);
+        }
+        let last_axis = ndarray::Axis(self.shape.len() - 1);
+        ndarray::Zip::from(input.lanes(last_axis))
+            .and(output.lanes_mut(last_axis))
+            .into_par_iter()
+            .for_each_with(
+                self.real_fft_instance.clone().unwrap(),
+                |fft, (input_lane, output_lane)| {
+                    fft.transform_real_inverse_lane(input_lane, output_lane)
                 },
             );
+    }
+
+    fn transform_axis(&mut self, data: &mut ndarray::ArrayD<Complex<F>>, axis: usize) {
+        let data_lane = data.lanes_mut(ndarray::Axis(axis));
+        ndarray::Zip::from(data_lane).into_par_iter().for_each_with(
+            self.fft_instances[axis - self.batch_axes].clone(),
+            |fft, row| fft.transform_lane(row.0),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+    use ndarray::Slice;
+
+    /// Deterministic field with values in `-1.0..1.0`.
+    fn field(shape: &[usize]) -> ndarray::ArrayD<f64> {
+        test_util::field_with(shape, 7, |state| (state % 2000) as f64 / 1000.0 - 1.0)
+    }
+
+    /// The half-spectrum, computed with the full complex transform.
+    fn full_spectrum_half(input: &ndarray::ArrayD<f64>) -> ndarray::ArrayD<Complex<f64>> {
+        let mut full = input.mapv(|value| Complex::new(value, 0.0));
+        PlannedFFTND::new(input.shape(), false).transform(&mut full);
+        let last_axis = ndarray::Axis(input.ndim() - 1);
+        let half = input.shape()[input.ndim() - 1] / 2 + 1;
+        full.slice_axis(last_axis, Slice::from(0..half)).to_owned()
+    }
+
+    fn assert_close(a: &ndarray::ArrayD<Complex<f64>>, b: &ndarray::ArrayD<Complex<f64>>) {
+        assert_eq!(a.shape(), b.shape());
+        for (a, b) in a.iter().zip(b) {
+            assert!((a - b).norm() < 1e-9, "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn real_transform_matches_complex_transform() {
+        for shape in [vec![16], vec![9], vec![6, 5], vec![7, 8], vec![4, 5, 6]] {
+            let input = field(&shape);
+            let expected = full_spectrum_half(&input);
+
+            let mut serial = PlannedFFTND::new_real(&shape, false);
+            let mut spectrum = ndarray::ArrayD::zeros(serial.spectrum_shape());
+            serial.transform_real(&input, &mut spectrum);
+            assert_close(&spectrum, &expected);
+
+            let mut parallel = ParPlannedFFTND::new_real(&shape, false);
+            let mut spectrum = ndarray::ArrayD::zeros(parallel.spectrum_shape());
+            parallel.transform_real(&input, &mut spectrum);
+            assert_close(&spectrum, &expected);
         }
     }
+
+    #[test]
+    fn real_transform_round_trips() {
+        for shape in [vec![9], vec![6, 5], vec![7, 8], vec![4, 5, 6]] {
+            let input = field(&shape);
+
+            let mut spectrum = ndarray::ArrayD::zeros(spectrum_shape(&shape));
+            PlannedFFTND::new_real(&shape, false).transform_real(&input, &mut spectrum);
+            let mut output = ndarray::ArrayD::zeros(shape.clone());
+            PlannedFFTND::new_real(&shape, true).transform_real_inverse(&mut spectrum, &mut output);
+            assert!(ndarray::Zip::from(&input)
+                .and(&output)
+                .all(|a, b| (a - b).abs() < 1e-12));
+
+            let mut spectrum = ndarray::ArrayD::zeros(spectrum_shape(&shape));
+            ParPlannedFFTND::new_real(&shape, false).transform_real(&input, &mut spectrum);
+            let mut output = ndarray::ArrayD::zeros(shape.clone());
+            ParPlannedFFTND::new_real(&shape, true)
+                .transform_real_inverse(&mut spectrum, &mut output);
+            assert!(ndarray::Zip::from(&input)
+                .and(&output)
+                .all(|a, b| (a - b).abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn batched_transform_matches_separate_transforms() {
+        let input = field(&[3, 6, 5]);
+        let mut batched = ParPlannedFFTND::new_real_batched(&[3, 6, 5], false);
+        let mut spectrum = ndarray::ArrayD::zeros(batched.spectrum_shape());
+        batched.transform_real(&input, &mut spectrum);
+        for world in 0..3 {
+            let world_input = input.index_axis(ndarray::Axis(0), world).to_owned();
+            let world_spectrum = spectrum.index_axis(ndarray::Axis(0), world).to_owned();
+            assert_close(&world_spectrum, &full_spectrum_half(&world_input));
+        }
+    }
+
+    #[test]
+    fn planning_an_empty_shape_is_an_error() {
+        assert!(matches!(
+            PlannedFFTND::<f64>::try_new(&[], false),
+            Err(LeniaError::InvalidShape(_))
+        ));
+        assert!(matches!(
+            PlannedFFTND::<f64>::try_new_real(&[8, 0], false),
+            Err(LeniaError::InvalidShape(_))
+        ));
+        assert!(matches!(
+            ParPlannedFFTND::<f64>::try_new(&[], true),
+            Err(LeniaError::InvalidShape(_))
+        ));
+        assert!(matches!(
+            ParPlannedFFTND::<f64>::try_new_real(&[], true),
+            Err(LeniaError::InvalidShape(_))
+        ));
+        assert!(matches!(
+            ParPlannedFFTND::<f64>::try_new_real_batched(&[4], false),
+            Err(LeniaError::InvalidShape(_))
+        ));
+        assert!(ParPlannedFFTND::<f64>::try_new_real_batched(&[4, 8, 6], false).is_ok());
+    }
 }
@@ -3,6 +3,8 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+use crate::{float, GrowthFunction, LeniaFloat};
+
 /// Standard unimodal "gaussian bump".
 /// 
 /// ### Parameters
@@ -12,9 +14,25 @@
 /// * `params[1]` - **sigma**: Standard deviation of the gaussian bump. 
 /// 
 /// ### Returns
-/// A `f64` in range `[-1.0..1,0]`. 
-pub fn standard_lenia(num: f64, params: &[f64]) -> f64 {
-    (2.0 * super::sample_normal(num, params[0], params[1])) - 1.0
+/// A number in range `[-1.0..1,0]`. 
+pub fn standard_lenia<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    (float::<F>(2.0) * super::sample_normal(num, params[0], params[1])) - F::one()
+}
+
+/// Target-style unimodal "gaussian bump" for channels with `UpdateMode::Asymptotic`.
+/// 
+/// Same as `standard_lenia`, but rescaled to give the value the channel should approach instead of its change.
+/// 
+/// ### Parameters
+/// 
+/// * `params[0]` - **mu**: The position of the mean / highest point of the growth function.
+/// 
+/// * `params[1]` - **sigma**: Standard deviation of the gaussian bump. 
+/// 
+/// ### Returns
+/// A number in range `[0.0..1.0]`. 
+pub fn standard_lenia_target<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    super::sample_normal(num, params[0], params[1])
 }
 
 /// Multimodal "gaussian bumps" growth function.
@@ -28,12 +46,30 @@ pub fn standard_lenia(num: f64, params: &[f64]) -> f64 {
 /// 
 /// * `params[odd index]` - **sigma**: Standard deviations of the gaussian bumps. Each sigma corresponds
 /// to the mu defined by the previous `params` index.
-pub fn multimodal_normal(num: f64, params: &[f64]) -> f64 {
-    let mut sum = 0.0;
+pub fn multimodal_normal<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    let mut sum = F::zero();
     for i in (0..params.len()).step_by(2) {
         sum += super::sample_normal(num, params[i], params[i + 1]);
     }
-    (sum * 2.0) - 1.0
+    (sum * float(2.0)) - F::one()
+}
+
+/// Target-style multimodal "gaussian bumps" for channels with `UpdateMode::Asymptotic`.
+/// 
+/// Same as `multimodal_normal`, but rescaled to give the value the channel should approach instead of its change.
+/// 
+/// ### Parameters
+/// 
+/// * `params[even index]` - **mu**: The position of the means / the centers of the gaussian bumps.
+/// 
+/// * `params[odd index]` - **sigma**: Standard deviations of the gaussian bumps. Each sigma corresponds
+/// to the mu defined by the previous `params` index.
+pub fn multimodal_normal_target<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    let mut sum = F::zero();
+    for i in (0..params.len()).step_by(2) {
+        sum += super::sample_normal(num, params[i], params[i + 1]);
+    }
+    sum
 }
 
 /// Standard unimodal "polynomial bump".
@@ -45,17 +81,42 @@ pub fn multimodal_normal(num: f64, params: &[f64]) -> f64 {
 /// `params[1]` - sigma
 /// 
 /// `params[2]` - alpha
-pub fn polynomial(num: f64, params: &[f64]) -> f64 {
+pub fn polynomial<F: LeniaFloat>(num: F, params: &[F]) -> F {
     let l = (num - params[0]).abs();
-    let k = params[1] * 3.0;
-    if l > k { -1.0 }
+    let k = params[1] * float(3.0);
+    if l > k { -F::one() }
     else {
-        let a = 1.0 - ((l * l) / (k * k));
-        let mut out = 1.0;
-        for _ in 0..(params[2] as usize) {
+        let a = F::one() - ((l * l) / (k * k));
+        let mut out = F::one();
+        for _ in 0..params[2].to_usize().unwrap_or(0) {
             out *= a;
         }
-        (out * 2.0) - 1.0
+        (out * float(2.0)) - F::one()
+    }
+}
+
+/// Target-style unimodal "polynomial bump" for channels with `UpdateMode::Asymptotic`.
+/// 
+/// Same as `polynomial`, but rescaled to give the value the channel should approach instead of its change.
+/// 
+/// ### Parameters
+/// 
+/// `params[0]` - mu
+/// 
+/// `params[1]` - sigma
+/// 
+/// `params[2]` - alpha
+pub fn polynomial_target<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    let l = (num - params[0]).abs();
+    let k = params[1] * float(3.0);
+    if l > k { F::zero() }
+    else {
+        let a = F::one() - ((l * l) / (k * k));
+        let mut out = F::one();
+        for _ in 0..params[2].to_usize().unwrap_or(0) {
+            out *= a;
+        }
+        out
     }
 }
 
@@ -68,23 +129,23 @@ pub fn polynomial(num: f64, params: &[f64]) -> f64 {
 /// ### Parameters
 /// 
 /// * `params[0..n]` - Distribution in range `[0.0..1.0]` to sample from
-pub fn precalculated_linear(num: f64, params: &[f64]) -> f64 {
-    let index = num * params.len() as f64;
-    if index as usize >= (params.len() - 1) { return params[params.len() - 1] }
-    if index as usize <= 0 { return params[0] }
-    let a = params[index.abs().floor() as usize];
-    let b = params[index.abs().ceil() as usize];
+pub fn precalculated_linear<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    let index = num * float(params.len() as f64);
+    if index.to_usize().unwrap_or(0) >= (params.len() - 1) { return params[params.len() - 1] }
+    if index.to_usize().unwrap_or(0) == 0 { return params[0] }
+    let a = params[index.abs().floor().to_usize().unwrap()];
+    let b = params[index.abs().ceil().to_usize().unwrap()];
     let dx = index - index.floor();
     let dy = b - a;
     a + (dx * dy)
 }
 
 /// Conway's "Game of life" growth function. `Rulestring: B3/S23`
-pub fn conway_game_of_life(num: f64, params: &[f64]) -> f64 {
-    let index = (num * 9.0).round() as usize;
-    if index == 2 { 0.0 }
-    else if index == 3 { 1.0 }
-    else {-1.0 }
+pub fn conway_game_of_life<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    let index = (num * float(9.0)).round().to_usize().unwrap_or(0);
+    if index == 2 { F::zero() }
+    else if index == 3 { F::one() }
+    else {-F::one() }
 }
 
 /// Basic Smooth Life growth function.
@@ -98,10 +159,10 @@ pub fn conway_game_of_life(num: f64, params: &[f64]) -> f64 {
 /// `params[2]` - Survive range start
 /// 
 /// `params[3]` - Survive range end
-pub fn smooth_life(num: f64, params: &[f64]) -> f64 {
-    if num >= params[0] && num <= params[1] { return 1.0 }
-    if num >= params[2] && num <= params[3] { return 0.0 }
-    -1.0
+pub fn smooth_life<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    if num >= params[0] && num <= params[1] { return F::one() }
+    if num >= params[2] && num <= params[3] { return F::zero() }
+    -F::one()
 }
 
 /// Smooth Life growth function with smoothed stepping. 
@@ -123,23 +184,502 @@ pub fn smooth_life(num: f64, params: &[f64]) -> f64 {
 /// `params[4]` - Birth step width
 /// 
 /// `params[5]` - Survive step width
-pub fn smooth_life_sigmoid_smoothed(num: f64, params: &[f64]) -> f64 {
-    let birth = (sigmoid(num, params[0], params[4], 2.0) + 
-        sigmoid(num, params[1], -params[4], 2.0)) - 3.0;
-    let survive = (sigmoid(num, params[2], params[5], 1.0) + 
-        sigmoid(num, params[3], -params[5], 1.0)) - 2.0;
+pub fn smooth_life_sigmoid_smoothed<F: LeniaFloat>(num: F, params: &[F]) -> F {
+    let birth = (sigmoid(num, params[0], params[4], float(2.0)) + 
+        sigmoid(num, params[1], -params[4], float(2.0))) - float(3.0);
+    let survive = (sigmoid(num, params[2], params[5], F::one()) + 
+        sigmoid(num, params[3], -params[5], F::one())) - float(2.0);
     if birth > survive { birth } else { survive }
 }
 
 /// Sigmoid function.
-fn sigmoid(x: f64, center: f64, sigma: f64, peak: f64) -> f64 {
-    peak / (1.0 + (-((x - center) * (4.0 / sigma))).exp())
+fn sigmoid<F: LeniaFloat>(x: F, center: F, sigma: F, peak: F) -> F {
+    peak / (F::one() + (-((x - center) * (float::<F>(4.0) / sigma))).exp())
 }
 
 /// Pass number on virtually unchanged.
 /// 
 /// Returns `num` multiplied by `params[0]`. Use this growth function if you would like to not use a growth function, 
 /// but merely explore the dynamics of iterative application of kernels.
-pub fn pass(num: f64, params: &[f64]) -> f64 {
+pub fn pass<F: LeniaFloat>(num: F, params: &[F]) -> F {
     num * params[0]
-}
\ No newline at end of file
+}
+
+/// Returns an error if the parameter called `name` is not positive.
+fn positive<F: LeniaFloat>(name: &str, value: F) -> Result<(), String> {
+    if value > F::zero() {
+        Ok(())
+    } else {
+        Err(format!("{} must be positive, got {}", name, value))
+    }
+}
+
+/// Returns an error if the range from `start` to `end` is reversed.
+fn ordered<F: LeniaFloat>(name: &str, start: F, end: F) -> Result<(), String> {
+    if start <= end {
+        Ok(())
+    } else {
+        Err(format!("{} range starts ({}) after it ends ({})", name, start, end))
+    }
+}
+
+/// Returns an error if the number of parameters does not match the number of parameter names.
+fn parameter_count<F: LeniaFloat>(function: &str, names: &[&str], parameters: &[F]) -> Result<(), String> {
+    if parameters.len() == names.len() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{} expects {} parameters ({}), got {}",
+            function,
+            names.len(),
+            names.join(", "),
+            parameters.len()
+        ))
+    }
+}
+
+/// Defines a typed `GrowthFunction` with a fixed number of parameters, which applies one of the functions above.
+macro_rules! typed_growth_function {
+    (
+        $(#[$meta:meta])*
+        $name:ident => $function:ident {
+            $($(#[$field_meta:meta])* $field:ident),+ $(,)?
+        }
+        validate($growth:ident) $validate:block
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct $name<F: LeniaFloat = f64> {
+            $($(#[$field_meta])* pub $field: F,)+
+        }
+
+        impl<F: LeniaFloat> $name<F> {
+            /// Creates the growth function from its parameters.
+            pub fn new($($field: F),+) -> Self {
+                $name { $($field),+ }
+            }
+
+            /// Creates the growth function from its parameters, in the order of `parameter_names()`.
+            pub fn from_parameters(parameters: &[F]) -> Result<Self, String> {
+                parameter_count(stringify!($function), &[$(stringify!($field)),+], parameters)?;
+                let mut parameters = parameters.iter().copied();
+                let growth = $name { $($field: parameters.next().unwrap()),+ };
+                growth.validate()?;
+                Ok(growth)
+            }
+        }
+
+        impl<F: LeniaFloat> GrowthFunction<F> for $name<F> {
+            fn growth(&self, num: F) -> F {
+                $function(num, &[$(self.$field),+])
+            }
+
+            fn growth_with_parameters(&self, num: F, parameters: &[F]) -> F {
+                $function(num, parameters)
+            }
+
+            fn name(&self) -> &str {
+                stringify!($function)
+            }
+
+            fn parameter_names(&self) -> Vec<String> {
+                vec![$(stringify!($field).to_string()),+]
+            }
+
+            fn parameters(&self) -> Vec<F> {
+                vec![$(self.$field),+]
+            }
+
+            fn validate(&self) -> Result<(), String> {
+                let $growth = self;
+                $validate
+            }
+        }
+    };
+}
+
+typed_growth_function! {
+    /// Typed version of `standard_lenia`.
+    StandardLenia => standard_lenia {
+        /// The position of the mean / highest point of the growth function.
+        mu,
+        /// Standard deviation of the gaussian bump.
+        sigma,
+    }
+    validate(growth) { positive("sigma", growth.sigma) }
+}
+
+typed_growth_function! {
+    /// Typed version of `standard_lenia_target`.
+    StandardLeniaTarget => standard_lenia_target {
+        /// The position of the mean / highest point of the growth function.
+        mu,
+        /// Standard deviation of the gaussian bump.
+        sigma,
+    }
+    validate(growth) { positive("sigma", growth.sigma) }
+}
+
+typed_growth_function! {
+    /// Typed version of `polynomial`.
+    Polynomial => polynomial {
+        /// The position of the highest point of the growth function.
+        mu,
+        /// A third of the half-width of the bump.
+        sigma,
+        /// Exponent of the bump, truncated to an integer.
+        alpha,
+    }
+    validate(growth) {
+        positive("sigma", growth.sigma)?;
+        if growth.alpha < F::zero() {
+            return Err(format!("alpha must not be negative, got {}", growth.alpha));
+        }
+        Ok(())
+    }
+}
+
+typed_growth_function! {
+    /// Typed version of `polynomial_target`.
+    PolynomialTarget => polynomial_target {
+        /// The position of the highest point of the growth function.
+        mu,
+        /// A third of the half-width of the bump.
+        sigma,
+        /// Exponent of the bump, truncated to an integer.
+        alpha,
+    }
+    validate(growth) {
+        positive("sigma", growth.sigma)?;
+        if growth.alpha < F::zero() {
+            return Err(format!("alpha must not be negative, got {}", growth.alpha));
+        }
+        Ok(())
+    }
+}
+
+typed_growth_function! {
+    /// Typed version of `smooth_life`.
+    SmoothLife => smooth_life {
+        /// Birth range start.
+        birth_start,
+        /// Birth range end.
+        birth_end,
+        /// Survive range start.
+        survive_start,
+        /// Survive range end.
+        survive_end,
+    }
+    validate(growth) {
+        ordered("birth", growth.birth_start, growth.birth_end)?;
+        ordered("survive", growth.survive_start, growth.survive_end)
+    }
+}
+
+typed_growth_function! {
+    /// Typed version of `smooth_life_sigmoid_smoothed`.
+    SmoothLifeSigmoidSmoothed => smooth_life_sigmoid_smoothed {
+        /// Birth range start.
+        birth_start,
+        /// Birth range end.
+        birth_end,
+        /// Survive range start.
+        survive_start,
+        /// Survive range end.
+        survive_end,
+        /// Birth step width.
+        birth_width,
+        /// Survive step width.
+        survive_width,
+    }
+    validate(growth) {
+        ordered("birth", growth.birth_start, growth.birth_end)?;
+        ordered("survive", growth.survive_start, growth.survive_end)?;
+        positive("birth_width", growth.birth_width)?;
+        positive("survive_width", growth.survive_width)
+    }
+}
+
+typed_growth_function! {
+    /// Typed version of `pass`.
+    Pass => pass {
+        /// The number the convolution result is multiplied with.
+        factor,
+    }
+    validate(growth) { Ok(()) }
+}
+
+/// Typed version of `multimodal_normal`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultimodalNormal<F: LeniaFloat = f64> {
+    // Flattened pairs of mu and sigma, as taken by `multimodal_normal`.
+    parameters: Vec<F>,
+}
+
+impl<F: LeniaFloat> MultimodalNormal<F> {
+    /// Creates the growth function from pairs of mu and sigma, one pair per gaussian bump.
+    pub fn new(bumps: &[(F, F)]) -> Self {
+        MultimodalNormal {
+            parameters: bumps.iter().flat_map(|(mu, sigma)| [*mu, *sigma]).collect(),
+        }
+    }
+
+    /// Creates the growth function from its parameters, in the order of `parameter_names()`.
+    pub fn from_parameters(parameters: &[F]) -> Result<Self, String> {
+        let growth = MultimodalNormal {
+            parameters: parameters.to_vec(),
+        };
+        growth.validate()?;
+        Ok(growth)
+    }
+
+    /// Returns the pairs of mu and sigma of each gaussian bump.
+    pub fn bumps(&self) -> Vec<(F, F)> {
+        self.parameters.chunks(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+}
+
+impl<F: LeniaFloat> GrowthFunction<F> for MultimodalNormal<F> {
+    fn growth(&self, num: F) -> F {
+        multimodal_normal(num, &self.parameters)
+    }
+
+    fn growth_with_parameters(&self, num: F, parameters: &[F]) -> F {
+        multimodal_normal(num, parameters)
+    }
+
+    fn name(&self) -> &str {
+        "multimodal_normal"
+    }
+
+    fn parameter_names(&self) -> Vec<String> {
+        bump_parameter_names(self.parameters.len())
+    }
+
+    fn parameters(&self) -> Vec<F> {
+        self.parameters.clone()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        validate_bumps(&self.parameters)
+    }
+}
+
+/// Typed version of `multimodal_normal_target`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultimodalNormalTarget<F: LeniaFloat = f64> {
+    // Flattened pairs of mu and sigma, as taken by `multimodal_normal_target`.
+    parameters: Vec<F>,
+}
+
+impl<F: LeniaFloat> MultimodalNormalTarget<F> {
+    /// Creates the growth function from pairs of mu and sigma, one pair per gaussian bump.
+    pub fn new(bumps: &[(F, F)]) -> Self {
+        MultimodalNormalTarget {
+            parameters: bumps.iter().flat_map(|(mu, sigma)| [*mu, *sigma]).collect(),
+        }
+    }
+
+    /// Creates the growth function from its parameters, in the order of `parameter_names()`.
+    pub fn from_parameters(parameters: &[F]) -> Result<Self, String> {
+        let growth = MultimodalNormalTarget {
+            parameters: parameters.to_vec(),
+        };
+        growth.validate()?;
+        Ok(growth)
+    }
+
+    /// Returns the pairs of mu and sigma of each gaussian bump.
+    pub fn bumps(&self) -> Vec<(F, F)> {
+        self.parameters.chunks(2).map(|pair| (pair[0], pair[1])).collect()
+    }
+}
+
+impl<F: LeniaFloat> GrowthFunction<F> for MultimodalNormalTarget<F> {
+    fn growth(&self, num: F) -> F {
+        multimodal_normal_target(num, &self.parameters)
+    }
+
+    fn growth_with_parameters(&self, num: F, parameters: &[F]) -> F {
+        multimodal_normal_target(num, parameters)
+    }
+
+    fn name(&self) -> &str {
+        "multimodal_normal_target"
+    }
+
+    fn parameter_names(&self) -> Vec<String> {
+        bump_parameter_names(self.parameters.len())
+    }
+
+    fn parameters(&self) -> Vec<F> {
+        self.parameters.clone()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        validate_bumps(&self.parameters)
+    }
+}
+
+/// Names of the flattened pairs of mu and sigma of the multimodal growth functions.
+fn bump_parameter_names(len: usize) -> Vec<String> {
+    (0..len)
+        .map(|i| format!("{}{}", if i % 2 == 0 { "mu" } else { "sigma" }, i / 2))
+        .collect()
+}
+
+/// Validates the flattened pairs of mu and sigma of the multimodal growth functions.
+fn validate_bumps<F: LeniaFloat>(parameters: &[F]) -> Result<(), String> {
+    if parameters.is_empty() || parameters.len() % 2 != 0 {
+        return Err(format!(
+            "expected pairs of mu and sigma, got {} parameters",
+            parameters.len()
+        ));
+    }
+    for (i, sigma) in parameters.iter().skip(1).step_by(2).enumerate() {
+        positive(&format!("sigma{}", i), *sigma)?;
+    }
+    Ok(())
+}
+
+/// Typed version of `precalculated_linear`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrecalculatedLinear<F: LeniaFloat = f64> {
+    /// Distribution in range `[0.0..1.0]` to sample from.
+    pub values: Vec<F>,
+}
+
+impl<F: LeniaFloat> PrecalculatedLinear<F> {
+    /// Creates the growth function from the distribution to sample from.
+    pub fn new(values: Vec<F>) -> Self {
+        PrecalculatedLinear { values }
+    }
+
+    /// Creates the growth function from its parameters, the distribution to sample from.
+    pub fn from_parameters(parameters: &[F]) -> Result<Self, String> {
+        let growth = PrecalculatedLinear::new(parameters.to_vec());
+        growth.validate()?;
+        Ok(growth)
+    }
+}
+
+impl<F: LeniaFloat> GrowthFunction<F> for PrecalculatedLinear<F> {
+    fn growth(&self, num: F) -> F {
+        precalculated_linear(num, &self.values)
+    }
+
+    fn growth_with_parameters(&self, num: F, parameters: &[F]) -> F {
+        precalculated_linear(num, parameters)
+    }
+
+    fn name(&self) -> &str {
+        "precalculated_linear"
+    }
+
+    fn parameter_names(&self) -> Vec<String> {
+        (0..self.values.len()).map(|i| format!("value{}", i)).collect()
+    }
+
+    fn parameters(&self) -> Vec<F> {
+        self.values.clone()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.values.is_empty() {
+            return Err("expected at least 1 value to sample from".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Typed version of `conway_game_of_life`, which has no parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ConwayGameOfLife;
+
+impl ConwayGameOfLife {
+    /// Creates the growth function from its parameters, of which there are none.
+    pub fn from_parameters<F: LeniaFloat>(parameters: &[F]) -> Result<Self, String> {
+        parameter_count("conway_game_of_life", &[], parameters)?;
+        Ok(ConwayGameOfLife)
+    }
+}
+
+impl<F: LeniaFloat> GrowthFunction<F> for ConwayGameOfLife {
+    fn growth(&self, num: F) -> F {
+        conway_game_of_life(num, &[])
+    }
+
+    fn name(&self) -> &str {
+        "conway_game_of_life"
+    }
+}
+
+/// Any closure taking the convolution result and returning the growth can be used as a growth function.
+///
+/// The closure is called "closure" and has no parameters, captured variables are not visible to the
+/// parameter schema.
+impl<F: LeniaFloat, T: Fn(F) -> F + Send + Sync> GrowthFunction<F> for T {
+    fn growth(&self, num: F) -> F {
+        self(num)
+    }
+
+    fn name(&self) -> &str {
+        "closure"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::ExpandedLenia;
+    use crate::{LeniaError, Simulator};
+
+    #[test]
+    fn invalid_parameters_are_rejected() {
+        assert!(StandardLenia::<f64>::from_parameters(&[0.15]).is_err());
+        assert!(StandardLenia::<f64>::from_parameters(&[0.15, 0.015, 1.0]).is_err());
+        assert!(StandardLenia::from_parameters(&[0.15, 0.0]).is_err());
+        assert!(Polynomial::from_parameters(&[0.15, 0.015, -1.0]).is_err());
+        assert!(SmoothLife::from_parameters(&[0.3, 0.2, 0.1, 0.4]).is_err());
+        assert!(
+            SmoothLifeSigmoidSmoothed::from_parameters(&[0.2, 0.3, 0.1, 0.4, 0.0, 0.1]).is_err()
+        );
+        assert!(MultimodalNormal::from_parameters(&[0.15, 0.015, 0.3]).is_err());
+        assert!(MultimodalNormalTarget::from_parameters(&[0.15, -0.015]).is_err());
+        assert!(PrecalculatedLinear::<f64>::from_parameters(&[]).is_err());
+        assert!(ConwayGameOfLife::from_parameters(&[1.0]).is_err());
+        assert_eq!(
+            StandardLenia::from_parameters(&[0.15, 0.015]),
+            Ok(StandardLenia::new(0.15, 0.015))
+        );
+
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[32, 32]);
+        assert!(matches!(
+            simulator.try_set_growth_function(StandardLenia::new(0.15, -0.015), 0),
+            Err(LeniaError::InvalidParameter(_))
+        ));
+        assert!(matches!(
+            simulator.try_set_growth_function(SmoothLife::new(0.3, 0.2, 0.1, 0.4), 0),
+            Err(LeniaError::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn debug_output_names_the_parameters() {
+        let standard: &dyn GrowthFunction<f64> = &StandardLenia::new(0.15, 0.015);
+        assert_eq!(
+            format!("{:?}", standard),
+            "standard_lenia(mu: 0.15, sigma: 0.015)"
+        );
+        let multimodal: &dyn GrowthFunction<f64> =
+            &MultimodalNormal::new(&[(0.1, 0.01), (0.3, 0.02)]);
+        assert_eq!(
+            format!("{:?}", multimodal),
+            "multimodal_normal(mu0: 0.1, sigma0: 0.01, mu1: 0.3, sigma1: 0.02)"
+        );
+        let conway: &dyn GrowthFunction<f64> = &ConwayGameOfLife;
+        assert_eq!(format!("{:?}", conway), "conway_game_of_life()");
+        let closure: &dyn GrowthFunction<f64> = &|num: f64| num;
+        assert_eq!(format!("{:?}", closure), "closure()");
+    }
+}
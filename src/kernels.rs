@@ -3,7 +3,7 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-use crate::{euclidean_dist, growth_functions, sample_normal};
+use crate::{euclidean_dist, float, growth_functions, sample_normal, LeniaError, LeniaFloat};
 use ndarray::IxDyn;
 
 /// Generates a kernel base of a gaussian donut in 2d.
@@ -17,22 +17,22 @@ use ndarray::IxDyn;
 /// but any values outside the radius are set to `0.0`.
 ///
 /// * `stddev` - Standard deviation to use.
-pub fn gaussian_donut_2d(radius: usize, stddev: f64) -> ndarray::ArrayD<f64> {
+pub fn gaussian_donut_2d<F: LeniaFloat>(radius: usize, stddev: F) -> ndarray::ArrayD<F> {
     let diameter = radius * 2;
-    let radius = radius as f64;
-    let normalizer = 1.0 / radius;
+    let radius: F = float(radius as f64);
+    let normalizer = F::one() / radius;
     let mut out = ndarray::ArrayD::zeros(IxDyn(&[diameter, diameter]));
     let x0 = radius;
     let y0 = radius;
     for i in 0..out.shape()[0] {
         for j in 0..out.shape()[1] {
-            let x1 = i as f64;
-            let y1 = j as f64;
+            let x1: F = float(i as f64);
+            let y1: F = float(j as f64);
             let dist = ((x1 - x0) * (x1 - x0) + (y1 - y0) * (y1 - y0)).sqrt();
             if dist <= radius {
-                out[[i, j]] = sample_normal(dist * normalizer, 0.5, stddev);
+                out[[i, j]] = sample_normal(dist * normalizer, float(0.5), stddev);
             } else {
-                out[[i, j]] = 0.0
+                out[[i, j]] = F::zero()
             }
         }
     }
@@ -56,38 +56,57 @@ pub fn gaussian_donut_2d(radius: usize, stddev: f64) -> ndarray::ArrayD<f64> {
 /// Can be any positive real number but will later be normalized compared to other rings.
 ///
 /// * `stddevs` - The standard deviations of each individual ring.
-pub fn multi_gaussian_donut_2d(
+///
+/// ### Panics
+///
+/// If `means`, `peaks` and `stddevs` are not of equal length.
+pub fn multi_gaussian_donut_2d<F: LeniaFloat>(
+    radius: usize,
+    means: &[F],
+    peaks: &[F],
+    stddevs: &[F],
+) -> ndarray::ArrayD<F> {
+    match try_multi_gaussian_donut_2d(radius, means, peaks, stddevs) {
+        Ok(kernel) => kernel,
+        Err(error) => panic!("Function \"multi_gaussian_donut_2d\" - {}", error),
+    }
+}
+
+/// Same as `multi_gaussian_donut_2d()`, but returns an error instead of panicking.
+pub fn try_multi_gaussian_donut_2d<F: LeniaFloat>(
     radius: usize,
-    means: &[f64],
-    peaks: &[f64],
-    stddevs: &[f64],
-) -> ndarray::ArrayD<f64> {
+    means: &[F],
+    peaks: &[F],
+    stddevs: &[F],
+) -> Result<ndarray::ArrayD<F>, LeniaError> {
     if means.len() != peaks.len() || means.len() != stddevs.len() {
-        panic!("Function \"multi_gaussian_donut_2d\" expects each mean parameter to be accompanied by a peak and stddev parameter!");
+        return Err(LeniaError::InvalidParameter(
+            "Each mean parameter must be accompanied by a peak and stddev parameter!".to_string(),
+        ));
     }
     let diameter = radius * 2;
-    let radius = radius as f64;
-    let normalizer = 1.0 / radius;
+    let radius: F = float(radius as f64);
+    let normalizer = F::one() / radius;
     let mut out = ndarray::ArrayD::zeros(IxDyn(&[diameter, diameter]));
     let x0 = radius;
     let y0 = radius;
     for i in 0..out.shape()[0] {
         for j in 0..out.shape()[1] {
-            let x1 = i as f64;
-            let y1 = j as f64;
+            let x1: F = float(i as f64);
+            let y1: F = float(j as f64);
             let dist = ((x1 - x0) * (x1 - x0) + (y1 - y0) * (y1 - y0)).sqrt();
             if dist <= radius {
-                let mut sum = 0.0;
+                let mut sum = F::zero();
                 for i in 0..means.len() {
                     sum += sample_normal(dist * normalizer, means[i], stddevs[i]) * peaks[i].abs();
                 }
                 out[[i, j]] = sum;
             } else {
-                out[[i, j]] = 0.0
+                out[[i, j]] = F::zero()
             }
         }
     }
-    out
+    Ok(out)
 }
 
 /// Generates a kernel base of a gaussian donut in n-dimensions.
@@ -101,28 +120,27 @@ pub fn multi_gaussian_donut_2d(
 /// Any values outside the radius are set to `0.0`.
 ///
 /// * `stddev` - Standard deviation to use.
-pub fn gaussian_donut_nd(radius: usize, dimensions: usize, stddev: f64) -> ndarray::ArrayD<f64> {
-    let radius = radius as f64;
-    let normalizer = 1.0 / radius;
+pub fn gaussian_donut_nd<F: LeniaFloat>(
+    radius: usize,
+    dimensions: usize,
+    stddev: F,
+) -> ndarray::ArrayD<F> {
+    let shape = vec![radius * 2; dimensions];
+    let radius: F = float(radius as f64);
+    let normalizer = F::one() / radius;
     let center = vec![radius; dimensions];
-    let mut shape: Vec<usize> = Vec::new();
-    let mut index: Vec<f64> = Vec::new();
-    for i in 0..dimensions {
-        shape.push((radius * 2.0) as usize);
-        index.push(0.0);
-    }
-    let out = ndarray::ArrayD::from_shape_fn(shape, |index_info| {
+    let mut index: Vec<F> = vec![F::zero(); dimensions];
+    ndarray::ArrayD::from_shape_fn(shape, |index_info| {
         for i in 0..index.len() {
-            index[i] = index_info[i] as f64;
+            index[i] = float(index_info[i] as f64);
         }
         let dist = euclidean_dist(&index, &center);
         if dist > radius {
-            0.0
+            F::zero()
         } else {
-            sample_normal(dist * normalizer, 0.5, stddev)
+            sample_normal(dist * normalizer, float(0.5), stddev)
         }
-    });
-    out
+    })
 }
 
 /// Generates a kernel base of multiple radial gaussian "hyper-donuts" in n-dimensions.
@@ -142,38 +160,65 @@ pub fn gaussian_donut_nd(radius: usize, dimensions: usize, stddev: f64) -> ndarr
 /// Can be any positive real number but will later be normalized compared to other donuts.
 ///
 /// * `stddevs` - The standard deviations of each individual donut.
-pub fn multi_gaussian_donut_nd(
+pub fn multi_gaussian_donut_nd<F: LeniaFloat>(
     radius: usize,
     dimensions: usize,
-    means: &[f64],
-    peaks: &[f64],
-    stddevs: &[f64],
-) -> ndarray::ArrayD<f64> {
-    let radius = radius as f64;
-    let normalizer = 1.0 / radius;
+    means: &[F],
+    peaks: &[F],
+    stddevs: &[F],
+) -> ndarray::ArrayD<F> {
+    let shape = vec![radius * 2; dimensions];
+    let radius: F = float(radius as f64);
+    let normalizer = F::one() / radius;
     let center = vec![radius; dimensions];
-    let mut shape: Vec<usize> = Vec::new();
-    let mut index: Vec<f64> = Vec::new();
-    for i in 0..dimensions {
-        shape.push((radius * 2.0) as usize);
-        index.push(0.0);
-    }
-    let out = ndarray::ArrayD::from_shape_fn(shape, |index_info| {
+    let mut index: Vec<F> = vec![F::zero(); dimensions];
+    ndarray::ArrayD::from_shape_fn(shape, |index_info| {
         for i in 0..index.len() {
-            index[i] = index_info[i] as f64;
+            index[i] = float(index_info[i] as f64);
         }
         let dist = euclidean_dist(&index, &center);
         if dist > radius {
-            0.0
+            F::zero()
         } else {
-            let mut sum = 0.0;
-            for i in 0..means.len() {
-                sum += sample_normal(dist * normalizer, means[i], stddevs[i]) * peaks[i].abs();
-            }
-            sum
+            multi_gaussian_donut_profile(dist * normalizer, means, peaks, stddevs).0
         }
-    });
-    out
+    })
+}
+
+/// Samples the radial profile of the gaussian "donut" kernels, along with its slope.
+///
+/// This is the continuous version of `multi_gaussian_donut_nd()`, usable at any distance from the kernel center,
+/// such as between the particles of `ParticleLenia`.
+///
+/// ### Parameters
+///
+/// * `distance` - Distance from the center of the kernel, where `0.0` is the center point of the kernel and
+/// `1.0` is its outer edge.
+///
+/// * `means` - The placement of the peak values of individual donuts.
+///
+/// * `peaks` - The maximum value that each individual donut can create.
+///
+/// * `stddevs` - The standard deviations of each individual donut.
+///
+/// ### Returns
+///
+/// The value of the profile at `distance` and its derivative with respect to `distance`.
+/// The profile is not cut off beyond the outer edge of the kernel, that is left up to the caller.
+pub fn multi_gaussian_donut_profile<F: LeniaFloat>(
+    distance: F,
+    means: &[F],
+    peaks: &[F],
+    stddevs: &[F],
+) -> (F, F) {
+    let mut sum = F::zero();
+    let mut slope = F::zero();
+    for i in 0..means.len() {
+        let value = sample_normal(distance, means[i], stddevs[i]) * peaks[i].abs();
+        sum += value;
+        slope -= value * (distance - means[i]) / (stddevs[i] * stddevs[i]);
+    }
+    (sum, slope)
 }
 
 /// Generates a kernel base of a radially symmetric sampling of precalculated values.
@@ -187,32 +232,27 @@ pub fn multi_gaussian_donut_nd(
 /// * `params[0..n]` - Value to set based on the distance from the center of the kernel
 /// to the outer edge of the kernel, where `params[0]` is the value at the kernel center
 /// and `params[1]` is the value at the edge of the kernel.
-pub fn precalculated_linear(
+pub fn precalculated_linear<F: LeniaFloat>(
     radius: usize,
     dimensions: usize,
-    params: &[f64],
-) -> ndarray::ArrayD<f64> {
-    let radius = radius as f64;
-    let normalizer = 1.0 / radius;
+    params: &[F],
+) -> ndarray::ArrayD<F> {
+    let shape = vec![radius * 2; dimensions];
+    let radius: F = float(radius as f64);
+    let normalizer = F::one() / radius;
     let center = vec![radius; dimensions];
-    let mut shape: Vec<usize> = Vec::new();
-    let mut index: Vec<f64> = Vec::new();
-    for i in 0..dimensions {
-        shape.push((radius * 2.0) as usize);
-        index.push(0.0);
-    }
-    let out = ndarray::ArrayD::from_shape_fn(shape, |index_info| {
+    let mut index: Vec<F> = vec![F::zero(); dimensions];
+    ndarray::ArrayD::from_shape_fn(shape, |index_info| {
         for i in 0..index.len() {
-            index[i] = index_info[i] as f64;
+            index[i] = float(index_info[i] as f64);
         }
         let dist = euclidean_dist(&index, &center);
         if dist > radius {
-            0.0
+            F::zero()
         } else {
             growth_functions::precalculated_linear(dist * normalizer, params)
         }
-    });
-    out
+    })
 }
 
 /// Generates a kernel base of "polynomial donuts".
@@ -225,45 +265,48 @@ pub fn precalculated_linear(
 /// * `params[0]` - Polynomial power, usually set to `4.0`;
 ///
 /// * `params[1..n]` - Peak heights of the individual donuts.
-pub fn polynomial_nd(radius: usize, dimensions: usize, params: &[f64]) -> ndarray::ArrayD<f64> {
-    let radius = radius as f64;
-    let normalizer = 1.0 / radius;
+pub fn polynomial_nd<F: LeniaFloat>(
+    radius: usize,
+    dimensions: usize,
+    params: &[F],
+) -> ndarray::ArrayD<F> {
+    let shape = vec![radius * 2; dimensions];
+    let radius: F = float(radius as f64);
+    let normalizer = F::one() / radius;
     let center = vec![radius; dimensions];
-    let mut shape: Vec<usize> = Vec::new();
-    let mut index: Vec<f64> = Vec::new();
-    for i in 0..dimensions {
-        shape.push((radius * 2.0) as usize);
-        index.push(0.0);
-    }
-    let out = ndarray::ArrayD::from_shape_fn(shape, |index_info| {
+    let mut index: Vec<F> = vec![F::zero(); dimensions];
+    ndarray::ArrayD::from_shape_fn(shape, |index_info| {
         for i in 0..index.len() {
-            index[i] = index_info[i] as f64;
+            index[i] = float(index_info[i] as f64);
         }
         let dist = euclidean_dist(&index, &center);
         if dist > radius {
-            0.0
+            F::zero()
         } else {
             let dist = dist * normalizer;
-            if dist == 0.0 {
-                0.0
+            if dist == F::zero() {
+                F::zero()
             } else {
-                let peak_index = (dist * (params.len() - 1) as f64).ceil() as usize;
+                let peak_index = (dist * float((params.len() - 1) as f64))
+                    .ceil()
+                    .to_usize()
+                    .unwrap();
                 params[peak_index]
                     * c(
-                        (params.len() - 1) as f64 * dist - (peak_index - 1) as f64,
+                        float::<F>((params.len() - 1) as f64) * dist
+                            - float((peak_index - 1) as f64),
                         params[0],
                     )
             }
         }
-    });
-    out
+    })
 }
 
 /// Refer to Lenia paper or someone more versed in mathematics, I have no clue... I just translated the math into code...
-fn c(r: f64, alpha: f64) -> f64 {
-    let num = 4.0 * r * (1.0 - r);
-    let mut out = 1.0;
-    for _ in 0..(alpha as usize) {
+fn c<F: LeniaFloat>(r: F, alpha: F) -> F {
+    let num = float::<F>(4.0) * r * (F::one() - r);
+    let mut out = F::one();
+    for _ in 0..alpha.to_usize().unwrap_or(0) {
         out *= num;
     }
     out
@@ -272,9 +315,9 @@ fn c(r: f64, alpha: f64) -> f64 {
 /// Moore neighborhood with radius of 1 in 2D.
 ///
 /// This is the kernel to use for Conway's game of life.
-pub fn conway_game_of_life() -> ndarray::ArrayD<f64> {
-    let mut out = ndarray::ArrayD::from_elem(vec![3 as usize, 3], 1.0);
-    out[[1, 1]] = 0.0;
+pub fn conway_game_of_life<F: LeniaFloat>() -> ndarray::ArrayD<F> {
+    let mut out = ndarray::ArrayD::from_elem(vec![3_usize, 3], F::one());
+    out[[1, 1]] = F::zero();
     out
 }
 
@@ -290,30 +333,34 @@ pub fn conway_game_of_life() -> ndarray::ArrayD<f64> {
 ///
 /// * `width_ratio` - Controls the width of the neighborhood ring around the center, where `0.0` is empty kernel
 /// and `1.0` is a completely filled in disk. Use `0.5` for default SmoothLife.
-pub fn smoothlife(radius: usize, dimensions: usize, width_ratio: f64) -> ndarray::ArrayD<f64> {
-    let width_ratio = width_ratio.clamp(0.0, 1.0);
-    let center = vec![radius as f64; dimensions];
+pub fn smoothlife<F: LeniaFloat>(
+    radius: usize,
+    dimensions: usize,
+    width_ratio: F,
+) -> ndarray::ArrayD<F> {
+    let width_ratio = num_traits::clamp(width_ratio, F::zero(), F::one());
+    let half: F = float(0.5);
+    let center = vec![float::<F>(radius as f64); dimensions];
     let shape = vec![radius * 2; dimensions];
-    let out = ndarray::ArrayD::from_shape_fn(shape, |index_info| {
+    ndarray::ArrayD::from_shape_fn(shape, |index_info| {
         let mut index = Vec::with_capacity(dimensions);
         for i in 0..dimensions {
-            index.push(index_info[i] as f64);
+            index.push(float::<F>(index_info[i] as f64));
         }
         let mut dist = euclidean_dist(&index, &center);
-        dist /= radius as f64;
-        if dist > (1.0 - ((1.0 - width_ratio) * 0.5)) {
-            0.0
-        } else if dist < ((1.0 - width_ratio) * 0.5) {
-            0.0
+        dist /= float(radius as f64);
+        if dist > (F::one() - ((F::one() - width_ratio) * half))
+            || dist < ((F::one() - width_ratio) * half)
+        {
+            F::zero()
         } else {
-            1.0
+            F::one()
         }
-    });
-    out
+    })
 }
 
 /// Generates a kernel base of a single pixel with n-dimensions.
-pub fn pass(dimensions: usize) -> ndarray::ArrayD<f64> {
+pub fn pass<F: LeniaFloat>(dimensions: usize) -> ndarray::ArrayD<F> {
     let unit_shape: Vec<usize> = vec![1; dimensions];
-    ndarray::ArrayD::<f64>::from_shape_fn(unit_shape, |a| 1.0)
+    ndarray::ArrayD::<F>::from_shape_fn(unit_shape, |a| F::one())
 }
@@ -1,10 +1,10 @@
 //! Collection of different types of Lenia systems.
 
-use super::fft::ParPlannedFFTND;
 use super::*;
 use num_complex::Complex;
-use std::sync::{Arc, Mutex, RwLock};
-use std::thread;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use rayon::prelude::*;
 
 /// Standard type of Lenia
 ///
@@ -22,26 +22,62 @@ use std::thread;
 /// Initializes a Lenia instance capable of making a soliton.
 ///
 /// ```
-/// let starting_pattern: ndarray::ArrayD<f64>; // fill with your data
+/// # use road_to_lenia::{lenias::StandardLenia, Simulator};
+/// # fn display(_: &ndarray::ArrayD<f64>) {}
 /// let channel_shape: Vec<usize> = vec![100, 100];
+/// let starting_pattern = ndarray::ArrayD::<f64>::zeros(channel_shape.clone()); // fill with your data
 /// let mut simulator = Simulator::<StandardLenia>::new(&channel_shape);
 /// simulator.fill_channel(&starting_pattern, 0);
-/// while true {
+/// for _ in 0..10 {
 ///     simulator.iterate();
-///     display(get_channel_as_ref(0));
+///     display(simulator.get_channel_as_ref(0));
 /// }
 /// ```
-pub struct StandardLenia {
-    dt: f64,
-    channel: Channel,
+pub struct StandardLenia<F: LeniaFloat = f64> {
+    dt: F,
+    channel: Channel<F>,
     shape: Vec<usize>,
-    conv_channel: ConvolutionChannel,
-    convolved: ndarray::ArrayD<Complex<f64>>,
-    forward_fft_instance: fft::ParPlannedFFTND,
-    inverse_fft_instance: fft::ParPlannedFFTND,
+    conv_channel: ConvolutionChannel<F>,
+    convolved: ndarray::ArrayD<F>,
+    spectrum: ndarray::ArrayD<Complex<F>>,
+    forward_fft_instance: fft::ParPlannedFFTND<F>,
+    inverse_fft_instance: fft::ParPlannedFFTND<F>,
+    boundaries: Vec<BoundaryCondition>,
+    // Padding of each axis of the channel before fft, needed for non-periodic boundaries.
+    padding: Vec<(usize, usize)>,
+    integrator: Integrator,
 }
 
-impl Lenia for StandardLenia {
+impl<F: LeniaFloat> StandardLenia<F> {
+    /// Re-plans the fft and re-makes the kernel if the kernel or the boundary conditions
+    /// require a different padding of the channel.
+    fn update_padding(&mut self) {
+        let padding = fft_padding(self.conv_channel.kernel.base.shape(), &self.boundaries);
+        if padding == self.padding {
+            return;
+        }
+        let fft_shape: Vec<usize> = self
+            .shape
+            .iter()
+            .zip(&padding)
+            .map(|(n, (before, after))| n + before + after)
+            .collect();
+        self.forward_fft_instance = fft::ParPlannedFFTND::new_real(&fft_shape, false);
+        self.inverse_fft_instance = fft::ParPlannedFFTND::new_real(&fft_shape, true);
+        self.spectrum = ndarray::ArrayD::from_elem(
+            fft::spectrum_shape(&fft_shape),
+            Complex::new(F::zero(), F::zero()),
+        );
+        self.conv_channel.kernel = Kernel::from_padded(
+            self.conv_channel.kernel.base.clone(),
+            &self.shape,
+            &fft_shape,
+        );
+        self.padding = padding;
+    }
+}
+
+impl<F: LeniaFloat> NewLenia for StandardLenia<F> {
     /// Create and initialize a new instance of "Standard Lenia".
     ///
     /// This version of Lenia
@@ -63,114 +99,175 @@ impl Lenia for StandardLenia {
     ///
     /// * If either of the axis lengths in `shape` are `<28`.
     fn new(shape: &[usize]) -> Self {
+        match Self::try_new(shape) {
+            Ok(lenia) => lenia,
+            Err(error) => panic!("StandardLenia::new() - {}", error),
+        }
+    }
+
+    fn try_new(shape: &[usize]) -> Result<Self, LeniaError> {
         if shape.len() != 2 {
-            panic!(
-                "StandardLenia::new() - Expected 2 dimensions for Standard Lenia! Found {}.",
+            return Err(LeniaError::InvalidShape(format!(
+                "Expected 2 dimensions for Standard Lenia! Found {}.",
                 shape.len()
-            );
+            )));
         }
         for (i, dim) in shape.iter().enumerate() {
             if *dim < 13 {
-                panic!("StandardLenia::new() - Axis {} is extremely small ({} pixels). Make it larger!", i, *dim);
+                return Err(LeniaError::InvalidShape(format!(
+                    "Axis {} is extremely small ({} pixels). Make it larger!",
+                    i, *dim
+                )));
             }
         }
-        let kernel = Kernel::from(kernels::gaussian_donut_2d(13, 1.0 / 6.7), shape);
+        let kernel = Kernel::from(kernels::gaussian_donut_2d(13, float(1.0 / 6.7)), shape);
 
         let conv_channel = ConvolutionChannel {
             input_channel: 0,
             kernel,
-            field: ndarray::ArrayD::from_elem(shape, 0.0),
-            growth: growth_functions::standard_lenia,
-            growth_params: vec![0.15, 0.017],
+            field: ndarray::ArrayD::from_elem(shape, F::zero()),
+            growth: Arc::new(growth_functions::StandardLenia::new(
+                float(0.15),
+                float(0.017),
+            )),
+            parameter_maps: Vec::new(),
+            backend: ConvolutionBackend::Automatic,
         };
 
         let channel = Channel {
-            field: ndarray::ArrayD::from_elem(shape, 0.0),
-            weights: vec![1.0],
-            weight_sum_reciprocal: 1.0,
+            field: ndarray::ArrayD::from_elem(shape, F::zero()),
+            weights: vec![F::one()],
+            weight_sum_reciprocal: F::one(),
+            update_mode: UpdateMode::Additive,
+            value_range: ValueRange::default(),
         };
 
-        StandardLenia {
-            forward_fft_instance: fft::ParPlannedFFTND::new(shape, false),
-            inverse_fft_instance: fft::ParPlannedFFTND::new(shape, true),
-            dt: 0.1,
+        Ok(StandardLenia {
+            forward_fft_instance: fft::ParPlannedFFTND::new_real(shape, false),
+            inverse_fft_instance: fft::ParPlannedFFTND::new_real(shape, true),
+            dt: float(0.1),
             channel,
             shape: shape.to_vec(),
             conv_channel,
-            convolved: ndarray::ArrayD::from_elem(shape, Complex::new(0.0, 0.0)),
-        }
+            convolved: ndarray::ArrayD::from_elem(shape, F::zero()),
+            spectrum: ndarray::ArrayD::from_elem(
+                fft::spectrum_shape(shape),
+                Complex::new(F::zero(), F::zero()),
+            ),
+            boundaries: vec![BoundaryCondition::Periodic; shape.len()],
+            padding: vec![(0, 0); shape.len()],
+            integrator: Integrator::Euler,
+        })
     }
+}
 
-    fn iterate(&mut self) {
-        let field = self.channel.field.clone();
-        let params = self.conv_channel.growth_params.clone();
-        self.convolved.zip_mut_with(&field, |a, b| {
-            a.re = *b;
-            a.im = 0.0;
-        });
-        drop(field);
-        self.forward_fft_instance.transform(&mut self.convolved);
-
-        self.convolved
-            .zip_mut_with(&self.conv_channel.kernel.transformed, |a, b| {
-                // Complex multiplication without cloning
-                let real = (a.re * b.re) - (a.im * b.im);
-                a.im = ((a.re + a.im) * (b.re + b.im)) - real;
-                a.re = real;
-            });
+impl<F: LeniaFloat> Lenia for StandardLenia<F> {
+    type Float = F;
 
-        self.inverse_fft_instance.transform(&mut self.convolved);
+    fn iterate(&mut self) {
+        integrate(self, self.integrator, self.dt);
+    }
 
-        for (a, b) in self
-            .conv_channel
-            .field
-            .iter_mut()
-            .zip(self.convolved.iter())
-        {
-            *a = (&self.conv_channel.growth)(b.re, &params);
-        }
+    fn set_channels(&mut self, num_channels: usize) -> Result<(), LeniaError> {
+        Err(LeniaError::Unsupported("Changing the number of channels is not available for Standard Lenia! Try using a different Lenia instead.".to_string()))
+    }
 
-        for (a, b) in self
-            .channel
-            .field
-            .iter_mut()
-            .zip(self.conv_channel.field.iter())
-        {
-            *a = (*a + (*b * self.dt)).clamp(0.0, 1.0);
-        }
+    fn set_conv_channels(&mut self, num_conv_channels: usize) -> Result<(), LeniaError> {
+        Err(LeniaError::Unsupported("Changing the number of convolution channels is not available for Standard Lenia! Try using a different Lenia instead.".to_string()))
     }
 
-    fn set_channels(&mut self, num_channels: usize) {
-        println!("Changing the number of channels is not available for Standard Lenia! Try using a different Lenia instead.");
+    fn set_source_channel(
+        &mut self,
+        conv_channel: usize,
+        src_channel: usize,
+    ) -> Result<(), LeniaError> {
+        Err(LeniaError::Unsupported("Adding or changing source channels is not available for Standard Lenia! Try using a different Lenia instead.".to_string()))
     }
 
-    fn set_conv_channels(&mut self, num_conv_channels: usize) {
-        println!("Changing the number of channels is not available for Standard Lenia! Try using a different Lenia instead.");
+    fn set_weights(&mut self, new_weights: &[F], conv_channel: usize) -> Result<(), LeniaError> {
+        Err(LeniaError::Unsupported("Adding or changing convolution output weights is not available for Standard Lenia! Try using a different Lenia instead.".to_string()))
     }
 
-    fn set_source_channel(&mut self, conv_channel: usize, src_channel: usize) {
-        println!("Adding or changing source channels is not available for Standard Lenia! Try using a different Lenia instead.");
+    fn set_kernel(
+        &mut self,
+        kernel: ndarray::ArrayD<F>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, 1)?;
+        self.conv_channel.kernel =
+            Kernel::try_from_padded(kernel, &self.shape, self.forward_fft_instance.shape())?;
+        self.update_padding();
+        Ok(())
     }
 
-    fn set_weights(&mut self, new_weights: &[f64], conv_channel: usize) {
-        println!("Adding or changing convolution output weights is not available for Standard Lenia! Try using a different Lenia instead.");
+    fn set_growth(
+        &mut self,
+        growth: Arc<dyn GrowthFunction<F>>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, 1)?;
+        check_growth_function(&*growth)?;
+        self.conv_channel.growth = growth;
+        self.conv_channel.parameter_maps.clear();
+        Ok(())
     }
 
-    fn set_kernel(&mut self, kernel: ndarray::ArrayD<f64>, conv_channel: usize) {
-        self.conv_channel.kernel = Kernel::from(kernel, self.channel.field.shape());
+    fn set_parameter_map(
+        &mut self,
+        map: Option<ndarray::ArrayD<F>>,
+        parameter: usize,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, 1)?;
+        set_parameter_map(&mut self.conv_channel, map, parameter, &self.shape)
     }
 
-    fn set_growth(
+    fn set_backend(
         &mut self,
-        f: fn(f64, &[f64]) -> f64,
-        growth_params: Vec<f64>,
+        backend: ConvolutionBackend,
         conv_channel: usize,
-    ) {
-        self.conv_channel.growth = f;
-        self.conv_channel.growth_params = growth_params;
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, 1)?;
+        self.conv_channel.backend = backend;
+        Ok(())
+    }
+
+    fn set_boundaries(&mut self, boundaries: &[BoundaryCondition]) -> Result<(), LeniaError> {
+        check_boundaries(boundaries, &self.shape)?;
+        self.boundaries = boundaries.to_vec();
+        self.update_padding();
+        Ok(())
+    }
+
+    fn set_integrator(&mut self, integrator: Integrator) -> Result<(), LeniaError> {
+        self.integrator = integrator;
+        Ok(())
+    }
+
+    fn set_update_mode(&mut self, mode: UpdateMode, channel: usize) -> Result<(), LeniaError> {
+        check_channel_index(channel, 1)?;
+        self.channel.update_mode = mode;
+        Ok(())
+    }
+
+    fn set_value_range(&mut self, range: ValueRange<F>, channel: usize) -> Result<(), LeniaError> {
+        check_channel_index(channel, 1)?;
+        check_value_range(&range)?;
+        self.channel.value_range = range;
+        Ok(())
+    }
+
+    fn set_stochasticity(
+        &mut self,
+        stochasticity: Option<Stochasticity<F>>,
+    ) -> Result<(), LeniaError> {
+        match stochasticity {
+            Some(_) => Err(LeniaError::Unsupported("Stochastic update steps are not available for Standard Lenia! Try using a different Lenia instead.".to_string())),
+            None => Ok(()),
+        }
     }
 
-    fn set_dt(&mut self, new_dt: f64) {
+    fn set_dt(&mut self, new_dt: F) {
         self.dt = new_dt;
     }
 
@@ -178,45 +275,162 @@ impl Lenia for StandardLenia {
         &self.shape
     }
 
-    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64> {
+    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<F> {
         &self.channel.field
     }
 
-    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel {
+    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel<F> {
         &self.conv_channel.kernel
     }
 
-    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<f64> {
+    fn growth_function(&self, conv_channel: usize) -> &dyn GrowthFunction<F> {
+        &*self.conv_channel.growth
+    }
+
+    fn parameter_map(&self, parameter: usize, conv_channel: usize) -> Option<&ndarray::ArrayD<F>> {
+        self.conv_channel
+            .parameter_maps
+            .get(parameter)
+            .and_then(|map| map.as_ref())
+    }
+
+    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<F> {
         &mut self.channel.field
     }
 
-    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<Complex<f64>> {
+    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<F> {
         &self.convolved
     }
 
-    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<f64> {
+    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<F> {
         &self.conv_channel.field
     }
 
-    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64> {
+    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<F> {
         &self.conv_channel.field // Same as growth result because weights are not available for Standard Lenia
     }
 
-    fn dt(&self) -> f64 {
+    fn dt(&self) -> F {
         self.dt
     }
 
     fn channels(&self) -> usize {
-        1 as usize
+        1
     }
 
     fn conv_channels(&self) -> usize {
-        1 as usize
+        1
+    }
+
+    fn backend(&self, conv_channel: usize) -> ConvolutionBackend {
+        self.conv_channel.backend
+    }
+
+    fn source_channel(&self, conv_channel: usize) -> usize {
+        self.conv_channel.input_channel
+    }
+
+    fn boundaries(&self) -> &[BoundaryCondition] {
+        &self.boundaries
+    }
+
+    fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    fn update_mode(&self, channel: usize) -> UpdateMode {
+        self.channel.update_mode
+    }
+
+    fn value_range(&self, channel: usize) -> ValueRange<F> {
+        self.channel.value_range
+    }
+
+    fn stochasticity(&self) -> Option<Stochasticity<F>> {
+        None
     }
 
-    fn weights(&self, channel: usize) -> &[f64] {
+    fn weights(&self, channel: usize) -> &[F] {
         &self.channel.weights
     }
+
+    fn write_state(&self, writer: &mut checkpoint::StateWriter) -> Result<(), LeniaError> {
+        writer.write_str("StandardLenia")?;
+        writer.write_float(self.dt)?;
+        writer.write_boundaries(&self.boundaries)?;
+        writer.write_integrator(self.integrator)?;
+        writer.write_channel(&self.channel)?;
+        writer.write_convolution_channel(&self.conv_channel)?;
+        writer.write_array(&self.convolved)
+    }
+
+    fn read_state(
+        &mut self,
+        reader: &mut checkpoint::StateReader,
+        registry: &rules::Registry<F>,
+    ) -> Result<(), LeniaError> {
+        reader.expect_tag("StandardLenia")?;
+        self.dt = reader.read_float()?;
+        let boundaries = reader.read_boundaries()?;
+        self.set_boundaries(&boundaries)?;
+        self.integrator = reader.read_integrator()?;
+        reader.read_channel(&mut self.channel)?;
+        let kernel = reader.read_convolution_channel(&mut self.conv_channel, registry)?;
+        self.set_kernel(kernel, 0)?;
+        self.convolved = reader.read_array_of_shape(&self.shape)?;
+        Ok(())
+    }
+}
+
+impl<F: LeniaFloat> Integrable<F> for StandardLenia<F> {
+    fn update_deltas(&mut self) {
+        if self
+            .conv_channel
+            .backend
+            .is_direct(&self.conv_channel.kernel, self.channel.field.len())
+        {
+            direct::convolve(
+                &self.channel.field,
+                &self.conv_channel.kernel.normalized,
+                &mut self.convolved,
+                &self.boundaries,
+            );
+        } else {
+            transform_padded(
+                &mut self.forward_fft_instance,
+                &self.channel.field,
+                &self.padding,
+                &self.boundaries,
+                &mut self.spectrum,
+            );
+
+            self.spectrum
+                .zip_mut_with(&self.conv_channel.kernel.transformed, |a, b| {
+                    // Complex multiplication without cloning
+                    let real = (a.re * b.re) - (a.im * b.im);
+                    a.im = (a.re * b.im) + (a.im * b.re);
+                    a.re = real;
+                });
+
+            transform_padded_inverse(
+                &mut self.inverse_fft_instance,
+                &mut self.spectrum,
+                &self.padding,
+                &mut self.convolved,
+            );
+        }
+
+        apply_growth(&mut self.conv_channel, &self.convolved);
+    }
+
+    fn fields_and_deltas(&mut self) -> Vec<ChannelUpdate<'_, F>> {
+        vec![(
+            &mut self.channel.field,
+            &self.conv_channel.field,
+            self.channel.update_mode,
+            self.channel.value_range,
+        )]
+    }
 }
 
 /// Expanded type of Lenia
@@ -233,10 +447,12 @@ impl Lenia for StandardLenia {
 /// to the channel_1 solitons.
 ///
 /// ```
+/// # use road_to_lenia::{growth_functions, kernels, lenias::ExpandedLenia, Simulator};
+/// # fn display(_: &ndarray::ArrayD<f64>) {}
 /// // initialize
-/// let starting_pattern0: ndarray::ArrayD<f64>; // fill with your data
-/// let starting_pattern1: ndarray::ArrayD<f64>; // fill with your data
 /// let channel_shape: Vec<usize> = vec![100, 100];
+/// let starting_pattern0 = ndarray::ArrayD::<f64>::zeros(channel_shape.clone()); // fill with your data
+/// let starting_pattern1 = ndarray::ArrayD::<f64>::zeros(channel_shape.clone()); // fill with your data
 /// let mut simulator = Simulator::<ExpandedLenia>::new(&channel_shape);
 /// // set up the simulation
 /// simulator.set_channels(2);
@@ -247,33 +463,173 @@ impl Lenia for StandardLenia {
 /// simulator.set_kernel(kernels::gaussian_donut_2d(14, 0.15), 0);
 /// simulator.set_kernel(kernels::polynomial_nd(25, 2, &vec![4.0, 1.0, 0.333]), 1);
 /// simulator.set_kernel(kernels::polynomial_nd(21, 2, &vec![4.0, 0.0, 1.0]), 2);
-/// simulator.set_growth_function(growth_functions::standard_lenia, vec![0.15, 0.02], 0);
-/// simulator.set_growth_function(growth_functions::polynomial, vec![0.25, 0.03, 4.0], 1);
-/// simulator.set_growth_function(growth_functions::polynomial, vec![0.07, 0.026, 4.0], 2);
+/// simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.02), 0);
+/// simulator.set_growth_function(growth_functions::Polynomial::new(0.25, 0.03, 4.0), 1);
+/// simulator.set_growth_function(growth_functions::Polynomial::new(0.07, 0.026, 4.0), 2);
 /// simulator.set_weights(0, &vec![2.0/3.0, 0.0, 1.0/3.0]);
 /// simulator.set_weights(1, &vec![0.0, 1.0, 0.0]);
 /// simulator.set_dt(0.1);
 /// // seed channels and simulate
 /// simulator.fill_channel(&starting_pattern0, 0);
 /// simulator.fill_channel(&starting_pattern1, 1);
-/// while true {
+/// for _ in 0..10 {
 ///     simulator.iterate();
-///     display(get_channel_as_ref(0));
-///     display(get_channel_as_ref(1));
+///     display(simulator.get_channel_as_ref(0));
+///     display(simulator.get_channel_as_ref(1));
 /// }
 /// ```
-pub struct ExpandedLenia {
-    dt: f64,
-    channels: Vec<Channel>,
-    deltas: Vec<ndarray::ArrayD<f64>>,
+pub struct ExpandedLenia<F: LeniaFloat = f64> {
+    dt: F,
+    channels: Vec<Channel<F>>,
+    deltas: Vec<ndarray::ArrayD<F>>,
     shape: Vec<usize>,
-    conv_channels: Vec<ConvolutionChannel>,
-    convolutions: Vec<ndarray::ArrayD<Complex<f64>>>,
-    forward_fft_instances: Vec<fft::ParPlannedFFTND>,
-    inverse_fft_instances: Vec<fft::ParPlannedFFTND>,
+    conv_channels: Vec<ConvolutionChannel<F>>,
+    convolutions: Vec<ndarray::ArrayD<F>>,
+    // One spectrum and forward transform per channel, shared by all convolution channels using it as a source.
+    spectra: Vec<ndarray::ArrayD<Complex<F>>>,
+    forward_fft_instances: Vec<fft::ParPlannedFFTND<F>>,
+    // One spectrum and inverse transform per convolution channel.
+    convolution_spectra: Vec<ndarray::ArrayD<Complex<F>>>,
+    inverse_fft_instances: Vec<fft::ParPlannedFFTND<F>>,
+    boundaries: Vec<BoundaryCondition>,
+    // Padding of each axis of the channels before fft, needed for non-periodic boundaries.
+    // Large enough for the largest kernel along each axis.
+    padding: Vec<(usize, usize)>,
+    integrator: Integrator,
+    stochasticity: Option<Stochasticity<F>>,
+    // The generator behind `rand::rngs::StdRng`, whose position can be saved in checkpoints.
+    rng: ChaCha12Rng,
+    // Noise added onto the deltas of each channel, drawn once per iteration.
+    noise: Vec<ndarray::ArrayD<F>>,
+}
+
+impl<F: LeniaFloat> ExpandedLenia<F> {
+    /// Shape of the channels after padding.
+    fn fft_shape(&self) -> Vec<usize> {
+        self.shape
+            .iter()
+            .zip(&self.padding)
+            .map(|(n, (before, after))| n + before + after)
+            .collect()
+    }
+
+    /// Re-plans the ffts and re-makes the kernels if the kernels or the boundary conditions
+    /// require a different padding of the channels.
+    fn update_padding(&mut self) {
+        let mut kernel_shape = vec![1; self.shape.len()];
+        for conv_channel in &self.conv_channels {
+            for (max, len) in kernel_shape
+                .iter_mut()
+                .zip(conv_channel.kernel.base.shape())
+            {
+                *max = (*max).max(*len);
+            }
+        }
+        let padding = fft_padding(&kernel_shape, &self.boundaries);
+        if padding == self.padding {
+            return;
+        }
+        self.padding = padding;
+        let fft_shape = self.fft_shape();
+        let spectrum = ndarray::ArrayD::from_elem(
+            fft::spectrum_shape(&fft_shape),
+            Complex::new(F::zero(), F::zero()),
+        );
+        for (spectrum_buffer, fft_instance) in self
+            .spectra
+            .iter_mut()
+            .zip(self.forward_fft_instances.iter_mut())
+        {
+            *spectrum_buffer = spectrum.clone();
+            *fft_instance = fft::ParPlannedFFTND::new_real(&fft_shape, false);
+        }
+        for ((spectrum_buffer, fft_instance), conv_channel) in self
+            .convolution_spectra
+            .iter_mut()
+            .zip(self.inverse_fft_instances.iter_mut())
+            .zip(self.conv_channels.iter_mut())
+        {
+            *spectrum_buffer = spectrum.clone();
+            *fft_instance = fft::ParPlannedFFTND::new_real(&fft_shape, true);
+            conv_channel.kernel =
+                Kernel::from_padded(conv_channel.kernel.base.clone(), &self.shape, &fft_shape);
+        }
+    }
+
+    /// Writes the state of the Lenia instance into a checkpoint, without the tag of its type.
+    fn write_fields(&self, writer: &mut checkpoint::StateWriter) -> Result<(), LeniaError> {
+        writer.write_float(self.dt)?;
+        writer.write_boundaries(&self.boundaries)?;
+        writer.write_integrator(self.integrator)?;
+        writer.write_usize(self.channels.len())?;
+        writer.write_usize(self.conv_channels.len())?;
+        for (channel, deltas) in self.channels.iter().zip(&self.deltas) {
+            writer.write_channel(channel)?;
+            writer.write_array(deltas)?;
+        }
+        for (conv_channel, convolution) in self.conv_channels.iter().zip(&self.convolutions) {
+            writer.write_convolution_channel(conv_channel)?;
+            writer.write_array(convolution)?;
+        }
+        writer.write_stochasticity(&self.stochasticity, &self.rng)
+    }
+
+    /// Restores the state written by `write_fields()`.
+    fn read_fields(
+        &mut self,
+        reader: &mut checkpoint::StateReader,
+        registry: &rules::Registry<F>,
+    ) -> Result<(), LeniaError> {
+        self.dt = reader.read_float()?;
+        let boundaries = reader.read_boundaries()?;
+        check_boundaries(&boundaries, &self.shape)?;
+        self.integrator = reader.read_integrator()?;
+        let channels = reader.read_usize()?;
+        let conv_channels = reader.read_usize()?;
+        if channels == 0 || conv_channels == 0 {
+            return Err(LeniaError::Format(
+                "The checkpoint holds no channels or no convolution channels.".to_string(),
+            ));
+        }
+        // The sources are read back below, they must not keep the channels from being dropped.
+        for conv_channel in &mut self.conv_channels {
+            conv_channel.input_channel = 0;
+        }
+        self.set_channels(channels)?;
+        self.set_conv_channels(conv_channels)?;
+        self.set_boundaries(&boundaries)?;
+        for i in 0..channels {
+            reader.read_channel(&mut self.channels[i])?;
+            if self.channels[i].weights.len() != conv_channels {
+                return Err(LeniaError::Format(format!(
+                    "The checkpoint holds {} weights for channel {}, but {} convolution channels.",
+                    self.channels[i].weights.len(),
+                    i,
+                    conv_channels
+                )));
+            }
+            self.deltas[i] = reader.read_array_of_shape(&self.shape)?;
+        }
+        for i in 0..conv_channels {
+            let kernel = reader.read_convolution_channel(&mut self.conv_channels[i], registry)?;
+            if self.conv_channels[i].input_channel >= channels {
+                return Err(LeniaError::ChannelOutOfRange {
+                    channel: self.conv_channels[i].input_channel,
+                    channels,
+                });
+            }
+            self.set_kernel(kernel, i)?;
+            self.convolutions[i] = reader.read_array_of_shape(&self.shape)?;
+        }
+        let (stochasticity, rng) = reader.read_stochasticity()?;
+        self.stochasticity = stochasticity;
+        self.rng = rng;
+        self.noise.clear();
+        Ok(())
+    }
 }
 
-impl Lenia for ExpandedLenia {
+impl<F: LeniaFloat> NewLenia for ExpandedLenia<F> {
     /// Create and initialize a new instance of "ExpandedLenia`.
     ///
     /// This type of Lenia is much more powerful than `StandardLenia` as it can have n-dimensional fields,
@@ -290,12 +646,24 @@ impl Lenia for ExpandedLenia {
     /// If any dimension/axis in `shape` is 0. This is not allowed, generally each dimension/axis should be
     /// relatively large.
     fn new(shape: &[usize]) -> Self {
+        match Self::try_new(shape) {
+            Ok(lenia) => lenia,
+            Err(error) => panic!("ExpandedLenia::new() - {}", error),
+        }
+    }
+
+    fn try_new(shape: &[usize]) -> Result<Self, LeniaError> {
+        if shape.is_empty() {
+            return Err(LeniaError::InvalidShape(
+                "The channels must have at least 1 dimension/axis!".to_string(),
+            ));
+        }
         for (i, dim) in shape.iter().enumerate() {
             if *dim == 0 {
-                panic!(
-                    "ExpandedLenia::new() - Dimension/axis {} is 0! This is not allowed!",
+                return Err(LeniaError::InvalidShape(format!(
+                    "Dimension/axis {} is 0! This is not allowed!",
                     i
-                );
+                )));
             }
         }
         let kernel = Kernel::from(kernels::pass(shape.len()), shape);
@@ -303,15 +671,18 @@ impl Lenia for ExpandedLenia {
         let conv_channel = ConvolutionChannel {
             input_channel: 0,
             kernel,
-            field: ndarray::ArrayD::from_elem(shape, 0.0),
-            growth: growth_functions::pass,
-            growth_params: vec![1.0],
+            field: ndarray::ArrayD::from_elem(shape, F::zero()),
+            growth: Arc::new(growth_functions::Pass::new(F::one())),
+            parameter_maps: Vec::new(),
+            backend: ConvolutionBackend::Automatic,
         };
 
         let channel = Channel {
-            field: ndarray::ArrayD::from_elem(shape, 0.0),
-            weights: vec![1.0],
-            weight_sum_reciprocal: 1.0,
+            field: ndarray::ArrayD::from_elem(shape, F::zero()),
+            weights: vec![F::one()],
+            weight_sum_reciprocal: F::one(),
+            update_mode: UpdateMode::Additive,
+            value_range: ValueRange::default(),
         };
 
         let mut channel_shape = Vec::new();
@@ -319,312 +690,302 @@ impl Lenia for ExpandedLenia {
             channel_shape.push(*dim);
         }
 
-        ExpandedLenia {
-            forward_fft_instances: vec![fft::ParPlannedFFTND::new(&channel_shape, false)],
-            inverse_fft_instances: vec![fft::ParPlannedFFTND::new(&channel_shape, true)],
-            dt: 0.1,
+        Ok(ExpandedLenia {
+            forward_fft_instances: vec![fft::ParPlannedFFTND::new_real(&channel_shape, false)],
+            inverse_fft_instances: vec![fft::ParPlannedFFTND::new_real(&channel_shape, true)],
+            dt: float(0.1),
             channels: vec![channel],
-            deltas: vec![ndarray::ArrayD::from_elem(shape, 0.0)],
+            deltas: vec![ndarray::ArrayD::from_elem(shape, F::zero())],
             conv_channels: vec![conv_channel],
-            convolutions: vec![ndarray::ArrayD::from_elem(shape, Complex::new(0.0, 0.0))],
+            convolutions: vec![ndarray::ArrayD::from_elem(shape, F::zero())],
+            spectra: vec![ndarray::ArrayD::from_elem(
+                fft::spectrum_shape(shape),
+                Complex::new(F::zero(), F::zero()),
+            )],
+            convolution_spectra: vec![ndarray::ArrayD::from_elem(
+                fft::spectrum_shape(shape),
+                Complex::new(F::zero(), F::zero()),
+            )],
             shape: shape.to_vec(),
-        }
+            boundaries: vec![BoundaryCondition::Periodic; shape.len()],
+            padding: vec![(0, 0); shape.len()],
+            integrator: Integrator::Euler,
+            stochasticity: None,
+            rng: ChaCha12Rng::seed_from_u64(0),
+            noise: Vec::new(),
+        })
     }
+}
 
-    // This is a very long and complex function, sorry.
-    // It uses concurrency to calculate multiple convolutions at the same time, as well as
-    // apply weights and sum the results.
-    fn iterate(&mut self) {
-        let mut axes: Vec<usize> = Vec::with_capacity(self.shape.len());
-        let mut inverse_axes: Vec<usize> = Vec::with_capacity(self.shape.len());
-        for i in 0..self.shape.len() {
-            axes.push(i);
-        }
-        for i in (0..self.shape.len()).rev() {
-            inverse_axes.push(i);
-        }
-
-        //Create mutexes and rwlocks
-        let mut sources: Vec<usize> = Vec::with_capacity(self.conv_channels.len());
-        let mut channel_rwlocks: Vec<Arc<RwLock<Channel>>> =
-            Vec::with_capacity(self.channels.len());
-        let mut delta_rwlocks: Vec<Arc<RwLock<ndarray::ArrayD<f64>>>> =
-            Vec::with_capacity(self.deltas.len());
-        let mut conv_channel_mutexes: Vec<Arc<Mutex<ConvolutionChannel>>> =
-            Vec::with_capacity(self.conv_channels.len());
-        let mut convolution_mutexes: Vec<Arc<Mutex<ndarray::ArrayD<Complex<f64>>>>> =
-            Vec::with_capacity(self.convolutions.len());
-        let mut forward_fft_mutexes: Vec<Arc<Mutex<ParPlannedFFTND>>> =
-            Vec::with_capacity(self.forward_fft_instances.len());
-        let mut inverse_fft_mutexes: Vec<Arc<Mutex<ParPlannedFFTND>>> =
-            Vec::with_capacity(self.inverse_fft_instances.len());
-
-        for _ in 0..self.channels.len() {
-            channel_rwlocks.push(Arc::new(RwLock::new(self.channels.remove(0))));
-            delta_rwlocks.push(Arc::new(RwLock::new(self.deltas.remove(0))));
-        }
-        for _ in 0..self.conv_channels.len() {
-            sources.push(self.conv_channels[0].input_channel);
-            conv_channel_mutexes.push(Arc::new(Mutex::new(self.conv_channels.remove(0))));
-            convolution_mutexes.push(Arc::new(Mutex::new(self.convolutions.remove(0))));
-            forward_fft_mutexes.push(Arc::new(Mutex::new(self.forward_fft_instances.remove(0))));
-            inverse_fft_mutexes.push(Arc::new(Mutex::new(self.inverse_fft_instances.remove(0))));
-        }
-
-        // Concurrent convolutions
-        let mut convolution_handles = Vec::with_capacity(conv_channel_mutexes.len());
-
-        for i in 0..conv_channel_mutexes.len() {
-            // Set up and aquire locks on data
-            let axes_clone = axes.clone();
-            let inverse_axes_clone = inverse_axes.clone();
-            let source_lock = Arc::clone(&channel_rwlocks[sources[i]]);
-            let delta_lock = Arc::clone(&delta_rwlocks[sources[i]]);
-            let convolution_lock = Arc::clone(&convolution_mutexes[i]);
-            let convolution_channel_lock = Arc::clone(&conv_channel_mutexes[i]);
-            let forward_fft_lock = Arc::clone(&forward_fft_mutexes[i]);
-            let inverse_fft_lock = Arc::clone(&inverse_fft_mutexes[i]);
-
-            convolution_handles.push(thread::spawn(move || {
-                let mut convolution_channel = convolution_channel_lock.lock().unwrap();
-                let input = source_lock.read().unwrap();
-                let delta = delta_lock.read().unwrap();
-                let mut convolution = convolution_lock.lock().unwrap();
-                let mut forward_fft = forward_fft_lock.lock().unwrap();
-                let mut inverse_fft = inverse_fft_lock.lock().unwrap();
-                // Get data from source channel
-                convolution.zip_mut_with(&input.field, |a, b| {
-                    a.re = *b;
-                    a.im = 0.0;
-                });
-                // Fourier-transform convolute
-                // Forward fft the input data
-                forward_fft.transform(&mut convolution);
-                // Complex multiplication without cloning
-                convolution.zip_mut_with(&convolution_channel.kernel.transformed, |a, b| {
-                    let real = (a.re * b.re) - (a.im * b.im);
-                    a.im = ((a.re + a.im) * (b.re + b.im)) - real;
-                    a.re = real;
-                });
-                // Inverse fft to get convolution result
-                inverse_fft.transform(&mut convolution);
-                // Apply growth function
-                let growth_info = (
-                    convolution_channel.growth,
-                    convolution_channel.growth_params.clone(),
-                );
-                convolution_channel
-                    .field
-                    .zip_mut_with(&convolution, |a, b| {
-                        *a = (growth_info.0)(b.re, &growth_info.1);
-                    });
-            }));
-        }
-
-        let mut summing_handles = Vec::with_capacity(channel_rwlocks.len());
+impl<F: LeniaFloat> Lenia for ExpandedLenia<F> {
+    type Float = F;
 
-        for handle in convolution_handles {
-            handle.join().unwrap();
-        }
+    fn iterate(&mut self) {
+        let stochasticity = match self.stochasticity {
+            Some(stochasticity) => stochasticity,
+            None => return integrate(self, self.integrator, self.dt),
+        };
 
-        // Collapse convolution channel mutexes back into a single owned vector
-        let mut convolution_channels: Vec<ConvolutionChannel> =
-            Vec::with_capacity(conv_channel_mutexes.len());
-        for i in 0..conv_channel_mutexes.len() {
-            let data = conv_channel_mutexes.remove(0);
-            convolution_channels.push(Arc::try_unwrap(data).unwrap().into_inner().unwrap());
+        // Draw the noise before integrating, such that every stage of the integrator sees the same noise.
+        self.noise.clear();
+        if stochasticity.noise > F::zero() {
+            // The deltas are multiplied by dt, so this adds sqrt(dt) * noise to the channels, as in Euler–Maruyama.
+            let scale = stochasticity.noise / self.dt.sqrt();
+            for _ in 0..self.channels.len() {
+                let rng = &mut self.rng;
+                self.noise.push(ndarray::ArrayD::from_shape_simple_fn(
+                    self.shape.clone(),
+                    || standard_normal::<F>(rng) * scale,
+                ));
+            }
         }
-
-        // Concurrent summing of results
-        // Make and aquire locks
-        let convoluted_results_rwlock = Arc::new(RwLock::new(convolution_channels));
-
-        for i in 0..channel_rwlocks.len() {
-            let dt = self.dt.clone();
-            let channel_lock = Arc::clone(&channel_rwlocks[i]);
-            let delta_lock = Arc::clone(&delta_rwlocks[i]);
-            let convoluted_results_lock = Arc::clone(&convoluted_results_rwlock);
-
-            // Thread code
-            summing_handles.push(thread::spawn(move || {
-                let mut channel = channel_lock.write().unwrap();
-                let mut deltas = delta_lock.write().unwrap();
-                let convoluted_results = convoluted_results_lock.read().unwrap();
-
-                let previous_deltas = deltas.clone();
-                // Apply weighted sums and dt to get the delta to be added to channel
-                for i in 0..channel.weights.len() {
-                    deltas.zip_mut_with(&convoluted_results[i].field, |a, b| {
-                        if i == 0 {
-                            *a = 0.0;
-                        }
-                        *a += *b * channel.weights[i];
-                        // I should have normalized weights while they are being set... oh well...
-                        if i == channel.weights.len() {
-                            *a *= channel.weight_sum_reciprocal;
-                        }
-                    });
-                }
-                // Add update channel and clamp
-                let dt_reciprocal = 1.0 / dt;
+        let previous: Option<Vec<ndarray::ArrayD<F>>> =
+            if stochasticity.update_probability < F::one() {
+                Some(
+                    self.channels
+                        .iter()
+                        .map(|channel| channel.field.clone())
+                        .collect(),
+                )
+            } else {
+                None
+            };
+
+        integrate(self, self.integrator, self.dt);
+
+        // Asynchronous update, cells that were not picked go back to their previous values.
+        if let Some(previous) = previous {
+            let update_probability = stochasticity.update_probability.to_f64().unwrap();
+            let rng = &mut self.rng;
+            for (channel, previous) in self.channels.iter_mut().zip(&previous) {
                 ndarray::Zip::from(&mut channel.field)
-                    .and(&mut deltas.view_mut())
-                    .and(&previous_deltas)
-                    .par_for_each(|a, b, c| {
-                        let previous = *a;
-                        *a = (previous + (*b * dt)).clamp(0.0, 1.0);
+                    .and(previous)
+                    .for_each(|a, b| {
+                        if rng.gen::<f64>() >= update_probability {
+                            *a = *b;
+                        }
                     });
-            }));
-        }
-
-        for _ in 0..convolution_mutexes.len() {
-            self.forward_fft_instances.push(
-                Arc::try_unwrap(forward_fft_mutexes.remove(0))
-                    .unwrap()
-                    .into_inner()
-                    .unwrap(),
-            );
-            self.inverse_fft_instances.push(
-                Arc::try_unwrap(inverse_fft_mutexes.remove(0))
-                    .unwrap()
-                    .into_inner()
-                    .unwrap(),
-            );
-            self.convolutions.push(
-                Arc::try_unwrap(convolution_mutexes.remove(0))
-                    .unwrap()
-                    .into_inner()
-                    .unwrap(),
-            );
-        }
-
-        for handle in summing_handles {
-            handle.join().unwrap();
-        }
-
-        // Return ownership of all data back to Lenia instance
-        self.conv_channels = Arc::try_unwrap(convoluted_results_rwlock)
-            .unwrap()
-            .into_inner()
-            .unwrap();
-
-        for _ in 0..channel_rwlocks.len() {
-            self.channels.push(
-                Arc::try_unwrap(channel_rwlocks.remove(0))
-                    .unwrap()
-                    .into_inner()
-                    .unwrap(),
-            );
-            self.deltas.push(
-                Arc::try_unwrap(delta_rwlocks.remove(0))
-                    .unwrap()
-                    .into_inner()
-                    .unwrap(),
-            );
+            }
         }
     }
 
-    fn set_channels(&mut self, num_channels: usize) {
+    fn set_channels(&mut self, num_channels: usize) -> Result<(), LeniaError> {
+        if num_channels == 0 {
+            return Err(LeniaError::InvalidParameter(
+                "There must be at least 1 channel.".to_string(),
+            ));
+        }
         if num_channels <= self.channels.len() {
+            if let Some(conv_channel) = self
+                .conv_channels
+                .iter()
+                .find(|conv_channel| conv_channel.input_channel >= num_channels)
+            {
+                return Err(LeniaError::ChannelOutOfRange {
+                    channel: conv_channel.input_channel,
+                    channels: num_channels,
+                });
+            }
             for i in (num_channels..self.channels.len()).rev() {
                 self.channels.remove(i);
                 self.deltas.remove(i);
+                self.spectra.remove(i);
+                self.forward_fft_instances.remove(i);
             }
         } else {
-            let weights_prototype: Vec<f64> = vec![0.0; self.conv_channels.len()];
+            let weights_prototype: Vec<F> = vec![F::zero(); self.conv_channels.len()];
+            let fft_shape = self.fft_shape();
             for _ in self.channels.len()..num_channels {
                 self.channels.push(Channel {
-                    field: ndarray::ArrayD::from_elem(self.shape.clone(), 0.0),
+                    field: ndarray::ArrayD::from_elem(self.shape.clone(), F::zero()),
                     weights: weights_prototype.clone(),
-                    weight_sum_reciprocal: 0.0,
+                    weight_sum_reciprocal: F::zero(),
+                    update_mode: UpdateMode::Additive,
+                    value_range: ValueRange::default(),
                 });
                 self.deltas
-                    .push(ndarray::ArrayD::from_elem(self.shape.clone(), 0.0));
+                    .push(ndarray::ArrayD::from_elem(self.shape.clone(), F::zero()));
+                self.spectra.push(ndarray::ArrayD::from_elem(
+                    fft::spectrum_shape(&fft_shape),
+                    Complex::new(F::zero(), F::zero()),
+                ));
+                self.forward_fft_instances
+                    .push(fft::ParPlannedFFTND::new_real(&fft_shape, false));
             }
         }
+        Ok(())
     }
 
-    fn set_conv_channels(&mut self, num_conv_channels: usize) {
+    fn set_conv_channels(&mut self, num_conv_channels: usize) -> Result<(), LeniaError> {
+        if num_conv_channels == 0 {
+            return Err(LeniaError::InvalidParameter(
+                "There must be at least 1 convolution channel.".to_string(),
+            ));
+        }
         if num_conv_channels <= self.conv_channels.len() {
             for i in (num_conv_channels..self.conv_channels.len()).rev() {
                 self.conv_channels.remove(i);
-                self.forward_fft_instances.remove(i);
                 self.inverse_fft_instances.remove(i);
                 self.convolutions.remove(i);
+                self.convolution_spectra.remove(i);
             }
             for channel in &mut self.channels {
                 for i in (num_conv_channels..channel.weights.len()).rev() {
                     channel.weights.remove(i);
                 }
-                let sum: f64 = channel.weights.iter().sum();
-                channel.weight_sum_reciprocal = 1.0 / sum;
+                let sum: F = channel.weights.iter().copied().sum();
+                channel.weight_sum_reciprocal = F::one() / sum;
             }
+            self.update_padding();
         } else {
+            let fft_shape = self.fft_shape();
             for i in self.conv_channels.len()..num_conv_channels {
                 self.conv_channels.push(ConvolutionChannel {
                     input_channel: 0,
                     field: self.conv_channels[0].field.clone(),
-                    kernel: Kernel::from(kernels::pass(self.shape.len()), &self.shape),
-                    growth: growth_functions::pass,
-                    growth_params: vec![0.0],
+                    kernel: Kernel::from_padded(
+                        kernels::pass(self.shape.len()),
+                        &self.shape,
+                        &fft_shape,
+                    ),
+                    growth: Arc::new(growth_functions::Pass::new(F::zero())),
+                    parameter_maps: Vec::new(),
+                    backend: ConvolutionBackend::Automatic,
                 });
-                self.forward_fft_instances
-                    .push(fft::ParPlannedFFTND::new(&self.shape, false));
                 self.inverse_fft_instances
-                    .push(fft::ParPlannedFFTND::new(&self.shape, true));
-                self.convolutions.push(ndarray::ArrayD::from_elem(
-                    self.shape.clone(),
-                    Complex::new(0.0, 0.0),
+                    .push(fft::ParPlannedFFTND::new_real(&fft_shape, true));
+                self.convolutions
+                    .push(ndarray::ArrayD::from_elem(self.shape.clone(), F::zero()));
+                self.convolution_spectra.push(ndarray::ArrayD::from_elem(
+                    fft::spectrum_shape(&fft_shape),
+                    Complex::new(F::zero(), F::zero()),
                 ));
             }
             for channel in &mut self.channels {
                 for _ in channel.weights.len()..num_conv_channels {
-                    channel.weights.push(0.0);
+                    channel.weights.push(F::zero());
                 }
             }
         }
+        Ok(())
     }
 
-    fn set_weights(&mut self, new_weights: &[f64], channel: usize) {
-        let mut weights: Vec<f64>;
+    fn set_weights(&mut self, new_weights: &[F], channel: usize) -> Result<(), LeniaError> {
+        check_channel_index(channel, self.channels.len())?;
+        let mut weights: Vec<F>;
         if new_weights.len() < self.conv_channels.len() {
             weights = new_weights.to_vec();
-            for _ in new_weights.len()..self.conv_channels.len() {
-                weights.push(0.0);
-            }
+            weights.resize(self.conv_channels.len(), F::zero());
         } else {
-            weights = Vec::with_capacity(new_weights.len());
-            for i in 0..self.conv_channels.len() {
-                weights.push(new_weights[i]);
-            }
+            weights = new_weights[..self.conv_channels.len()].to_vec();
         }
-        let mut sum: f64 = 0.0;
+        let mut sum = F::zero();
         for weight in &weights {
             sum += weight.abs();
         }
         self.channels[channel].weights = weights;
-        self.channels[channel].weight_sum_reciprocal = 1.0 / sum;
+        self.channels[channel].weight_sum_reciprocal = F::one() / sum;
+        Ok(())
     }
 
-    fn set_source_channel(&mut self, conv_channel: usize, src_channel: usize) {
+    fn set_source_channel(
+        &mut self,
+        conv_channel: usize,
+        src_channel: usize,
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, self.conv_channels.len())?;
+        check_channel_index(src_channel, self.channels.len())?;
         self.conv_channels[conv_channel].input_channel = src_channel;
+        Ok(())
     }
 
-    fn set_kernel(&mut self, kernel: ndarray::ArrayD<f64>, conv_channel: usize) {
-        self.conv_channels[conv_channel].kernel = Kernel::from(kernel, &self.shape);
+    fn set_kernel(
+        &mut self,
+        kernel: ndarray::ArrayD<F>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, self.conv_channels.len())?;
+        self.conv_channels[conv_channel].kernel =
+            Kernel::try_from_padded(kernel, &self.shape, &self.fft_shape())?;
+        self.update_padding();
+        Ok(())
     }
 
     fn set_growth(
         &mut self,
-        f: fn(f64, &[f64]) -> f64,
-        growth_params: Vec<f64>,
+        growth: Arc<dyn GrowthFunction<F>>,
         conv_channel: usize,
-    ) {
-        self.conv_channels[conv_channel].growth = f;
-        self.conv_channels[conv_channel].growth_params = growth_params;
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, self.conv_channels.len())?;
+        check_growth_function(&*growth)?;
+        self.conv_channels[conv_channel].growth = growth;
+        self.conv_channels[conv_channel].parameter_maps.clear();
+        Ok(())
+    }
+
+    fn set_parameter_map(
+        &mut self,
+        map: Option<ndarray::ArrayD<F>>,
+        parameter: usize,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, self.conv_channels.len())?;
+        set_parameter_map(
+            &mut self.conv_channels[conv_channel],
+            map,
+            parameter,
+            &self.shape,
+        )
+    }
+
+    fn set_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        check_convolution_channel_index(conv_channel, self.conv_channels.len())?;
+        self.conv_channels[conv_channel].backend = backend;
+        Ok(())
+    }
+
+    fn set_boundaries(&mut self, boundaries: &[BoundaryCondition]) -> Result<(), LeniaError> {
+        check_boundaries(boundaries, &self.shape)?;
+        self.boundaries = boundaries.to_vec();
+        self.update_padding();
+        Ok(())
+    }
+
+    fn set_integrator(&mut self, integrator: Integrator) -> Result<(), LeniaError> {
+        self.integrator = integrator;
+        Ok(())
+    }
+
+    fn set_update_mode(&mut self, mode: UpdateMode, channel: usize) -> Result<(), LeniaError> {
+        check_channel_index(channel, self.channels.len())?;
+        self.channels[channel].update_mode = mode;
+        Ok(())
+    }
+
+    fn set_value_range(&mut self, range: ValueRange<F>, channel: usize) -> Result<(), LeniaError> {
+        check_channel_index(channel, self.channels.len())?;
+        check_value_range(&range)?;
+        self.channels[channel].value_range = range;
+        Ok(())
     }
 
-    fn set_dt(&mut self, new_dt: f64) {
+    fn set_stochasticity(
+        &mut self,
+        stochasticity: Option<Stochasticity<F>>,
+    ) -> Result<(), LeniaError> {
+        if let Some(stochasticity) = stochasticity {
+            self.rng = ChaCha12Rng::seed_from_u64(stochasticity.seed);
+        }
+        self.stochasticity = stochasticity;
+        self.noise.clear();
+        Ok(())
+    }
+
+    fn set_dt(&mut self, new_dt: F) {
         self.dt = new_dt;
     }
 
@@ -632,31 +993,42 @@ impl Lenia for ExpandedLenia {
         &self.shape
     }
 
-    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64> {
+    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<F> {
         &self.channels[channel].field
     }
 
-    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel {
+    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel<F> {
         &self.conv_channels[conv_channel].kernel
     }
 
-    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<f64> {
+    fn growth_function(&self, conv_channel: usize) -> &dyn GrowthFunction<F> {
+        &*self.conv_channels[conv_channel].growth
+    }
+
+    fn parameter_map(&self, parameter: usize, conv_channel: usize) -> Option<&ndarray::ArrayD<F>> {
+        self.conv_channels[conv_channel]
+            .parameter_maps
+            .get(parameter)
+            .and_then(|map| map.as_ref())
+    }
+
+    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<F> {
         &mut self.channels[channel].field
     }
 
-    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<Complex<f64>> {
+    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<F> {
         &self.convolutions[conv_channel]
     }
 
-    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<f64> {
+    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<F> {
         &self.conv_channels[conv_channel].field
     }
 
-    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64> {
+    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<F> {
         &self.deltas[channel]
     }
 
-    fn dt(&self) -> f64 {
+    fn dt(&self) -> F {
         self.dt
     }
 
@@ -668,7 +1040,1326 @@ impl Lenia for ExpandedLenia {
         self.conv_channels.len()
     }
 
-    fn weights(&self, channel: usize) -> &[f64] {
+    fn backend(&self, conv_channel: usize) -> ConvolutionBackend {
+        self.conv_channels[conv_channel].backend
+    }
+
+    fn source_channel(&self, conv_channel: usize) -> usize {
+        self.conv_channels[conv_channel].input_channel
+    }
+
+    fn boundaries(&self) -> &[BoundaryCondition] {
+        &self.boundaries
+    }
+
+    fn integrator(&self) -> Integrator {
+        self.integrator
+    }
+
+    fn update_mode(&self, channel: usize) -> UpdateMode {
+        self.channels[channel].update_mode
+    }
+
+    fn value_range(&self, channel: usize) -> ValueRange<F> {
+        self.channels[channel].value_range
+    }
+
+    fn stochasticity(&self) -> Option<Stochasticity<F>> {
+        self.stochasticity
+    }
+
+    fn weights(&self, channel: usize) -> &[F] {
         &self.channels[channel].weights
     }
+
+    fn write_state(&self, writer: &mut checkpoint::StateWriter) -> Result<(), LeniaError> {
+        writer.write_str("ExpandedLenia")?;
+        self.write_fields(writer)
+    }
+
+    fn read_state(
+        &mut self,
+        reader: &mut checkpoint::StateReader,
+        registry: &rules::Registry<F>,
+    ) -> Result<(), LeniaError> {
+        reader.expect_tag("ExpandedLenia")?;
+        self.read_fields(reader, registry)
+    }
+}
+
+impl<F: LeniaFloat> Integrable<F> for ExpandedLenia<F> {
+    /// Convolves the channels, applies the growth functions and sums the results into the deltas
+    /// of each channel according to the channels' weights.
+    ///
+    /// Convolution channels, and later channels, are processed in parallel on the rayon thread-pool,
+    /// borrowing the data directly from the Lenia instance.
+    fn update_deltas(&mut self) {
+        // Forward fft each channel used as a source only once, the resulting spectrum is shared
+        // between all of the convolution channels convolving it. Channels only convolved directly
+        // need no spectrum.
+        let channel_size = self.shape.iter().product();
+        let is_direct: Vec<bool> = self
+            .conv_channels
+            .iter()
+            .map(|conv_channel| {
+                conv_channel
+                    .backend
+                    .is_direct(&conv_channel.kernel, channel_size)
+            })
+            .collect();
+        let padding = &self.padding;
+        let boundaries = &self.boundaries;
+        let mut is_source = vec![false; self.channels.len()];
+        for (conv_channel, is_direct) in self.conv_channels.iter().zip(&is_direct) {
+            if !*is_direct {
+                is_source[conv_channel.input_channel] = true;
+            }
+        }
+        self.channels
+            .par_iter()
+            .zip(self.spectra.par_iter_mut())
+            .zip(self.forward_fft_instances.par_iter_mut())
+            .zip(is_source.par_iter())
+            .for_each(|(((channel, spectrum), forward_fft), is_source)| {
+                if *is_source {
+                    transform_padded(forward_fft, &channel.field, padding, boundaries, spectrum);
+                }
+            });
+
+        // Concurrent convolutions
+        let channels = &self.channels;
+        let spectra = &self.spectra;
+        self.conv_channels
+            .par_iter_mut()
+            .zip(self.convolution_spectra.par_iter_mut())
+            .zip(self.convolutions.par_iter_mut())
+            .zip(self.inverse_fft_instances.par_iter_mut())
+            .zip(is_direct.par_iter())
+            .for_each(
+                |((((convolution_channel, spectrum), convolution), inverse_fft), is_direct)| {
+                    if *is_direct {
+                        direct::convolve(
+                            &channels[convolution_channel.input_channel].field,
+                            &convolution_channel.kernel.normalized,
+                            convolution,
+                            boundaries,
+                        );
+                    } else {
+                        // Multiply the shared spectrum of the source channel with the kernel
+                        ndarray::Zip::from(&mut *spectrum)
+                            .and(&spectra[convolution_channel.input_channel])
+                            .and(&convolution_channel.kernel.transformed)
+                            .for_each(|a, b, c| {
+                                a.re = (b.re * c.re) - (b.im * c.im);
+                                a.im = (b.re * c.im) + (b.im * c.re);
+                            });
+                        // Inverse fft to get convolution result
+                        transform_padded_inverse(inverse_fft, spectrum, padding, convolution);
+                    }
+                    // Apply growth function
+                    apply_growth(convolution_channel, convolution);
+                },
+            );
+
+        // Concurrent summing of results
+        let conv_channels = &self.conv_channels;
+        self.channels
+            .par_iter()
+            .zip(self.deltas.par_iter_mut())
+            .for_each(|(channel, deltas)| {
+                // Apply weighted sums to get the delta to be added to channel
+                deltas.fill(F::zero());
+                for (weight, convolution_channel) in channel.weights.iter().zip(conv_channels) {
+                    ndarray::Zip::from(&mut *deltas)
+                        .and(&convolution_channel.field)
+                        .par_for_each(|a, b| {
+                            *a += *b * *weight;
+                        });
+                }
+            });
+
+        for (deltas, noise) in self.deltas.iter_mut().zip(&self.noise) {
+            *deltas += noise;
+        }
+    }
+
+    fn fields_and_deltas(&mut self) -> Vec<ChannelUpdate<'_, F>> {
+        self.channels
+            .iter_mut()
+            .zip(self.deltas.iter())
+            .map(|(channel, deltas)| {
+                (
+                    &mut channel.field,
+                    deltas,
+                    channel.update_mode,
+                    channel.value_range,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Mass-conserving type of Lenia
+///
+/// `FlowLenia` struct implements Flow Lenia, where the weighted sums of the convolution channels
+/// do not get added onto the channels, but are instead used as an affinity map. The contents of each channel flow
+/// along the gradient of its affinity map, and away from crowded areas (where the sum of all channels
+/// exceeds the critical mass), after which they get redistributed using reintegration tracking.
+/// Thus the total mass of every channel is conserved, and the channels are not clamped to `[0.0..1.0]`.
+///
+/// Channels, convolution channels, kernels, growth functions and weights are set up exactly as in `ExpandedLenia`.
+/// Axes with a non-periodic `BoundaryCondition` act as walls, keeping all of the mass within the channels.
+///
+/// The parameters specific to Flow Lenia can be changed on the `FlowLenia` directly, or through
+/// `Simulator::lenia_mut()`, otherwise the defaults are used:
+/// * critical mass `2.0`, with an exponent of `2.0`.
+/// * spread of the redistributed mass `0.65`.
+/// * maximum displacement of the mass per iteration `5` cells.
+///
+/// ### Example of initializing a `FlowLenia`.
+///
+/// ```
+/// # use road_to_lenia::{growth_functions, kernels, lenias::FlowLenia, Simulator};
+/// # fn display(_: &ndarray::ArrayD<f64>) {}
+/// let channel_shape: Vec<usize> = vec![128, 128];
+/// let starting_pattern = ndarray::ArrayD::<f64>::zeros(channel_shape.clone()); // fill with your data
+/// let mut simulator = Simulator::<FlowLenia>::new(&channel_shape);
+/// simulator.set_kernel(kernels::gaussian_donut_2d(13, 0.15), 0);
+/// simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+/// simulator.set_dt(0.2);
+/// simulator.lenia_mut().set_spread(0.5);
+/// simulator.fill_channel(&starting_pattern, 0);
+/// for _ in 0..10 {
+///     simulator.iterate();
+///     display(simulator.get_channel_as_ref(0));
+/// }
+/// ```
+pub struct FlowLenia<F: LeniaFloat = f64> {
+    // Convolutions, growth and the weighted sums (the affinity maps) are computed by `ExpandedLenia`.
+    expanded: ExpandedLenia<F>,
+    critical_mass: F,
+    critical_mass_exponent: F,
+    spread: F,
+    max_displacement: usize,
+}
+
+impl<F: LeniaFloat> FlowLenia<F> {
+    /// Sets the critical mass, the total mass of all channels above which the mass flows
+    /// only away from crowded areas, regardless of the affinity maps.
+    ///
+    /// ### Panics
+    ///
+    /// If `critical_mass` is not positive.
+    pub fn set_critical_mass(&mut self, critical_mass: F) {
+        if let Err(error) = self.try_set_critical_mass(critical_mass) {
+            panic!("FlowLenia::set_critical_mass() - {}", error);
+        }
+    }
+
+    /// Same as `set_critical_mass()`, but returns an error instead of panicking.
+    pub fn try_set_critical_mass(&mut self, critical_mass: F) -> Result<(), LeniaError> {
+        if critical_mass <= F::zero() {
+            return Err(LeniaError::InvalidParameter(
+                "The critical mass must be positive!".to_string(),
+            ));
+        }
+        self.critical_mass = critical_mass;
+        Ok(())
+    }
+
+    /// Sets the exponent controlling how sharply the flow switches from following the affinity maps
+    /// to avoiding crowded areas as the total mass approaches the critical mass.
+    pub fn set_critical_mass_exponent(&mut self, exponent: F) {
+        self.critical_mass_exponent = exponent;
+    }
+
+    /// Sets the spread (half of the side length) of the area each cell's mass is redistributed onto.
+    ///
+    /// ### Panics
+    ///
+    /// * If `spread` is not positive.
+    ///
+    /// * If `spread` is larger than the maximum displacement.
+    pub fn set_spread(&mut self, spread: F) {
+        if let Err(error) = self.try_set_spread(spread) {
+            panic!("FlowLenia::set_spread() - {}", error);
+        }
+    }
+
+    /// Same as `set_spread()`, but returns an error instead of panicking.
+    pub fn try_set_spread(&mut self, spread: F) -> Result<(), LeniaError> {
+        if spread <= F::zero() || spread > float(self.max_displacement as f64) {
+            return Err(LeniaError::InvalidParameter(
+                "The spread must be positive and no larger than the maximum displacement!"
+                    .to_string(),
+            ));
+        }
+        self.spread = spread;
+        Ok(())
+    }
+
+    /// Sets the maximum distance (in cells, along each axis) the mass of a cell can travel in a single iteration.
+    ///
+    /// ### Panics
+    ///
+    /// If `max_displacement` is smaller than the spread.
+    pub fn set_max_displacement(&mut self, max_displacement: usize) {
+        if let Err(error) = self.try_set_max_displacement(max_displacement) {
+            panic!("FlowLenia::set_max_displacement() - {}", error);
+        }
+    }
+
+    /// Same as `set_max_displacement()`, but returns an error instead of panicking.
+    pub fn try_set_max_displacement(&mut self, max_displacement: usize) -> Result<(), LeniaError> {
+        if float::<F>(max_displacement as f64) < self.spread {
+            return Err(LeniaError::InvalidParameter(
+                "The maximum displacement must be at least as large as the spread!".to_string(),
+            ));
+        }
+        self.max_displacement = max_displacement;
+        Ok(())
+    }
+
+    /// Returns the critical mass.
+    pub fn critical_mass(&self) -> F {
+        self.critical_mass
+    }
+
+    /// Returns the exponent of the critical mass.
+    pub fn critical_mass_exponent(&self) -> F {
+        self.critical_mass_exponent
+    }
+
+    /// Returns the spread of the redistributed mass.
+    pub fn spread(&self) -> F {
+        self.spread
+    }
+
+    /// Returns the maximum displacement of mass per iteration.
+    pub fn max_displacement(&self) -> usize {
+        self.max_displacement
+    }
+}
+
+impl<F: LeniaFloat> NewLenia for FlowLenia<F> {
+    /// Create and initialize a new instance of "FlowLenia".
+    ///
+    /// The default kernel is a unit size and the default growth function for the kernel is a "pass" function.
+    ///
+    /// ### Parameters
+    ///
+    /// * `shape` - The shape of the channels of the Lenia instance.
+    ///
+    /// ### Panics
+    ///
+    /// If any dimension/axis in `shape` is 0.
+    fn new(shape: &[usize]) -> Self {
+        match Self::try_new(shape) {
+            Ok(lenia) => lenia,
+            Err(error) => panic!("FlowLenia::new() - {}", error),
+        }
+    }
+
+    fn try_new(shape: &[usize]) -> Result<Self, LeniaError> {
+        let mut expanded = ExpandedLenia::try_new(shape)?;
+        expanded.set_dt(float(0.2));
+        Ok(FlowLenia {
+            expanded,
+            critical_mass: float(2.0),
+            critical_mass_exponent: float(2.0),
+            spread: float(0.65),
+            max_displacement: 5,
+        })
+    }
+}
+
+impl<F: LeniaFloat> Lenia for FlowLenia<F> {
+    type Float = F;
+
+    fn iterate(&mut self) {
+        self.expanded.update_deltas();
+
+        let shape = &self.expanded.shape;
+        let boundaries = &self.expanded.boundaries;
+        let dt = self.expanded.dt;
+        let critical_mass = self.critical_mass;
+        let exponent = self.critical_mass_exponent;
+        let spread = self.spread;
+        let max_displacement = self.max_displacement;
+        let max_flow = float::<F>(max_displacement as f64) - spread;
+        let half: F = float(0.5);
+
+        // The total mass, and how much it makes the mass flow away from crowded areas.
+        let mut total = ndarray::ArrayD::zeros(shape.clone());
+        for channel in &self.expanded.channels {
+            total += &channel.field;
+        }
+        let total_gradient = gradient(&total, boundaries);
+        let alpha = total.mapv(|mass| {
+            num_traits::clamp((mass / critical_mass).powf(exponent), F::zero(), F::one())
+        });
+
+        self.expanded
+            .channels
+            .par_iter_mut()
+            .zip(self.expanded.deltas.par_iter())
+            .for_each(|(channel, affinity)| {
+                let affinity_gradient = gradient(affinity, boundaries);
+                let displacements: Vec<ndarray::ArrayD<F>> = (0..shape.len())
+                    .map(|axis| {
+                        let mut displacement = ndarray::ArrayD::zeros(shape.clone());
+                        let n: F = float(shape[axis] as f64);
+                        ndarray::Zip::from(&mut displacement)
+                            .and(&affinity_gradient[axis])
+                            .and(&total_gradient[axis])
+                            .and(&alpha)
+                            .par_for_each(|displacement, affinity, total, alpha| {
+                                let flow = (F::one() - *alpha) * *affinity - *alpha * *total;
+                                *displacement = num_traits::clamp(flow * dt, -max_flow, max_flow);
+                            });
+                        if boundaries[axis] != BoundaryCondition::Periodic {
+                            // Keep the redistributed mass within the walls
+                            let low = spread - half;
+                            let high = n - half - spread;
+                            ndarray::Zip::from(displacement.lanes_mut(Axis(axis))).par_for_each(
+                                |mut lane| {
+                                    for (i, displacement) in lane.iter_mut().enumerate() {
+                                        let position: F = float(i as f64);
+                                        let target = if low > high {
+                                            (n - F::one()) * half
+                                        } else {
+                                            num_traits::clamp(position + *displacement, low, high)
+                                        };
+                                        *displacement = target - position;
+                                    }
+                                },
+                            );
+                        }
+                        displacement
+                    })
+                    .collect();
+                channel.field = reintegrate(
+                    &channel.field,
+                    &displacements,
+                    boundaries,
+                    spread,
+                    max_displacement,
+                );
+            });
+    }
+
+    fn set_channels(&mut self, num_channels: usize) -> Result<(), LeniaError> {
+        self.expanded.set_channels(num_channels)
+    }
+
+    fn set_conv_channels(&mut self, num_conv_channels: usize) -> Result<(), LeniaError> {
+        self.expanded.set_conv_channels(num_conv_channels)
+    }
+
+    fn set_weights(&mut self, new_weights: &[F], channel: usize) -> Result<(), LeniaError> {
+        self.expanded.set_weights(new_weights, channel)
+    }
+
+    fn set_source_channel(
+        &mut self,
+        conv_channel: usize,
+        src_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.expanded.set_source_channel(conv_channel, src_channel)
+    }
+
+    fn set_kernel(
+        &mut self,
+        kernel: ndarray::ArrayD<F>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.expanded.set_kernel(kernel, conv_channel)
+    }
+
+    fn set_growth(
+        &mut self,
+        growth: Arc<dyn GrowthFunction<F>>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.expanded.set_growth(growth, conv_channel)
+    }
+
+    fn set_parameter_map(
+        &mut self,
+        map: Option<ndarray::ArrayD<F>>,
+        parameter: usize,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.expanded
+            .set_parameter_map(map, parameter, conv_channel)
+    }
+
+    fn set_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.expanded.set_backend(backend, conv_channel)
+    }
+
+    fn set_boundaries(&mut self, boundaries: &[BoundaryCondition]) -> Result<(), LeniaError> {
+        self.expanded.set_boundaries(boundaries)
+    }
+
+    fn set_integrator(&mut self, integrator: Integrator) -> Result<(), LeniaError> {
+        Err(LeniaError::Unsupported("Changing the integrator is not available for Flow Lenia, as it moves the mass by a single reintegration step!".to_string()))
+    }
+
+    fn set_update_mode(&mut self, mode: UpdateMode, channel: usize) -> Result<(), LeniaError> {
+        Err(LeniaError::Unsupported("Changing the update mode is not available for Flow Lenia, as the channels are only ever moved around!".to_string()))
+    }
+
+    fn set_value_range(&mut self, range: ValueRange<F>, channel: usize) -> Result<(), LeniaError> {
+        Err(LeniaError::Unsupported("Changing the value range is not available for Flow Lenia, as the channels are only ever moved around!".to_string()))
+    }
+
+    fn set_stochasticity(
+        &mut self,
+        stochasticity: Option<Stochasticity<F>>,
+    ) -> Result<(), LeniaError> {
+        match stochasticity {
+            Some(_) => Err(LeniaError::Unsupported("Stochastic update steps are not available for Flow Lenia, as the channels are only ever moved around!".to_string())),
+            None => Ok(()),
+        }
+    }
+
+    fn set_dt(&mut self, new_dt: F) {
+        self.expanded.set_dt(new_dt);
+    }
+
+    fn shape(&self) -> &[usize] {
+        self.expanded.shape()
+    }
+
+    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<F> {
+        self.expanded.get_channel_as_ref(channel)
+    }
+
+    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel<F> {
+        self.expanded.get_kernel_as_ref(conv_channel)
+    }
+
+    fn growth_function(&self, conv_channel: usize) -> &dyn GrowthFunction<F> {
+        self.expanded.growth_function(conv_channel)
+    }
+
+    fn parameter_map(&self, parameter: usize, conv_channel: usize) -> Option<&ndarray::ArrayD<F>> {
+        self.expanded.parameter_map(parameter, conv_channel)
+    }
+
+    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<F> {
+        self.expanded.get_channel_as_mut_ref(channel)
+    }
+
+    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<F> {
+        self.expanded.get_convoluted_as_ref(conv_channel)
+    }
+
+    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<F> {
+        self.expanded.get_grown_as_ref(conv_channel)
+    }
+
+    /// Returns the affinity map of the channel.
+    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<F> {
+        self.expanded.get_deltas_as_ref(channel)
+    }
+
+    fn dt(&self) -> F {
+        self.expanded.dt()
+    }
+
+    fn channels(&self) -> usize {
+        self.expanded.channels()
+    }
+
+    fn conv_channels(&self) -> usize {
+        self.expanded.conv_channels()
+    }
+
+    fn backend(&self, conv_channel: usize) -> ConvolutionBackend {
+        self.expanded.backend(conv_channel)
+    }
+
+    fn source_channel(&self, conv_channel: usize) -> usize {
+        self.expanded.source_channel(conv_channel)
+    }
+
+    fn boundaries(&self) -> &[BoundaryCondition] {
+        self.expanded.boundaries()
+    }
+
+    /// Always `Integrator::Euler`, the mass is moved by a single reintegration step.
+    fn integrator(&self) -> Integrator {
+        Integrator::Euler
+    }
+
+    /// Always `UpdateMode::Additive`, the channels are not updated by their deltas at all.
+    fn update_mode(&self, channel: usize) -> UpdateMode {
+        UpdateMode::Additive
+    }
+
+    /// Always unbounded, as the channels are never clamped.
+    fn value_range(&self, channel: usize) -> ValueRange<F> {
+        ValueRange::unbounded()
+    }
+
+    fn stochasticity(&self) -> Option<Stochasticity<F>> {
+        None
+    }
+
+    fn weights(&self, channel: usize) -> &[F] {
+        self.expanded.weights(channel)
+    }
+
+    fn write_state(&self, writer: &mut checkpoint::StateWriter) -> Result<(), LeniaError> {
+        writer.write_str("FlowLenia")?;
+        self.expanded.write_fields(writer)?;
+        writer.write_float(self.critical_mass)?;
+        writer.write_float(self.critical_mass_exponent)?;
+        writer.write_float(self.spread)?;
+        writer.write_usize(self.max_displacement)
+    }
+
+    fn read_state(
+        &mut self,
+        reader: &mut checkpoint::StateReader,
+        registry: &rules::Registry<F>,
+    ) -> Result<(), LeniaError> {
+        reader.expect_tag("FlowLenia")?;
+        self.expanded.read_fields(reader, registry)?;
+        self.critical_mass = reader.read_float()?;
+        self.critical_mass_exponent = reader.read_float()?;
+        self.spread = reader.read_float()?;
+        self.max_displacement = reader.read_usize()?;
+        Ok(())
+    }
+}
+
+/// A channel's data paired with its deltas, the way the deltas update it, and the range it is kept within.
+pub(crate) type ChannelUpdate<'a, F> = (
+    &'a mut ndarray::ArrayD<F>,
+    &'a ndarray::ArrayD<F>,
+    UpdateMode,
+    ValueRange<F>,
+);
+
+/// A Lenia whose channels are advanced by adding their deltas, scaled by `dt`, onto them.
+pub(crate) trait Integrable<F: LeniaFloat> {
+    /// Computes the deltas of the current state of the channels.
+    fn update_deltas(&mut self);
+    /// Returns each channel's data paired with its deltas, the way the deltas update it and its range.
+    fn fields_and_deltas(&mut self) -> Vec<ChannelUpdate<'_, F>>;
+}
+
+/// Advances the channels of `lenia` by one integration step of `dt` with the scheme of `integrator`.
+pub(crate) fn integrate<F: LeniaFloat, L: Integrable<F>>(
+    lenia: &mut L,
+    integrator: Integrator,
+    dt: F,
+) {
+    if integrator == Integrator::Euler {
+        lenia.update_deltas();
+        lenia
+            .fields_and_deltas()
+            .into_par_iter()
+            .for_each(|(field, deltas, mode, range)| {
+                ndarray::Zip::from(field).and(deltas).par_for_each(|a, b| {
+                    *a = mode.advance(*a, mode.rate(*a, *b), dt, &range);
+                });
+            });
+        return;
+    }
+
+    let initial: Vec<ndarray::ArrayD<F>> = lenia
+        .fields_and_deltas()
+        .into_iter()
+        .map(|(field, _, _, _)| field.clone())
+        .collect();
+    let mut sums: Vec<ndarray::ArrayD<F>> = initial
+        .iter()
+        .map(|field| ndarray::ArrayD::zeros(field.raw_dim()))
+        .collect();
+    for (stage, (offset, weight)) in integrator.stages::<F>().into_iter().enumerate() {
+        if stage > 0 {
+            // Evaluate the stage at the original channels, advanced by the rates of the previous stage.
+            let step = dt * offset;
+            lenia
+                .fields_and_deltas()
+                .into_par_iter()
+                .zip(initial.par_iter())
+                .for_each(|((field, deltas, mode, range), initial)| {
+                    ndarray::Zip::from(field)
+                        .and(initial)
+                        .and(deltas)
+                        .par_for_each(|a, b, c| {
+                            *a = mode.advance(*b, mode.rate(*a, *c), step, &range);
+                        });
+                });
+        }
+        lenia.update_deltas();
+        lenia
+            .fields_and_deltas()
+            .into_par_iter()
+            .zip(sums.par_iter_mut())
+            .for_each(|((field, deltas, mode, _), sum)| {
+                ndarray::Zip::from(sum)
+                    .and(&*field)
+                    .and(deltas)
+                    .par_for_each(|a, b, c| {
+                        *a += mode.rate(*b, *c) * weight;
+                    });
+            });
+    }
+    lenia
+        .fields_and_deltas()
+        .into_par_iter()
+        .zip(initial.par_iter())
+        .zip(sums.par_iter())
+        .for_each(|(((field, _, mode, range), initial), sum)| {
+            ndarray::Zip::from(field)
+                .and(initial)
+                .and(sum)
+                .par_for_each(|a, b, c| {
+                    *a = mode.advance(*b, *c, dt, &range);
+                });
+        });
+}
+
+/// Gradient of `field` along each of its axes, using central differences.
+fn gradient<F: LeniaFloat>(
+    field: &ndarray::ArrayD<F>,
+    boundaries: &[BoundaryCondition],
+) -> Vec<ndarray::ArrayD<F>> {
+    let padded = pad(field, &vec![(1, 1); field.shape().len()], boundaries);
+    let half: F = float(0.5);
+    (0..field.shape().len())
+        .map(|axis| {
+            let shifted = |offset: usize| {
+                padded.slice_each_axis(|a| {
+                    let start = if a.axis.index() == axis { offset } else { 1 };
+                    Slice::from(start..start + field.shape()[a.axis.index()])
+                })
+            };
+            let mut out = ndarray::ArrayD::zeros(field.shape());
+            ndarray::Zip::from(&mut out)
+                .and(shifted(2))
+                .and(shifted(0))
+                .par_for_each(|out, next, previous| {
+                    *out = (*next - *previous) * half;
+                });
+            out
+        })
+        .collect()
+}
+
+/// Reintegration tracking: moves the mass of every cell of `field` by its `displacements`, spreading it
+/// uniformly over a square (or hyper-cube) with a half-side of `spread`, and sums up the mass landing in each cell.
+///
+/// Displacements along non-periodic axes must keep the spread out mass within the field for the mass to be conserved.
+///
+/// The cells are processed in parallel in slabs along the first axis, each slab scattering its mass
+/// into its own buffer, which reaches `max_displacement + 1` cells past the slab on either side.
+fn reintegrate<F: LeniaFloat>(
+    field: &ndarray::ArrayD<F>,
+    displacements: &[ndarray::ArrayD<F>],
+    boundaries: &[BoundaryCondition],
+    spread: F,
+    max_displacement: usize,
+) -> ndarray::ArrayD<F> {
+    let shape = field.shape();
+    let dimensions = shape.len();
+    let field = field.as_standard_layout();
+    let field = field.as_slice().unwrap();
+    let displacements: Vec<&[F]> = displacements
+        .iter()
+        .map(|displacement| displacement.as_slice().unwrap())
+        .collect();
+    let mut strides = vec![1; dimensions];
+    for axis in (0..dimensions - 1).rev() {
+        strides[axis] = strides[axis + 1] * shape[axis + 1];
+    }
+
+    let half: F = float(0.5);
+    let max_overlap = (spread + spread).min(F::one());
+    let normalizer = F::one() / (spread + spread).powi(dimensions as i32);
+    let reach = max_displacement + 1;
+    let slab_length =
+        ((shape[0] + rayon::current_num_threads() - 1) / rayon::current_num_threads()).max(1);
+    let slabs: Vec<(usize, Vec<F>)> = (0..shape[0])
+        .step_by(slab_length)
+        .collect::<Vec<usize>>()
+        .into_par_iter()
+        .map(|start| {
+            let end = (start + slab_length).min(shape[0]);
+            let mut slab = vec![F::zero(); (end - start + 2 * reach) * strides[0]];
+            // Cells the mass of a source cell lands on, and their overlap with it, along each axis
+            let mut targets: Vec<Vec<(usize, F)>> = vec![Vec::new(); dimensions];
+            let mut counters = vec![0; dimensions];
+            let sources = start * strides[0]..end * strides[0];
+            'sources: for (source, mass) in sources.clone().zip(&field[sources]) {
+                let mass = *mass;
+                if mass == F::zero() {
+                    continue;
+                }
+                let mut remainder = source;
+                for axis in (0..dimensions).rev() {
+                    let index = remainder % shape[axis];
+                    remainder /= shape[axis];
+                    let center = float::<F>(index as f64) + displacements[axis][source];
+                    let first = (center - spread + half).floor().to_isize().unwrap_or(0);
+                    let last = (center + spread + half).floor().to_isize().unwrap_or(-1);
+                    targets[axis].clear();
+                    for target in first..=last {
+                        let overlap = num_traits::clamp(
+                            half + spread - (float::<F>(target as f64) - center).abs(),
+                            F::zero(),
+                            max_overlap,
+                        );
+                        if overlap <= F::zero() {
+                            continue;
+                        }
+                        let n = shape[axis] as isize;
+                        let target = if axis == 0 {
+                            // Relative to the slab buffer
+                            target - start as isize + reach as isize
+                        } else if (0..n).contains(&target) {
+                            target
+                        } else if boundaries[axis] == BoundaryCondition::Periodic {
+                            target.rem_euclid(n)
+                        } else {
+                            continue;
+                        };
+                        targets[axis].push((target as usize, overlap));
+                    }
+                    if targets[axis].is_empty() {
+                        continue 'sources;
+                    }
+                }
+                // Add the mass onto every combination of the target cells along each axis
+                counters.fill(0);
+                'targets: loop {
+                    let mut target = 0;
+                    let mut share = mass * normalizer;
+                    for axis in 0..dimensions {
+                        let (index, overlap) = targets[axis][counters[axis]];
+                        target += index * strides[axis];
+                        share *= overlap;
+                    }
+                    slab[target] += share;
+                    let mut axis = dimensions;
+                    loop {
+                        if axis == 0 {
+                            break 'targets;
+                        }
+                        axis -= 1;
+                        counters[axis] += 1;
+                        if counters[axis] < targets[axis].len() {
+                            break;
+                        }
+                        counters[axis] = 0;
+                    }
+                }
+            }
+            (start, slab)
+        })
+        .collect();
+
+    // Sum the slabs together
+    let mut out = ndarray::ArrayD::zeros(shape);
+    let out_data = out.as_slice_mut().unwrap();
+    let n = shape[0] as isize;
+    for (start, slab) in slabs {
+        for (i, row) in slab.chunks(strides[0]).enumerate() {
+            let index = (start + i) as isize - reach as isize;
+            let index = if (0..n).contains(&index) {
+                index
+            } else if boundaries[0] == BoundaryCondition::Periodic {
+                index.rem_euclid(n)
+            } else {
+                continue;
+            };
+            let offset = index as usize * strides[0];
+            for (a, b) in out_data[offset..offset + strides[0]].iter_mut().zip(row) {
+                *a += *b;
+            }
+        }
+    }
+    out
+}
+
+/// Applies the growth function of `conv_channel` onto the `convolved` result, writing the outcome into its `field`.
+///
+/// Where parameter maps are set, the growth function is evaluated with the local parameter values.
+fn apply_growth<F: LeniaFloat>(
+    conv_channel: &mut ConvolutionChannel<F>,
+    convolved: &ndarray::ArrayD<F>,
+) {
+    let growth = &*conv_channel.growth;
+    if conv_channel.parameter_maps.iter().all(|map| map.is_none()) {
+        ndarray::Zip::from(&mut conv_channel.field)
+            .and(convolved)
+            .par_for_each(|a, b| {
+                *a = growth.growth(*b);
+            });
+        return;
+    }
+
+    let defaults = growth.parameters();
+    let maps: Vec<(usize, &[F])> = conv_channel
+        .parameter_maps
+        .iter()
+        .enumerate()
+        .filter_map(|(i, map)| {
+            map.as_ref().map(|map| {
+                (
+                    i,
+                    map.as_slice().expect(
+                        "lenias::apply_growth() - Parameter map is not in standard layout!",
+                    ),
+                )
+            })
+        })
+        .collect();
+    let convolved = convolved.as_standard_layout();
+    let convolved = convolved.as_slice().unwrap();
+    let row_length = *conv_channel.field.shape().last().unwrap();
+    conv_channel
+        .field
+        .as_slice_mut()
+        .expect("lenias::apply_growth() - Field is not in standard layout!")
+        .par_chunks_mut(row_length)
+        .enumerate()
+        .for_each(|(row, out)| {
+            let mut parameters = defaults.clone();
+            let start = row * row_length;
+            for (i, a) in out.iter_mut().enumerate() {
+                for (parameter, map) in &maps {
+                    parameters[*parameter] = map[start + i];
+                }
+                *a = growth.growth_with_parameters(convolved[start + i], &parameters);
+            }
+        });
+}
+
+/// Sets or removes the map of the `parameter`-th growth function parameter of `conv_channel`,
+/// whose channels have the shape `shape`.
+fn set_parameter_map<F: LeniaFloat>(
+    conv_channel: &mut ConvolutionChannel<F>,
+    map: Option<ndarray::ArrayD<F>>,
+    parameter: usize,
+    shape: &[usize],
+) -> Result<(), LeniaError> {
+    let parameters = conv_channel.growth.parameters().len();
+    if parameter >= parameters {
+        return Err(LeniaError::InvalidParameter(format!(
+            "The growth function \"{}\" has {} parameters, it has no parameter {}.",
+            conv_channel.growth.name(),
+            parameters,
+            parameter
+        )));
+    }
+    if let Some(map) = &map {
+        if map.shape() != shape {
+            return Err(LeniaError::ShapeMismatch {
+                expected: shape.to_vec(),
+                found: map.shape().to_vec(),
+            });
+        }
+    }
+    conv_channel.parameter_maps.resize(parameters, None);
+    conv_channel.parameter_maps[parameter] = map.map(|map| map.as_standard_layout().into_owned());
+    if conv_channel.parameter_maps.iter().all(|map| map.is_none()) {
+        conv_channel.parameter_maps.clear();
+    }
+    Ok(())
+}
+
+/// Draws a sample of the standard normal distribution using the Box-Muller transform.
+fn standard_normal<F: LeniaFloat>(rng: &mut ChaCha12Rng) -> F {
+    let radius = (-2.0 * (1.0 - rng.gen::<f64>()).ln()).sqrt();
+    let angle = 2.0 * std::f64::consts::PI * rng.gen::<f64>();
+    float(radius * angle.cos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{growth_functions, kernels, BoundaryCondition, Simulator};
+
+    /// A blob of mass in the middle of a `size` by `size` world.
+    fn blob(size: usize) -> ndarray::ArrayD<f64> {
+        let center = size as f64 / 2.0;
+        ndarray::ArrayD::from_shape_fn(vec![size, size], |index| {
+            let dx = index[0] as f64 - center;
+            let dy = index[1] as f64 - center;
+            let value = 1.0 - (dx * dx + dy * dy).sqrt() / 12.0;
+            value.max(0.0) * (0.6 + 0.4 * ((index[0] * 7 + index[1] * 3) % 5) as f64 / 4.0)
+        })
+    }
+
+    fn flow_lenia(size: usize) -> Simulator<FlowLenia> {
+        let mut simulator = Simulator::<FlowLenia>::new(&[size, size]);
+        simulator.set_kernel(kernels::gaussian_donut_2d(8, 0.15), 0);
+        simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+        simulator.set_dt(0.2);
+        simulator.fill_channel(&blob(size), 0);
+        simulator
+    }
+
+    #[test]
+    fn flow_lenia_conserves_mass() {
+        for boundary in [BoundaryCondition::Periodic, BoundaryCondition::Zero] {
+            let mut simulator = flow_lenia(64);
+            simulator.set_boundary_conditions(&[boundary; 2]);
+            let initial_mass = simulator.get_channel_as_ref(0).sum();
+            for _ in 0..50 {
+                simulator.iterate();
+                let mass = simulator.get_channel_as_ref(0).sum();
+                assert!(
+                    (mass - initial_mass).abs() < 1e-9 * initial_mass,
+                    "{:?} boundaries: mass went from {} to {}",
+                    boundary,
+                    initial_mass,
+                    mass
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn flow_lenia_parameters_are_reachable_through_the_simulator() {
+        let mut simulator = flow_lenia(64);
+        simulator.lenia_mut().set_max_displacement(3);
+        simulator.lenia_mut().set_spread(1.5);
+        simulator.lenia_mut().set_critical_mass(4.0);
+        assert!(simulator.lenia_mut().try_set_spread(3.5).is_err());
+        assert_eq!(simulator.lenia().max_displacement(), 3);
+        assert_eq!(simulator.lenia().spread(), 1.5);
+        assert_eq!(simulator.lenia().critical_mass(), 4.0);
+
+        let initial_mass = simulator.get_channel_as_ref(0).sum();
+        for _ in 0..20 {
+            simulator.iterate();
+        }
+        let mass = simulator.get_channel_as_ref(0).sum();
+        assert!((mass - initial_mass).abs() < 1e-9 * initial_mass);
+    }
+
+    #[test]
+    fn shrinking_channels_keeps_the_sources_of_convolution_channels_valid() {
+        for backend in [ConvolutionBackend::Fft, ConvolutionBackend::Direct] {
+            let mut simulator = Simulator::<ExpandedLenia>::new(&[32, 32]);
+            simulator.set_convolution_backend(backend, 0);
+            simulator.set_channels(3);
+            simulator.set_convolution_channel_source(0, 2);
+            assert!(matches!(
+                simulator.try_set_channels(1),
+                Err(LeniaError::ChannelOutOfRange {
+                    channel: 2,
+                    channels: 1
+                })
+            ));
+            assert_eq!(simulator.channels(), 3);
+            simulator.iterate();
+
+            simulator.set_convolution_channel_source(0, 1);
+            simulator.set_channels(2);
+            simulator.fill_channel(&blob(32), 1);
+            simulator.iterate();
+
+            simulator.set_convolution_channel_source(0, 0);
+            simulator.set_channels(1);
+            simulator.fill_channel(&blob(32), 0);
+            simulator.iterate();
+            assert_eq!(simulator.channels(), 1);
+        }
+    }
+
+    #[test]
+    fn kernels_that_cannot_be_normalized_are_rejected() {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[16, 16]);
+        let mut cancelling = ndarray::ArrayD::from_elem(vec![3, 3], 1.0);
+        cancelling[[1, 1]] = -8.0;
+        for kernel in [
+            ndarray::ArrayD::zeros(vec![3, 3]),
+            cancelling,
+            ndarray::ArrayD::from_elem(vec![3, 3], f64::NAN),
+        ] {
+            assert!(matches!(
+                Kernel::try_from(kernel.clone(), &[16, 16]),
+                Err(LeniaError::InvalidParameter(_))
+            ));
+            assert!(matches!(
+                simulator.try_set_kernel(kernel, 0),
+                Err(LeniaError::InvalidParameter(_))
+            ));
+        }
+        simulator.fill_channel(&ndarray::ArrayD::from_elem(vec![16, 16], 0.5), 0);
+        simulator.iterate();
+        assert!(simulator
+            .get_channel_as_ref(0)
+            .iter()
+            .all(|value| value.is_finite()));
+    }
+
+    /// Straightforward convolution with periodic boundaries, centering the kernel as `Kernel` does.
+    fn reference_convolution(
+        input: &ndarray::ArrayD<f64>,
+        kernel: &ndarray::ArrayD<f64>,
+    ) -> ndarray::ArrayD<f64> {
+        let kernel = kernel / kernel.sum();
+        let shape = input.shape();
+        ndarray::ArrayD::from_shape_fn(shape, |index| {
+            kernel
+                .indexed_iter()
+                .map(|(offset, weight)| {
+                    let source: Vec<usize> = (0..shape.len())
+                        .map(|axis| {
+                            let position = index[axis] as isize
+                                + (kernel.shape()[axis] / 2) as isize
+                                - offset[axis] as isize;
+                            position.rem_euclid(shape[axis] as isize) as usize
+                        })
+                        .collect();
+                    weight * input[source.as_slice()]
+                })
+                .sum()
+        })
+    }
+
+    #[test]
+    fn expanded_lenia_matches_a_reference_implementation() {
+        let shape = [24, 26];
+        let sources = [0, 1, 2, 0];
+        let mut asymmetric = ndarray::ArrayD::from_elem(vec![3, 4], 0.1);
+        asymmetric[[0, 3]] = 1.0;
+        asymmetric[[2, 0]] = 0.5;
+        let kernels = [
+            kernels::gaussian_donut_2d(5, 0.15),
+            kernels::gaussian_donut_2d(4, 0.2),
+            asymmetric,
+            kernels::multi_gaussian_donut_2d(6, &[0.25, 0.75], &[1.0, 0.5], &[0.1, 0.1]),
+        ];
+        let growth = [(0.15, 0.015), (0.2, 0.04), (0.3, 0.05), (0.25, 0.03)];
+        let weights = [
+            [1.0, 0.0, 0.5, 0.0],
+            [0.5, 1.0, 0.0, 0.25],
+            [0.0, 0.5, 0.5, 1.0],
+        ];
+        let dt = 0.2;
+        let seeds: Vec<ndarray::ArrayD<f64>> = (0..3)
+            .map(|channel| {
+                ndarray::ArrayD::from_shape_fn(shape.to_vec(), |index| {
+                    ((index[0] * 7 + index[1] * 11 + channel * 5) % 17) as f64 / 17.0
+                })
+            })
+            .collect();
+
+        let mut expected = seeds.clone();
+        for _ in 0..5 {
+            let activated: Vec<ndarray::ArrayD<f64>> = (0..4)
+                .map(|i| {
+                    let (mu, sigma) = growth[i];
+                    reference_convolution(&expected[sources[i]], &kernels[i]).mapv(|x: f64| {
+                        2.0 * (-(x - mu) * (x - mu) / (2.0 * sigma * sigma)).exp() - 1.0
+                    })
+                })
+                .collect();
+            for (channel, weights) in expected.iter_mut().zip(&weights) {
+                let mut deltas = ndarray::ArrayD::<f64>::zeros(shape.to_vec());
+                for (weight, activated) in weights.iter().zip(&activated) {
+                    deltas = deltas + activated * *weight;
+                }
+                *channel = (&*channel + &(deltas * dt)).mapv(|value: f64| value.clamp(0.0, 1.0));
+            }
+        }
+
+        for backend in [ConvolutionBackend::Fft, ConvolutionBackend::Direct] {
+            let mut simulator = Simulator::<ExpandedLenia>::new(&shape);
+            simulator.set_channels(3);
+            simulator.set_convolution_channels(4);
+            for i in 0..4 {
+                simulator.set_convolution_channel_source(i, sources[i]);
+                simulator.set_kernel(kernels[i].clone(), i);
+                simulator.set_growth_function(
+                    growth_functions::StandardLenia::new(growth[i].0, growth[i].1),
+                    i,
+                );
+                simulator.set_convolution_backend(backend, i);
+            }
+            for (channel, seed) in seeds.iter().enumerate() {
+                simulator.set_weights(channel, &weights[channel]);
+                simulator.fill_channel(seed, channel);
+            }
+            simulator.set_dt(dt);
+            for _ in 0..5 {
+                simulator.iterate();
+            }
+            for (channel, expected) in expected.iter().enumerate() {
+                for (a, b) in simulator.get_channel_as_ref(channel).iter().zip(expected) {
+                    assert!(
+                        (a - b).abs() < 1e-9,
+                        "{:?} backend, channel {}: {} != {}",
+                        backend,
+                        channel,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn setters_reject_invalid_indices_through_trait_objects() {
+        let lenias: Vec<Box<dyn Lenia<Float = f64>>> = vec![
+            Box::new(StandardLenia::new(&[64, 64])),
+            Box::new(ExpandedLenia::new(&[32, 32])),
+            Box::new(FlowLenia::new(&[32, 32])),
+        ];
+        for mut lenia in lenias {
+            let kernel = || kernels::gaussian_donut_2d(4, 0.15);
+            let growth = || -> Arc<dyn GrowthFunction<f64>> {
+                Arc::new(growth_functions::StandardLenia::new(0.15, 0.015))
+            };
+            assert!(lenia.set_kernel(kernel(), 5).is_err());
+            assert!(lenia
+                .set_kernel(ndarray::ArrayD::zeros(vec![3, 3]), 0)
+                .is_err());
+            assert!(lenia.set_growth(growth(), 5).is_err());
+            assert!(lenia
+                .set_growth(
+                    Arc::new(growth_functions::StandardLenia::new(0.15, -1.0)),
+                    0
+                )
+                .is_err());
+            assert!(lenia.set_parameter_map(None, 0, 5).is_err());
+            assert!(lenia.set_backend(ConvolutionBackend::Direct, 5).is_err());
+            assert!(lenia.set_source_channel(5, 0).is_err());
+            assert!(lenia.set_source_channel(0, 5).is_err());
+            assert!(lenia.set_weights(&[1.0], 5).is_err());
+            assert!(lenia.set_update_mode(UpdateMode::Additive, 5).is_err());
+            assert!(lenia.set_value_range(ValueRange::default(), 5).is_err());
+            assert!(lenia.set_channels(0).is_err());
+            assert!(lenia.set_conv_channels(0).is_err());
+
+            lenia.set_kernel(kernel(), 0).unwrap();
+            lenia.set_growth(growth(), 0).unwrap();
+            assert!(lenia.set_parameter_map(None, 2, 0).is_err());
+            let map = ndarray::ArrayD::from_elem(lenia.shape(), 0.2);
+            assert!(matches!(
+                lenia.set_parameter_map(Some(ndarray::ArrayD::zeros(vec![3, 3])), 0, 0),
+                Err(LeniaError::ShapeMismatch { .. })
+            ));
+            lenia.set_parameter_map(Some(map), 0, 0).unwrap();
+            lenia.iterate();
+        }
+    }
+
+    /// Sets up a simulation of `L` the same way whether it is used directly or through a trait object.
+    fn configure<L: Lenia<Float = f64>>(simulator: &mut Simulator<L>) {
+        simulator.set_kernel(kernels::gaussian_donut_2d(8, 0.15), 0);
+        simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+        simulator.set_dt(0.2);
+        simulator.fill_channel(&blob(64), 0);
+    }
+
+    #[test]
+    fn mixed_trait_objects_iterate_like_their_implementations() {
+        let mut simulators: Vec<crate::DynSimulator> = vec![
+            Simulator::from_lenia(Box::new(StandardLenia::new(&[64, 64]))),
+            Simulator::from_lenia(Box::new(ExpandedLenia::new(&[64, 64]))),
+            Simulator::from_lenia(Box::new(FlowLenia::new(&[64, 64]))),
+        ];
+        let mut standard = Simulator::<StandardLenia>::new(&[64, 64]);
+        let mut expanded = Simulator::<ExpandedLenia>::new(&[64, 64]);
+        let mut flow = Simulator::<FlowLenia>::new(&[64, 64]);
+        configure(&mut standard);
+        configure(&mut expanded);
+        configure(&mut flow);
+        for simulator in simulators.iter_mut() {
+            configure(simulator);
+        }
+
+        for _ in 0..3 {
+            for simulator in simulators.iter_mut() {
+                simulator.iterate();
+            }
+            standard.iterate();
+            expanded.iterate();
+            flow.iterate();
+        }
+        let expected = [
+            standard.get_channel_as_ref(0),
+            expanded.get_channel_as_ref(0),
+            flow.get_channel_as_ref(0),
+        ];
+        for (simulator, expected) in simulators.iter().zip(expected) {
+            assert_eq!(simulator.steps(), 3);
+            assert_eq!(simulator.get_channel_as_ref(0), expected);
+            assert_ne!(simulator.get_channel_as_ref(0), &blob(64));
+        }
+    }
+
+    #[test]
+    fn setting_boundaries_checks_their_number() {
+        let short = [BoundaryCondition::Zero];
+        let mut standard = Simulator::<StandardLenia>::new(&[64, 64]);
+        let mut expanded = Simulator::<ExpandedLenia>::new(&[32, 32]);
+        let mut flow = flow_lenia(32);
+        for result in [
+            standard.lenia_mut().set_boundaries(&short),
+            expanded.lenia_mut().set_boundaries(&short),
+            flow.lenia_mut().set_boundaries(&short),
+            standard.try_set_boundary_conditions(&[BoundaryCondition::Zero; 3]),
+        ] {
+            assert!(matches!(
+                result,
+                Err(LeniaError::DimensionalityMismatch { expected: 2, .. })
+            ));
+        }
+        standard.iterate();
+        expanded.iterate();
+        flow.iterate();
+    }
+
+    #[test]
+    fn soft_value_ranges_leave_unchanging_values_alone() {
+        for saturation in [Saturation::Sigmoid, Saturation::Tanh] {
+            for (min, max) in [(0.0, 1.0), (-2.0, 3.0)] {
+                for value in [0.0, 0.3] {
+                    let mut simulator = Simulator::<ExpandedLenia>::new(&[16, 16]);
+                    simulator.set_growth_function(|_: f64| 0.0, 0);
+                    simulator.set_value_range(0, ValueRange::new(min, max, saturation));
+                    simulator.fill_channel(&ndarray::ArrayD::from_elem(vec![16, 16], value), 0);
+                    for _ in 0..20 {
+                        simulator.iterate();
+                    }
+                    assert!(
+                        simulator.get_channel_as_ref(0).iter().all(|&v| v == value),
+                        "{:?} on {}..{} moved {}",
+                        saturation,
+                        min,
+                        max,
+                        value
+                    );
+
+                    simulator.set_growth_function(|_: f64| 1.0, 0);
+                    let mut previous = value;
+                    for _ in 0..20 {
+                        simulator.iterate();
+                        let current = simulator.get_channel_as_ref(0)[[0, 0]];
+                        assert!(current >= previous && current <= max);
+                        previous = current;
+                    }
+                    assert!(previous > value);
+                }
+            }
+        }
+    }
 }
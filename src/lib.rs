@@ -10,25 +10,64 @@
 //!
 //! A rough example of a quick-start code is below... Please note that `display()` function would have to be implemented by the user.
 //! ```
-//! let starting_pattern: ndarray::ArrayD<f64>; // fill with your data
+//! # use road_to_lenia::{lenias::StandardLenia, Simulator};
+//! # fn display(_: &ndarray::ArrayD<f64>) {}
 //! let channel_shape: Vec<usize> = vec![100, 100];
+//! let starting_pattern = ndarray::ArrayD::<f64>::zeros(channel_shape.clone()); // fill with your data
 //! let mut simulator = Simulator::<StandardLenia>::new(&channel_shape);
 //! simulator.fill_channel(&starting_pattern, 0);
-//! while true {
+//! for _ in 0..10 {
 //!     simulator.iterate();
-//!     display(get_channel_as_ref(0));
+//!     display(simulator.get_channel_as_ref(0));
 //! }
 //! ```
 //!
 //! ### Types of Lenia
 //!
-//! This version of `lenia_ca` crate supports only 2 different types of Lenia systems. `StandardLenia` and `ExpandedLenia` types, and is
-//! not capable of simulating types like "asymptotic" or "particle" Lenia.
+//! This version of `lenia_ca` crate supports 3 different types of grid-based Lenia systems. `StandardLenia`, `ExpandedLenia` and
+//! `FlowLenia` types. "Asymptotic" Lenia is simulated by `StandardLenia` and `ExpandedLenia` with channels set to
+//! `UpdateMode::Asymptotic`.
+//!
+//! The `Lenia` type does not have to be known at compile time. A `DynSimulator` wraps a `Box<dyn Lenia>`, made with
+//! `Simulator::from_lenia()`, such as when the type of Lenia is read from a configuration file.
+//!
+//! Particle Lenia, where the system is made of point particles in continuous space, is available as `ParticleLenia`
+//! in the `particles` module. It does not use the `Simulator`, but its particles can be rasterized onto a grid for
+//! `get_frame()` and `export_frame_as_png()`.
+//!
+//! Many worlds of the same shape and rules, such as when running the same rules from many seeds or sweeping over growth
+//! function parameters, are best simulated by `BatchSimulator` in the `batch` module. It plans its ffts and makes its
+//! kernels once for the whole batch, and steps every world in parallel.
+//!
+//! Rulesets can also be described declaratively, and read from or written to JSON and TOML files with
+//! `Simulator::load_rules()` and `Simulator::save_rules()`, see the `rules` module.
+//!
+//! Long simulations can be stopped and resumed bit-for-bit with `Simulator::save_checkpoint()` and
+//! `Simulator::load_checkpoint()`, see the `checkpoint` module.
+//!
+//! Species of the original Lenia animal library can be read with `animals::load_library()`, and simulated with
+//! `Simulator::from_species()`.
+//!
+//! Channels, kernels and snapshots of whole simulations can be read from and written to NumPy `.npy` and `.npz`
+//! files at full precision, see the `npy` module.
+//!
+//! Simulations can be recorded into animated GIFs or numbered PNG sequences with a `Recorder`, see the `recorder`
+//! module.
+//!
+//! Frames can be rendered with colormaps, or several channels composited in their own colors, into RGBA images for
+//! exporting or drawing, see the `rendering` module.
+//!
+//! ### Precision
+//!
+//! Both Lenia types are generic over their floating-point type (see `LeniaFloat`), which defaults to `f64`.
+//! `Simulator::<ExpandedLenia<f32>>` runs the same simulation in single precision, with half the memory use.
+//! Kernels and growth functions from the `kernels` and `growth_functions` modules work at either precision.
 //!
 //! ### Implementation notes
 //!
 //! The working principle for `StandardLenia` is the following:
-//! * Perform a convolution operation (implemented as a FFT-based convolution) between the `channel` and `kernel` of the `convolution_channel`
+//! * Perform a convolution operation (implemented as a FFT-based convolution, or a direct convolution for small kernels,
+//! see `ConvolutionBackend`) between the `channel` and `kernel` of the `convolution_channel`
 //! * Each point/pixel's value is then passed into a `growth_function` of the `convolution_channel`.
 //! * The resulting points/pixels are then multiplied by the integration step `dt` and added onto the original values in the `channel`.
 //! * The resulting points/pixels are then clamped to be in range `0..1`. This result is the next time-step of the `channel`, and would
@@ -40,10 +79,13 @@
 //!
 //! use `set_dt()` to change the integration-step of the simulation.
 //!
+//! use `set_integrator()` to advance the channel with a higher-order scheme (midpoint, Heun or Runge-Kutta) instead of Euler.
+//!
 //! [Image of the algorithm available on Github](https://github.com/BirdbrainEngineer/lenia_ca)
 //!
 //! The working principle for `ExpandedLenia` is the following:
-//! * For each `convolution_channel`, perform a convolution operation (implemented as a FFT-based convolution) between a source `channel`
+//! * For each `convolution_channel`, perform a convolution operation (implemented as a FFT-based convolution, or a direct
+//! convolution for small kernels, see `ConvolutionBackend`) between a source `channel`
 //! and the `convolution_channel`'s `kernel`.
 //! * For each `convolution_channel`, pass the convolution results into the `growth_function` of the `convolution_channel`.
 //! * For each `channel`, perform an elementwise multiplication between the corresponding `convolution_channel` results and weights of the
@@ -67,20 +109,55 @@
 //!
 //! use `set_weights()` to set a channel's weights for the corresponding convolution channel results.
 //!
+//! use `set_update_mode()` to make a channel approach its weighted sum as a target (Asymptotic Lenia) instead of adding it on.
+//!
+//! use `set_boundary_conditions()` to make the edges of the world absorbing or reflective instead of wrapping around.
+//!
+//! use `set_convolution_backend()` to force a convolution channel to use direct or FFT-based convolution.
+//!
+//! use `set_integrator()` to advance the channels with a higher-order scheme (midpoint, Heun or Runge-Kutta) instead of Euler.
+//!
 //! use `set_dt()` to change the integration-step of the simulation.
+//!
+//! use `set_adaptive_dt()` to let the simulation pick the integration-step of every iteration, and `time()` for the simulated time.
+//!
+//! The working principle for `FlowLenia` is the following:
+//! * Compute the growth of each `channel` exactly like `ExpandedLenia` does, but treat it as an "affinity" map instead of
+//! adding it onto the `channel`.
+//! * For each `channel`, compute the gradient of its affinity and the gradient of the total mass of all channels.
+//! * Mix the two gradients into a flow field. Where the total mass approaches the critical mass, matter flows away from dense
+//! regions instead of up the affinity gradient.
+//! * Move the matter of each point along the flow field by `dt` and spread it over a small square, which is redistributed
+//! onto the grid. The total mass of each `channel` is conserved, and values are not clamped to `0..1`.
+//!
+//! `FlowLenia` is configured with the same functions as `ExpandedLenia`. The flow parameters (critical mass, spread of the
+//! redistributed matter and maximum displacement) are set through the methods of `FlowLenia` itself.
 
 #![allow(dead_code)]
 #![allow(unused_variables)]
 use ndarray::{self, Axis, Ix2, Order, Slice};
 use num_complex::Complex;
-use png;
+use num_traits::{Float as _, One as _, Zero as _};
+use serde::{Deserialize, Serialize};
 #[cfg(target_has_atomic = "ptr")]
 use std::fmt;
+use std::sync::Arc;
 use std::thread::JoinHandle;
+pub mod animals;
+pub mod batch;
+pub mod checkpoint;
+mod direct;
 mod fft;
 pub mod growth_functions;
 pub mod kernels;
 pub mod lenias;
+pub mod npy;
+pub mod particles;
+pub mod recorder;
+pub mod rendering;
+pub mod rules;
+#[cfg(test)]
+mod test_util;
 
 trait SetBytes {
     fn set_low(&mut self, value: u8);
@@ -112,6 +189,162 @@ impl GetBytes for u16 {
     }
 }
 
+/// Floating-point type that a Lenia simulation can be run at.
+///
+/// Implemented for `f32` and `f64`. Every `Lenia` implementation, `Kernel`, growth function
+/// and kernel generator is generic over it, `f64` being the default everywhere.
+/// Use `f32` to halve the memory use of large (especially 3d and higher dimensional) worlds.
+pub trait LeniaFloat:
+    num_traits::Float
+    + num_traits::NumAssign
+    + rustfft::FftNum
+    + ndarray::ScalarOperand
+    + std::iter::Sum
+    + Default
+    + fmt::Display
+{
+}
+
+impl LeniaFloat for f32 {}
+
+impl LeniaFloat for f64 {}
+
+/// Error returned by the fallible functions of the crate.
+///
+/// Every function that panics on bad input has a `try_` variant returning this instead, such as
+/// `Simulator::try_set_kernel()` for `Simulator::set_kernel()`, for programs that must not crash on bad
+/// user input. Operations that a type of Lenia does not support are also returned as errors.
+#[derive(Debug)]
+pub enum LeniaError {
+    /// The shape of the channels can not be used, such as when an axis has a length of `0`.
+    InvalidShape(String),
+    /// The specified channel does not exist.
+    ChannelOutOfRange { channel: usize, channels: usize },
+    /// The specified convolution channel does not exist.
+    ConvolutionChannelOutOfRange {
+        convolution_channel: usize,
+        convolution_channels: usize,
+    },
+    /// The specified world of a `BatchSimulator` does not exist.
+    WorldOutOfRange { world: usize, worlds: usize },
+    /// The number of axes of some data does not agree with the dimensionality of the channels.
+    DimensionalityMismatch { expected: usize, found: usize },
+    /// The shape of some data does not agree with the shape it is used with.
+    ShapeMismatch {
+        expected: Vec<usize>,
+        found: Vec<usize>,
+    },
+    /// A parameter is outside of its allowed range.
+    InvalidParameter(String),
+    /// A growth function has no parameter by the given name.
+    UnknownParameter {
+        growth_function: String,
+        parameter: String,
+        available: Vec<String>,
+    },
+    /// The operation is not supported by the type of Lenia.
+    Unsupported(String),
+    /// No kernel generator or growth function is registered by the given name, see `rules::Registry`.
+    UnknownName {
+        kind: String,
+        name: String,
+        available: Vec<String>,
+    },
+    /// A file is not in the expected format, such as a malformed rules file.
+    Format(String),
+    /// A checkpoint was saved at a different floating-point precision than it is being restored at.
+    PrecisionMismatch { expected: String, found: String },
+    /// A checkpoint was saved in a different version of the checkpoint format.
+    VersionMismatch {
+        expected: u32,
+        found: u32,
+        written_by: String,
+    },
+    /// An image could not be decoded or encoded.
+    Image(String),
+    /// A file could not be read or written.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for LeniaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeniaError::InvalidShape(message) => write!(f, "{}", message),
+            LeniaError::ChannelOutOfRange { channel, channels } => write!(f, "Specified channel (index {}) does not exist. Current number of channels: {}.", channel, channels),
+            LeniaError::ConvolutionChannelOutOfRange { convolution_channel, convolution_channels } => write!(f, "Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, convolution_channels),
+            LeniaError::WorldOutOfRange { world, worlds } => write!(f, "Specified world (index {}) does not exist. Current number of worlds: {}.", world, worlds),
+            LeniaError::DimensionalityMismatch { expected, found } => write!(f, "Dimensionality of the data ({}) does not agree with channels' dimensionality ({}).", found, expected),
+            LeniaError::ShapeMismatch { expected, found } => write!(f, "Shape of the data ({:?}) does not agree with the expected shape ({:?}).", found, expected),
+            LeniaError::InvalidParameter(message) => write!(f, "{}", message),
+            LeniaError::UnknownParameter { growth_function, parameter, available } => write!(f, "Growth function \"{}\" has no parameter called \"{}\". Available parameters: {:?}.", growth_function, parameter, available),
+            LeniaError::Unsupported(message) => write!(f, "{}", message),
+            LeniaError::UnknownName { kind, name, available } => write!(f, "There is no {} called \"{}\". Available: {:?}.", kind, name, available),
+            LeniaError::Format(message) => write!(f, "{}", message),
+            LeniaError::PrecisionMismatch { expected, found } => write!(f, "The checkpoint was saved at {} precision, but is being restored at {} precision.", found, expected),
+            LeniaError::VersionMismatch { expected, found, written_by } => write!(f, "The checkpoint is in version {} of the checkpoint format (written by version {} of the crate), but only version {} is supported.", found, written_by, expected),
+            LeniaError::Image(message) => write!(f, "{}", message),
+            LeniaError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for LeniaError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LeniaError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LeniaError {
+    fn from(error: std::io::Error) -> Self {
+        LeniaError::Io(error)
+    }
+}
+
+impl From<png::DecodingError> for LeniaError {
+    fn from(error: png::DecodingError) -> Self {
+        match error {
+            png::DecodingError::IoError(error) => LeniaError::Io(error),
+            error => LeniaError::Image(error.to_string()),
+        }
+    }
+}
+
+impl From<png::EncodingError> for LeniaError {
+    fn from(error: png::EncodingError) -> Self {
+        match error {
+            png::EncodingError::IoError(error) => LeniaError::Io(error),
+            error => LeniaError::Image(error.to_string()),
+        }
+    }
+}
+
+impl From<gif::EncodingError> for LeniaError {
+    fn from(error: gif::EncodingError) -> Self {
+        match error {
+            gif::EncodingError::Io(error) => LeniaError::Io(error),
+            error => LeniaError::Image(error.to_string()),
+        }
+    }
+}
+
+/// Checks that no axis of `shape` has a length of `0`.
+fn check_shape(shape: &[usize]) -> Result<(), LeniaError> {
+    for (i, dim) in shape.iter().enumerate() {
+        if *dim == 0 {
+            return Err(LeniaError::InvalidShape(format!("Axis {} of the provided shape has a length of 0! Each axis must have a length of at least 1.", i)));
+        }
+    }
+    Ok(())
+}
+
+/// Converts a `f64` constant into the floating-point type of the simulation.
+fn float<F: LeniaFloat>(value: f64) -> F {
+    F::from(value).unwrap()
+}
+
 /// Samples the normal distribution where the peak (at `x = mu`) is 1.
 /// This is not suitable for use as a gaussian probability density function!
 ///
@@ -122,26 +355,171 @@ impl GetBytes for u16 {
 /// * `mu` - The mean (point of the highest value/peak) of the normal distribution.
 ///
 /// * `stddev` - Standard deviation of the normal distribution.
-fn sample_normal(x: f64, mu: f64, stddev: f64) -> f64 {
-    (-(((x - mu) * (x - mu)) / (2.0 * (stddev * stddev)))).exp()
+fn sample_normal<F: LeniaFloat>(x: F, mu: F, stddev: F) -> F {
+    (-(((x - mu) * (x - mu)) / (float::<F>(2.0) * (stddev * stddev)))).exp()
 }
 
-fn sample_exponential(x: f64, exponent: f64, peak: f64) -> f64 {
+fn sample_exponential<F: LeniaFloat>(x: F, exponent: F, peak: F) -> F {
     peak * (-(x * exponent)).exp()
 }
 
 /// Euclidean distance between points `a` and `b`.
-fn euclidean_dist(a: &[f64], b: &[f64]) -> f64 {
-    let mut out: f64 = 0.0;
+fn euclidean_dist<F: LeniaFloat>(a: &[F], b: &[F]) -> F {
+    let mut out = F::zero();
     for i in 0..a.len() {
         out += (a[i] - b[i]) * (a[i] - b[i]);
     }
     out.sqrt()
 }
 
+/// Extends `data` by `before` and `after` elements along `axis`, filling the new elements
+/// according to the `boundary` condition of the axis.
+///
+/// Neither `before` nor `after` may be larger than the length of the axis.
+fn pad_axis<F: LeniaFloat>(
+    data: ndarray::ArrayViewD<F>,
+    axis: usize,
+    before: usize,
+    after: usize,
+    boundary: BoundaryCondition,
+) -> ndarray::ArrayD<F> {
+    let n = data.shape()[axis] as isize;
+    let (before, after) = (before as isize, after as isize);
+    let mut padding_shape = data.shape().to_vec();
+    let zeros_before;
+    let zeros_after;
+    let parts = match boundary {
+        BoundaryCondition::Periodic => [
+            data.slice_axis(Axis(axis), Slice::from(n - before..n)),
+            data.view(),
+            data.slice_axis(Axis(axis), Slice::from(0..after)),
+        ],
+        BoundaryCondition::Zero => {
+            padding_shape[axis] = before as usize;
+            zeros_before = ndarray::ArrayD::zeros(padding_shape.clone());
+            padding_shape[axis] = after as usize;
+            zeros_after = ndarray::ArrayD::zeros(padding_shape);
+            [zeros_before.view(), data.view(), zeros_after.view()]
+        }
+        BoundaryCondition::Mirror => [
+            data.slice_axis(Axis(axis), Slice::new(0, Some(before), -1)),
+            data.view(),
+            data.slice_axis(Axis(axis), Slice::new(n - after, Some(n), -1)),
+        ],
+    };
+    ndarray::concatenate(Axis(axis), &parts).unwrap()
+}
+
+/// Extends `data` along every axis by the corresponding `(before, after)` number of elements in `padding`,
+/// filling the new elements according to the `boundaries` of each axis.
+fn pad<F: LeniaFloat>(
+    data: &ndarray::ArrayD<F>,
+    padding: &[(usize, usize)],
+    boundaries: &[BoundaryCondition],
+) -> ndarray::ArrayD<F> {
+    let mut padded = data.clone();
+    for (axis, (before, after)) in padding.iter().enumerate() {
+        if *before != 0 || *after != 0 {
+            padded = pad_axis(padded.view(), axis, *before, *after, boundaries[axis]);
+        }
+    }
+    padded
+}
+
+/// Calculates how much each axis of a channel with `channel_shape` needs to be padded by,
+/// such that a fft-based convolution with kernels no larger than `kernel_shape` respects the
+/// `boundaries` of the channel.
+///
+/// Returns the `(before, after)` padding of each axis.
+fn fft_padding(kernel_shape: &[usize], boundaries: &[BoundaryCondition]) -> Vec<(usize, usize)> {
+    kernel_shape
+        .iter()
+        .zip(boundaries)
+        .map(|(k, boundary)| match boundary {
+            BoundaryCondition::Periodic => (0, 0),
+            _ => (k - 1 - k / 2, k / 2),
+        })
+        .collect()
+}
+
+/// Forward fft of a channel, padding it beforehand by `padding` according to `boundaries`.
+fn transform_padded<F: LeniaFloat>(
+    fft_instance: &mut fft::ParPlannedFFTND<F>,
+    field: &ndarray::ArrayD<F>,
+    padding: &[(usize, usize)],
+    boundaries: &[BoundaryCondition],
+    spectrum: &mut ndarray::ArrayD<Complex<F>>,
+) {
+    if padding.iter().all(|p| *p == (0, 0)) {
+        fft_instance.transform_real(field, spectrum);
+    } else {
+        fft_instance.transform_real(&pad(field, padding, boundaries), spectrum);
+    }
+}
+
+/// Inverse fft of a padded channel's spectrum, cropping away the `padding` of the result.
+fn transform_padded_inverse<F: LeniaFloat>(
+    fft_instance: &mut fft::ParPlannedFFTND<F>,
+    spectrum: &mut ndarray::ArrayD<Complex<F>>,
+    padding: &[(usize, usize)],
+    output: &mut ndarray::ArrayD<F>,
+) {
+    if padding.iter().all(|p| *p == (0, 0)) {
+        fft_instance.transform_real_inverse(spectrum, output);
+    } else {
+        let mut padded = ndarray::ArrayD::zeros(fft_instance.shape());
+        fft_instance.transform_real_inverse(spectrum, &mut padded);
+        output.assign(&padded.slice_each_axis(|a| {
+            let before = padding[a.axis.index()].0;
+            Slice::from(before..before + output.shape()[a.axis.index()])
+        }));
+    }
+}
+
+/// Mapping of the values of a frame onto the range `0.0..1.0` for rendering,
+/// needed for channels with a `ValueRange` other than `0.0..1.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Normalization<F: LeniaFloat = f64> {
+    /// The values are kept as they are.
+    #[default]
+    None,
+    /// Values in range `min..max` are mapped linearly onto `0.0..1.0`, values outside of it are clamped.
+    Range {
+        /// Value mapped onto `0.0`.
+        min: F,
+        /// Value mapped onto `1.0`.
+        max: F,
+    },
+    /// The smallest and largest values of the frame are mapped onto `0.0` and `1.0`.
+    MinMax,
+}
+
+impl<F: LeniaFloat> Normalization<F> {
+    /// Maps the values of `frame` onto the range `0.0..1.0`.
+    fn normalize<D: ndarray::Dimension>(&self, frame: &mut ndarray::Array<F, D>) {
+        let (min, max) = match *self {
+            Normalization::None => return,
+            Normalization::Range { min, max } => (min, max),
+            Normalization::MinMax => frame
+                .iter()
+                .fold((F::infinity(), F::neg_infinity()), |(min, max), value| {
+                    (min.min(*value), max.max(*value))
+                }),
+        };
+        let scale = if max > min {
+            F::one() / (max - min)
+        } else {
+            F::zero()
+        };
+        frame.par_mapv_inplace(|value| {
+            num_traits::clamp((value - min) * scale, F::zero(), F::one())
+        });
+    }
+}
+
 /// Extract data from n-dimensional array into a 2-dimensional array.
 ///
-/// Extract a 2d array (`ndarray::Array2`) of `f64` values of a 2d slice of a channel's data.
+/// Extract a 2d array (`ndarray::Array2`) of values of a 2d slice of a channel's data.
 /// Use this to simply get a 2d frame for rendering.
 ///
 /// ### Parameters
@@ -154,9 +532,9 @@ fn euclidean_dist(a: &[f64], b: &[f64]) -> f64 {
 ///
 /// * `dimensions` - Which indexes in any other axes the 2d slice is extracted from.
 /// The entries for axes selected in `display_axes` can be any number, and will be disregarded.
-pub fn get_frame(
-    input: &ndarray::ArrayD<f64>,
-    output: &mut ndarray::Array2<f64>,
+pub fn get_frame<F: LeniaFloat>(
+    input: &ndarray::ArrayD<F>,
+    output: &mut ndarray::Array2<F>,
     display_axes: &[usize; 2],
     dimensions: &[usize],
 ) {
@@ -170,17 +548,17 @@ pub fn get_frame(
     }
     let data = input.slice_each_axis(|a| {
         if a.axis.index() == display_axes[0] || a.axis.index() == display_axes[1] {
-            return Slice {
+            Slice {
                 start: 0,
                 end: None,
                 step: 1,
-            };
+            }
         } else {
-            return Slice {
+            Slice {
                 start: dimensions[a.axis.index()] as isize,
                 end: Some((dimensions[a.axis.index()] + 1) as isize),
                 step: 1,
-            };
+            }
         }
     });
     let data = data
@@ -197,6 +575,34 @@ pub fn get_frame(
     });
 }
 
+/// Extract data from n-dimensional array into a 2-dimensional array, normalizing the values.
+///
+/// Same as `get_frame()`, after which the extracted frame is normalized with `normalization`. With
+/// `Normalization::MinMax` the smallest and largest values of the extracted 2d slice are used.
+///
+/// ### Parameters
+///
+/// * `input` - Channel data to extract the 2d frame from.
+///
+/// * `output` - 2D array into which to place the extracted frame.
+///
+/// * `display_axes` - Indexes of the axes to extract
+///
+/// * `dimensions` - Which indexes in any other axes the 2d slice is extracted from.
+/// The entries for axes selected in `display_axes` can be any number, and will be disregarded.
+///
+/// * `normalization` - The way the values are mapped onto `0.0..1.0`.
+pub fn get_frame_normalized<F: LeniaFloat>(
+    input: &ndarray::ArrayD<F>,
+    output: &mut ndarray::Array2<F>,
+    display_axes: &[usize; 2],
+    dimensions: &[usize],
+    normalization: Normalization<F>,
+) {
+    get_frame(input, output, display_axes, dimensions);
+    normalization.normalize(output);
+}
+
 /// Loads a png into an `ndarray`.
 ///
 /// ### Parameters
@@ -208,15 +614,26 @@ pub fn get_frame(
 /// * If the bit-depth of the png is less than 8.
 ///
 /// * If the png has a color type different from Grayscale, Grayscale with alpha, RGB or RGBA.
+///
+/// * If the file can not be read or decoded as a png.
 pub fn load_from_png(file_path: &str) -> ndarray::Array2<f64> {
-    let decoder = png::Decoder::new(std::fs::File::open(file_path).unwrap());
-    let mut reader = decoder.read_info().unwrap();
+    match try_load_from_png(file_path) {
+        Ok(frame) => frame,
+        Err(error) => panic!("lenia_ca::load_from_png() - {}", error),
+    }
+}
+
+/// Same as `load_from_png()`, but returns an error instead of panicking.
+pub fn try_load_from_png(file_path: &str) -> Result<ndarray::Array2<f64>, LeniaError> {
+    let decoder = png::Decoder::new(std::fs::File::open(file_path)?);
+    let mut reader = decoder.read_info()?;
     let mut buf = vec![0; reader.output_buffer_size()];
-    let info = reader.next_frame(&mut buf).unwrap();
+    let info = reader.next_frame(&mut buf)?;
     if info.bit_depth != png::BitDepth::Eight && info.bit_depth != png::BitDepth::Sixteen {
-        panic!("lenia_ca::load_from_png() - Unable to load from .png, as it has a bit depth of less than 8!");
+        return Err(LeniaError::Image(
+            "Unable to load from .png, as it has a bit depth of less than 8!".to_string(),
+        ));
     }
-    let output: ndarray::Array2<f64>;
     let offset: usize;
     match info.color_type {
         png::ColorType::Grayscale => {
@@ -248,12 +665,12 @@ pub fn load_from_png(file_path: &str) -> ndarray::Array2<f64> {
             }
         }
         _ => {
-            panic!("lenia_ca::load_from_png() - Unsupported color type!");
+            return Err(LeniaError::Image("Unsupported color type!".to_string()));
         }
     }
     let shape = [info.width as usize, info.height as usize];
-    if info.bit_depth == png::BitDepth::Sixteen {
-        output = ndarray::Array2::from_shape_fn(Ix2(shape[0], shape[1]), |a| {
+    Ok(if info.bit_depth == png::BitDepth::Sixteen {
+        ndarray::Array2::from_shape_fn(Ix2(shape[0], shape[1]), |a| {
             let mut num: u16 = 0;
             num.set_high(
                 *buf.get((a.1 * info.width as usize * offset) + (a.0 * offset))
@@ -264,27 +681,97 @@ pub fn load_from_png(file_path: &str) -> ndarray::Array2<f64> {
                     .unwrap(),
             );
             num as f64 * (1.0 / 65535.0)
-        });
+        })
     } else {
-        output = ndarray::Array2::from_shape_fn(Ix2(shape[0], shape[1]), |a| {
+        ndarray::Array2::from_shape_fn(Ix2(shape[0], shape[1]), |a| {
             *buf.get((a.1 * info.width as usize * offset) + (a.0 * offset))
                 .unwrap() as f64
                 * (1.0 / 255.0)
-        });
-    }
-    output
+        })
+    })
 }
 
 /// Export a frame as a png or a bunch of png-s if multidimensional.
 ///
-/// The function returns a `JoinHandle` because the exporting takes place on a separate thread.
+/// The function returns a `JoinHandle` because the exporting takes place on a separate thread, which is
+/// spawned for every call. See `write_frame_as_png()` to export on the calling thread instead.
+///
+/// ### Parameters
+///
+/// * `bit_depth` - Controls whether to output as 8-bit or 16-bit png-s.
+///
+/// * `frame` - Reference to the frame to be stored.
+///
+/// * `colormap` - Colors of the values of the frame. Grayscale png-s are written with `Colormap::Grayscale`,
+/// and RGB png-s with any other colormap.
+///
+/// * `prefix` - Output file name. Numbers will be added after this string based on the 2d slice
+/// of the frame (if exporting a 3d or higher dimensionality frame). **This prefix should also
+/// contain the frame number, if saving multiple successive frames.**
+///
+/// * `folder_path` - Folder path to where to save the frame at.
+///
+/// ### Panics
+///
+/// * If `frame` is empty, or `bit_depth` is neither 8 nor 16 bits.
+///
+/// * On the exporting thread, under various circumstances, most commonly if the folder given by `folder_path`
+/// does not exist.
+pub fn export_frame_as_png<F: LeniaFloat>(
+    bit_depth: png::BitDepth,
+    frame: &ndarray::ArrayD<F>,
+    colormap: &rendering::Colormap,
+    prefix: &str,
+    folder_path: &str,
+) -> JoinHandle<()> {
+    export_frame_as_png_normalized(
+        bit_depth,
+        frame,
+        colormap,
+        Normalization::None,
+        prefix,
+        folder_path,
+    )
+}
+
+/// Same as `export_frame_as_png()`, but returns an error instead of panicking.
+///
+/// Bad parameters are returned as an error right away, while errors while writing the png-s are
+/// returned by the exporting thread, when joined.
+pub fn try_export_frame_as_png<F: LeniaFloat>(
+    bit_depth: png::BitDepth,
+    frame: &ndarray::ArrayD<F>,
+    colormap: &rendering::Colormap,
+    prefix: &str,
+    folder_path: &str,
+) -> Result<JoinHandle<Result<(), LeniaError>>, LeniaError> {
+    try_export_frame_as_png_normalized(
+        bit_depth,
+        frame,
+        colormap,
+        Normalization::None,
+        prefix,
+        folder_path,
+    )
+}
+
+/// Export a frame as a png or a bunch of png-s if multidimensional, normalizing the values.
+///
+/// Same as `export_frame_as_png()`, except that the frame is first normalized with `normalization`.
+/// With `Normalization::MinMax` the smallest and largest values of the whole frame are used, such that
+/// all of the png-s of a multidimensional frame share the same scale.
 ///
 /// ### Parameters
 ///
-/// * `bit_depth` - Controls whether to output as 8-bit grayscale or 16-bit grayscale png.
+/// * `bit_depth` - Controls whether to output as 8-bit or 16-bit png-s.
 ///
 /// * `frame` - Reference to the frame to be stored.
 ///
+/// * `colormap` - Colors of the values of the frame. Grayscale png-s are written with `Colormap::Grayscale`,
+/// and RGB png-s with any other colormap.
+///
+/// * `normalization` - The way the values are mapped onto `0.0..1.0`.
+///
 /// * `prefix` - Output file name. Numbers will be added after this string based on the 2d slice
 /// of the frame (if exporting a 3d or higher dimensionality frame). **This prefix should also
 /// contain the frame number, if saving multiple successive frames.**
@@ -293,15 +780,90 @@ pub fn load_from_png(file_path: &str) -> ndarray::Array2<f64> {
 ///
 /// ### Panics
 ///
-/// Under various circumstances, most commonly if the folder given by `folder_path` does not exist.
-pub fn export_frame_as_png(
+/// * If `frame` is empty, or `bit_depth` is neither 8 nor 16 bits.
+///
+/// * On the exporting thread, under various circumstances, most commonly if the folder given by `folder_path`
+/// does not exist.
+pub fn export_frame_as_png_normalized<F: LeniaFloat>(
     bit_depth: png::BitDepth,
-    frame: &ndarray::ArrayD<f64>,
+    frame: &ndarray::ArrayD<F>,
+    colormap: &rendering::Colormap,
+    normalization: Normalization<F>,
     prefix: &str,
     folder_path: &str,
 ) -> JoinHandle<()> {
+    let (path_base, data) =
+        match prepare_png_export(bit_depth, frame, normalization, prefix, folder_path) {
+            Ok(prepared) => prepared,
+            Err(error) => panic!("lenia_ca::export_frame_as_png() - {}", error),
+        };
+    let colormap = *colormap;
+    std::thread::spawn(move || {
+        let mut indexes: Vec<usize> = vec![0; data.shape().len()];
+        if let Err(error) =
+            nested_png_export(bit_depth, &colormap, path_base, &data, &mut indexes, 0)
+        {
+            panic!("lenia_ca::nested_png_export() - {}", error);
+        }
+    })
+}
+
+/// Same as `export_frame_as_png_normalized()`, but returns an error instead of panicking.
+///
+/// Bad parameters are returned as an error right away, while errors while writing the png-s are
+/// returned by the exporting thread, when joined.
+pub fn try_export_frame_as_png_normalized<F: LeniaFloat>(
+    bit_depth: png::BitDepth,
+    frame: &ndarray::ArrayD<F>,
+    colormap: &rendering::Colormap,
+    normalization: Normalization<F>,
+    prefix: &str,
+    folder_path: &str,
+) -> Result<JoinHandle<Result<(), LeniaError>>, LeniaError> {
+    let (path_base, data) =
+        prepare_png_export(bit_depth, frame, normalization, prefix, folder_path)?;
+    let colormap = *colormap;
+    Ok(std::thread::spawn(move || {
+        let mut indexes: Vec<usize> = vec![0; data.shape().len()];
+        nested_png_export(bit_depth, &colormap, path_base, &data, &mut indexes, 0)
+    }))
+}
+
+/// Same as `try_export_frame_as_png_normalized()`, but writes the png-s on the calling thread instead of spawning one.
+///
+/// Use this, or a `recorder::Recorder`, when exporting many successive frames, rather than spawning a thread
+/// for each of them.
+pub fn write_frame_as_png<F: LeniaFloat>(
+    bit_depth: png::BitDepth,
+    frame: &ndarray::ArrayD<F>,
+    colormap: &rendering::Colormap,
+    normalization: Normalization<F>,
+    prefix: &str,
+    folder_path: &str,
+) -> Result<(), LeniaError> {
+    let (path_base, data) =
+        prepare_png_export(bit_depth, frame, normalization, prefix, folder_path)?;
+    let mut indexes: Vec<usize> = vec![0; data.shape().len()];
+    nested_png_export(bit_depth, colormap, path_base, &data, &mut indexes, 0)
+}
+
+/// Checks the parameters of a png export, and returns the base of the file paths along with the normalized frame.
+fn prepare_png_export<F: LeniaFloat>(
+    bit_depth: png::BitDepth,
+    frame: &ndarray::ArrayD<F>,
+    normalization: Normalization<F>,
+    prefix: &str,
+    folder_path: &str,
+) -> Result<(String, ndarray::ArrayD<f64>), LeniaError> {
     if frame.shape().is_empty() {
-        panic!("lenia_ca::export_frame_as_png() - Can not export an empty frame!")
+        return Err(LeniaError::InvalidShape(
+            "Can not export an empty frame!".to_string(),
+        ));
+    }
+    if bit_depth != png::BitDepth::Eight && bit_depth != png::BitDepth::Sixteen {
+        return Err(LeniaError::InvalidParameter(
+            "Unsupported bit depth! Only 8 and 16 bit png-s can be exported.".to_string(),
+        ));
     }
 
     let path_base = format!(
@@ -311,48 +873,46 @@ pub fn export_frame_as_png(
         } else {
             folder_path
         },
-        if folder_path.chars().last().unwrap() != '/' && folder_path.chars().last().unwrap() != '\\'
-        {
+        if !folder_path.ends_with('/') && !folder_path.ends_with('\\') {
             &"/"
         } else {
             &""
         },
         prefix
     );
-    let data;
-    if frame.shape().len() == 1 {
-        data = frame
+    let mut frame = frame.clone();
+    normalization.normalize(&mut frame);
+    let data = if frame.shape().len() == 1 {
+        frame
             .to_shape((ndarray::IxDyn(&[frame.shape()[0], 1]), Order::RowMajor))
             .unwrap()
-            .mapv(|el| el.clone());
+            .mapv(|el| el.to_f64().unwrap())
     } else {
-        data = frame.clone();
-    }
-
-    std::thread::spawn(move || {
-        let mut indexes: Vec<usize> = vec![0; data.shape().len()];
-        nested_png_export(bit_depth, path_base, &data, &mut indexes, 0);
-    })
+        frame.mapv(|el| el.to_f64().unwrap())
+    };
+    Ok((path_base, data))
 }
 
 fn nested_png_export(
     bit_depth: png::BitDepth,
+    colormap: &rendering::Colormap,
     path: String,
     data: &ndarray::ArrayD<f64>,
     indexes: &mut Vec<usize>,
     current_axis: usize,
-) {
+) -> Result<(), LeniaError> {
     if current_axis == (indexes.len() - 2) {
         let file_path = format!("{}.png", &path);
-        println!("{}", &file_path);
-        let file = std::fs::File::create(file_path).unwrap();
+        let file = std::fs::File::create(file_path)?;
         let buf_writer = std::io::BufWriter::new(file);
         let width = data.shape()[data.shape().len() - 2];
         let height = data.shape()[data.shape().len() - 1];
         let mut encoder = png::Encoder::new(buf_writer, width as u32, height as u32);
+        let grayscale = *colormap == rendering::Colormap::Grayscale;
         let mut image_data: Vec<u8> = Vec::with_capacity(
             width
                 * height
+                * if grayscale { 1 } else { 3 }
                 * if bit_depth == png::BitDepth::Eight {
                     1
                 } else {
@@ -362,17 +922,17 @@ fn nested_png_export(
         let image_data_buffer = data
             .slice_each_axis(|a| {
                 if a.axis.index() == (indexes.len() - 2) || a.axis.index() == (indexes.len() - 1) {
-                    return Slice {
+                    Slice {
                         start: 0,
                         end: None,
                         step: 1,
-                    };
+                    }
                 } else {
-                    return Slice {
+                    Slice {
                         start: indexes[a.axis.index()] as isize,
                         end: Some((indexes[a.axis.index()] + 1) as isize),
                         step: 1,
-                    };
+                    }
                 }
             })
             .to_shape(((width * height), Order::ColumnMajor))
@@ -380,38 +940,46 @@ fn nested_png_export(
             .mapv(|el| el);
 
         for i in 0..(width * height) {
-            if bit_depth == png::BitDepth::Eight {
-                image_data.push((image_data_buffer[[i]] * 255.0) as u8);
-            } else if bit_depth == png::BitDepth::Sixteen {
+            if !grayscale {
+                for component in colormap.color(image_data_buffer[[i]]) {
+                    if bit_depth == png::BitDepth::Eight {
+                        image_data.push(component);
+                    } else {
+                        // Spreads the 8-bit component over the whole 16-bit range.
+                        image_data.push(component);
+                        image_data.push(component);
+                    }
+                }
+            } else if bit_depth == png::BitDepth::Eight {
+                image_data.push(rendering::level(image_data_buffer[[i]]));
+            } else {
                 let num = (image_data_buffer[[i]] * 65535.0) as u16;
                 image_data.push(num.get_high());
                 image_data.push(num.get_low());
-            } else {
-                panic!("lenia_ca::nested_png_export() - Unsupported bit depth!");
             }
         }
         encoder.set_depth(bit_depth);
-        encoder.set_color(png::ColorType::Grayscale);
-        let mut writer = encoder.write_header().unwrap();
-        let write_result = writer.write_image_data(&image_data);
-        match write_result {
-            Ok(_) => {}
-            Err(_) => {
-                panic!("lenia_ca::nested_png_export() - Failed to write png!");
-            }
-        }
+        encoder.set_color(if grayscale {
+            png::ColorType::Grayscale
+        } else {
+            png::ColorType::Rgb
+        });
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&image_data)?;
     } else {
         for i in 0..data.shape()[current_axis] {
             indexes[current_axis] = i;
             nested_png_export(
                 bit_depth,
+                colormap,
                 format!("{}_{}", &path, i),
                 data,
                 indexes,
                 current_axis + 1,
-            );
+            )?;
         }
     }
+    Ok(())
 }
 
 /// A Lenia simulation.
@@ -420,9 +988,48 @@ fn nested_png_export(
 /// The Simulator has all the needed methods to control a Lenia instance in normal operation.
 pub struct Simulator<L: Lenia> {
     sim: L,
+    // Simulated time, the sum of the `dt`s of all iterations.
+    time: L::Float,
+    adaptive_dt: Option<AdaptiveDt<L::Float>>,
+    // Number of iterations so far.
+    steps: u64,
+    // Generators of the kernels of the convolution channels, if they were made from `rules::Rules`.
+    kernel_rules: Vec<Option<rules::KernelRules>>,
 }
 
-impl<L: Lenia> Simulator<L> {
+/// A `Simulator` of a `Lenia` implementation chosen at runtime.
+///
+/// ```
+/// # use road_to_lenia::lenias::{ExpandedLenia, StandardLenia};
+/// # use road_to_lenia::{DynSimulator, Lenia, NewLenia, Simulator};
+/// # let (expanded, channel_shape) = (true, [64, 64]);
+/// let lenia: Box<dyn Lenia<Float = f64>> = if expanded {
+///     Box::new(ExpandedLenia::new(&channel_shape))
+/// } else {
+///     Box::new(StandardLenia::new(&channel_shape))
+/// };
+/// let mut simulator: DynSimulator = Simulator::from_lenia(lenia);
+/// ```
+pub type DynSimulator<F = f64> = Simulator<Box<dyn Lenia<Float = F>>>;
+
+/// Bounds of the adaptive integration step of a `Simulator`.
+#[derive(Clone, Copy, Debug)]
+struct AdaptiveDt<F: LeniaFloat> {
+    tolerance: F,
+    min_dt: F,
+    max_dt: F,
+}
+
+/// Fraction of the optimal integration step that the adaptive integration step is set to, leaving some headroom.
+const ADAPTIVE_DT_SAFETY: f64 = 0.9;
+
+/// Largest factor by which the adaptive integration step may grow after a single iteration.
+const ADAPTIVE_DT_MAX_GROWTH: f64 = 2.0;
+
+/// Smallest factor by which the adaptive integration step may shrink after a single rejected step.
+const ADAPTIVE_DT_MIN_SHRINK: f64 = 0.2;
+
+impl<L: NewLenia> Simulator<L> {
     /// Initialize a Lenia simulator.
     ///
     /// Barring wanting to change the type of the `Lenia` instance used by the `Simulator`,
@@ -436,18 +1043,28 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// ### Panics
     ///
-    /// If any axis length in `channel_shape`is `0`.
+    /// * If any axis length in `channel_shape`is `0`.
+    ///
+    /// * If the `Lenia` instance can not be made with `channel_shape`, see `NewLenia::try_new()`.
     pub fn new(channel_shape: &[usize]) -> Self {
-        for (i, dim) in channel_shape.iter().enumerate() {
-            if *dim == 0 {
-                panic!("Simulator::new() - Axis {} of the provided shape has a length of 0! Each axis must have a length of at least 1.", i);
-            }
-        }
-        Simulator {
-            sim: L::new(channel_shape),
+        match Self::try_new(channel_shape) {
+            Ok(simulator) => simulator,
+            Err(error) => panic!("Simulator::new() - {}", error),
         }
     }
 
+    /// Same as `new()`, but returns an error instead of panicking.
+    pub fn try_new(channel_shape: &[usize]) -> Result<Self, LeniaError> {
+        check_shape(channel_shape)?;
+        Ok(Simulator {
+            sim: L::try_new(channel_shape)?,
+            time: L::Float::zero(),
+            adaptive_dt: None,
+            steps: 0,
+            kernel_rules: Vec::new(),
+        })
+    }
+
     /// Re-initialize a Lenia simulator.
     ///
     /// Re-initializes the `Lenia` instance, losing **all** of the previous changes, such as
@@ -464,21 +1081,64 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// ### Panics
     ///
-    /// If any axis length in `channel_shape`is `0`.
+    /// * If any axis length in `channel_shape`is `0`.
+    ///
+    /// * If the `Lenia` instance can not be made with `channel_shape`, see `NewLenia::try_new()`.
     pub fn remake(&mut self, channel_shape: &[usize]) {
-        for (i, dim) in channel_shape.iter().enumerate() {
-            if *dim == 0 {
-                panic!("Simulator::new() - Axis {} of the provided shape has a length of 0! Each axis must have a length of at least 1.", i);
-            }
+        if let Err(error) = self.try_remake(channel_shape) {
+            panic!("Simulator::remake() - {}", error);
         }
-        self.sim = L::new(channel_shape);
+    }
+
+    /// Same as `remake()`, but returns an error instead of panicking, leaving the `Simulator` unchanged.
+    pub fn try_remake(&mut self, channel_shape: &[usize]) -> Result<(), LeniaError> {
+        check_shape(channel_shape)?;
+        self.sim = L::try_new(channel_shape)?;
+        self.time = L::Float::zero();
+        self.adaptive_dt = None;
+        self.steps = 0;
+        self.kernel_rules.clear();
+        Ok(())
+    }
+}
+
+impl<L: Lenia> Simulator<L> {
+    /// Wrap an already made `Lenia` instance in a simulator.
+    ///
+    /// Allows the `Lenia` implementation to be chosen at runtime, as a `Box<dyn Lenia>`, see `DynSimulator`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `lenia` - The `Lenia` instance to simulate.
+    pub fn from_lenia(lenia: L) -> Self {
+        Simulator {
+            sim: lenia,
+            time: L::Float::zero(),
+            adaptive_dt: None,
+            steps: 0,
+            kernel_rules: Vec::new(),
+        }
+    }
+
+    /// Get a reference to the simulated `Lenia` instance.
+    pub fn lenia(&self) -> &L {
+        &self.sim
+    }
+
+    /// Get a mutable reference to the simulated `Lenia` instance.
+    ///
+    /// Meant for the settings specific to a `Lenia` implementation, such as `FlowLenia::set_spread()`.
+    /// Channels, kernels, growth functions and the integration step should be changed through the `Simulator`,
+    /// which keeps track of how they were made and of the simulated time.
+    pub fn lenia_mut(&mut self) -> &mut L {
+        &mut self.sim
     }
 
     /// Set the number of channels in the `Lenia` instance.
     ///
-    /// **In case the number of channels
-    /// is less than the current number of channels, it is up to the user to make sure that
-    /// no convolution channel tries to use a dropped channel as its source!**
+    /// In case the number of channels is less than the current number of channels, no convolution
+    /// channel may use a dropped channel as its source. Change their sources with `set_convolution_channel_source()`
+    /// or drop them with `set_convolution_channels()` first.
     ///
     /// All values in newly created channels will be set to `0.0`.
     ///
@@ -491,15 +1151,28 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// ### Panics
     ///
-    /// If `channels` is `0`.
+    /// * If `channels` is `0`.
+    ///
+    /// * If a convolution channel uses a dropped channel as its source.
+    ///
+    /// * If the `Lenia` instance does not support changing the number of channels.
     pub fn set_channels(&mut self, channels: usize) {
+        if let Err(error) = self.try_set_channels(channels) {
+            panic!("Simulator::set_channels: {}", error);
+        }
+    }
+
+    /// Same as `set_channels()`, but returns an error instead of panicking.
+    pub fn try_set_channels(&mut self, channels: usize) -> Result<(), LeniaError> {
         if channels == 0 {
-            panic!("Simulator::set_channels: Attempting to set the number of channels to 0. This is not allowed.");
+            return Err(LeniaError::InvalidParameter(
+                "Attempting to set the number of channels to 0. This is not allowed.".to_string(),
+            ));
         }
         if channels == self.sim.channels() {
-            return;
+            return Ok(());
         }
-        self.sim.set_channels(channels);
+        self.sim.set_channels(channels)
     }
 
     /// Set the number of convolution channels in the `Lenia` instance.
@@ -519,15 +1192,32 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// ### Panics
     ///
-    /// If `convolution_channels` is `0`.
+    /// * If `convolution_channels` is `0`.
+    ///
+    /// * If the `Lenia` instance does not support changing the number of convolution channels.
     pub fn set_convolution_channels(&mut self, convolution_channels: usize) {
+        if let Err(error) = self.try_set_convolution_channels(convolution_channels) {
+            panic!("Simulator::set_convolution_channels: {}", error);
+        }
+    }
+
+    /// Same as `set_convolution_channels()`, but returns an error instead of panicking.
+    pub fn try_set_convolution_channels(
+        &mut self,
+        convolution_channels: usize,
+    ) -> Result<(), LeniaError> {
         if convolution_channels == 0 {
-            panic!("Simulator::set_convolution_channels: Attempting to set the number of convolution channels to 0. This is not allowed.");
+            return Err(LeniaError::InvalidParameter(
+                "Attempting to set the number of convolution channels to 0. This is not allowed."
+                    .to_string(),
+            ));
         }
         if convolution_channels == self.convolution_channels() {
-            return;
+            return Ok(());
         }
-        self.sim.set_conv_channels(convolution_channels);
+        self.sim.set_conv_channels(convolution_channels)?;
+        self.kernel_rules.truncate(convolution_channels);
+        Ok(())
     }
 
     /// Set the source channel a given convolution channel should act on.
@@ -544,26 +1234,37 @@ impl<L: Lenia> Simulator<L> {
     /// * If the specified `convolution_channel` does not exist.
     ///
     /// * If the specified `source_channel` does not exist.
+    ///
+    /// * If the `Lenia` instance does not support changing the source channels.
     pub fn set_convolution_channel_source(
         &mut self,
         convolution_channel: usize,
         source_channel: usize,
     ) {
-        if convolution_channel >= self.sim.conv_channels() {
-            panic!("Simulator::set_convolution_channel_source: Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, self.sim.conv_channels());
-        }
-        if source_channel >= self.sim.channels() {
-            panic!("Simulator::set_convolution_channel_source: Specified channel (index {}) does not exist. Current number of channels: {}.", source_channel, self.sim.channels());
+        if let Err(error) =
+            self.try_set_convolution_channel_source(convolution_channel, source_channel)
+        {
+            panic!("Simulator::set_convolution_channel_source: {}", error);
         }
+    }
+
+    /// Same as `set_convolution_channel_source()`, but returns an error instead of panicking.
+    pub fn try_set_convolution_channel_source(
+        &mut self,
+        convolution_channel: usize,
+        source_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        self.check_channel(source_channel)?;
         self.sim
-            .set_source_channel(convolution_channel, source_channel);
+            .set_source_channel(convolution_channel, source_channel)
     }
 
     /// Set and make the kernel of the specified convolution channel.
     ///
     /// ### Parameters
     ///
-    /// * `kernel` - n-dimensional array (`ndarray::ArrayD<f64>`), where the number of
+    /// * `kernel` - n-dimensional array (`ndarray::ArrayD`), where the number of
     /// dimensions / axes must match the number of dimensions / axes of the channels of the
     /// `Lenia` instance.
     ///
@@ -571,44 +1272,345 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// ### Panics
     ///
-    /// If the specified `convolution_channel` does not exist.
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the dimensionality of the kernel is not the same as the channels'
     ///
-    /// If the dimensionality of the kernel is not the same as the channels'
-    pub fn set_kernel(&mut self, kernel: ndarray::ArrayD<f64>, convolution_channel: usize) {
-        if convolution_channel >= self.sim.conv_channels() {
-            panic!("Simulator::set_kernel: Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, self.sim.conv_channels());
+    /// * If the kernel is larger than the channels along any axis.
+    ///
+    /// * If the elements of the kernel sum up to `0.0` or to a non-finite value.
+    pub fn set_kernel(&mut self, kernel: ndarray::ArrayD<L::Float>, convolution_channel: usize) {
+        if let Err(error) = self.try_set_kernel(kernel, convolution_channel) {
+            panic!("Simulator::set_kernel: {}", error);
         }
-        if kernel.shape().len() != self.sim.shape().len() {
-            panic!("Simulator::set_kernel: Number of kernel dimensionality ({}) does not agree with channels' dimensionality ({}).", kernel.shape().len(), self.sim.shape().len());
+    }
+
+    /// Same as `set_kernel()`, but returns an error instead of panicking.
+    pub fn try_set_kernel(
+        &mut self,
+        kernel: ndarray::ArrayD<L::Float>,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        check_kernel_shape(kernel.shape(), self.sim.shape())?;
+        check_kernel_sum(&kernel)?;
+        self.sim.set_kernel(kernel, convolution_channel)?;
+        if let Some(rules) = self.kernel_rules.get_mut(convolution_channel) {
+            *rules = None;
         }
-        self.sim.set_kernel(kernel, convolution_channel);
+        Ok(())
     }
 
-    /// Set the growth function and its parameters of the specified convolution channel.
+    /// Set the growth function of the specified convolution channel.
     ///
-    /// ### Parameters
+    /// Any `GrowthFunction` can be used, such as the typed growth functions in the `growth_functions`
+    /// module, or a closure taking and returning a single value.
     ///
-    /// * `f` - Growth function to use.
+    /// ```
+    /// # use road_to_lenia::{growth_functions, lenias::ExpandedLenia, Simulator};
+    /// # let mut simulator = Simulator::<ExpandedLenia>::new(&[64, 64]);
+    /// # simulator.set_convolution_channels(2);
+    /// simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.017), 0);
+    /// simulator.set_growth_function(|x: f64| 2.0 * x - 1.0, 1);
+    /// ```
     ///
-    /// * `growth_parameters` - The parameters passed to the growth function.
+    /// ### Parameters
+    ///
+    /// * `growth_function` - Growth function to use, along with its parameters.
     ///
-    /// * `convolution_channel` - The convoltution channel to which the new growth function and
-    /// parameters are to be assigned.
+    /// * `convolution_channel` - The convoltution channel to which the new growth function is to be assigned.
     ///
     /// ### Panics
     ///
-    /// If the specified `convolution_channel` does not exist.
-    pub fn set_growth_function(
-        &mut self,
-        f: fn(f64, &[f64]) -> f64,
-        growth_parameters: Vec<f64>,
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the parameters of `growth_function` are invalid.
+    pub fn set_growth_function<G: GrowthFunction<L::Float> + 'static>(
+        &mut self,
+        growth_function: G,
+        convolution_channel: usize,
+    ) {
+        if let Err(error) = self.try_set_growth_function(growth_function, convolution_channel) {
+            panic!("Simulator::set_growth_function: {}", error);
+        }
+    }
+
+    /// Same as `set_growth_function()`, but returns an error instead of panicking.
+    pub fn try_set_growth_function<G: GrowthFunction<L::Float> + 'static>(
+        &mut self,
+        growth_function: G,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.try_set_shared_growth_function(Arc::new(growth_function), convolution_channel)
+    }
+
+    /// Same as `try_set_growth_function()`, for a growth function that is already shared.
+    fn try_set_shared_growth_function(
+        &mut self,
+        growth_function: Arc<dyn GrowthFunction<L::Float>>,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        check_growth_function(&*growth_function)?;
+        self.sim.set_growth(growth_function, convolution_channel)
+    }
+
+    /// Make a parameter of the growth function of the specified convolution channel vary across space.
+    ///
+    /// At each point of the convolution channel the growth function uses the value of `map` at that point
+    /// in place of its own value of the parameter, e.g. a `mu` map and a `sigma` map for
+    /// `growth_functions::StandardLenia` to give different regions different rules.
+    ///
+    /// Parameter maps are removed whenever the growth function of the convolution channel is set.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channel` - The convolution channel whose growth function parameter varies.
+    ///
+    /// * `parameter` - Name of the parameter, as in the growth function's `parameter_names()`.
+    ///
+    /// * `map` - Value of the parameter at each point. Must have the same shape as the channels.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the growth function has no parameter called `parameter`.
+    ///
+    /// * If the shape of `map` is not the same as the shape of the channels.
+    pub fn set_growth_parameter_map(
+        &mut self,
         convolution_channel: usize,
+        parameter: &str,
+        map: ndarray::ArrayD<L::Float>,
     ) {
-        if convolution_channel >= self.sim.conv_channels() {
-            panic!("Simulator::set_growth_function: Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, self.sim.conv_channels());
+        if let Err(error) = self.try_set_growth_parameter_map(convolution_channel, parameter, map) {
+            panic!("Simulator::set_growth_parameter_map: {}", error);
+        }
+    }
+
+    /// Same as `set_growth_parameter_map()`, but returns an error instead of panicking.
+    pub fn try_set_growth_parameter_map(
+        &mut self,
+        convolution_channel: usize,
+        parameter: &str,
+        map: ndarray::ArrayD<L::Float>,
+    ) -> Result<(), LeniaError> {
+        let index = self.growth_parameter_index(convolution_channel, parameter)?;
+        if map.shape() != self.sim.shape() {
+            return Err(LeniaError::ShapeMismatch {
+                expected: self.sim.shape().to_vec(),
+                found: map.shape().to_vec(),
+            });
         }
         self.sim
-            .set_growth(f, growth_parameters, convolution_channel);
+            .set_parameter_map(Some(map), index, convolution_channel)
+    }
+
+    /// Make a parameter of the growth function of the specified convolution channel uniform again,
+    /// removing its parameter map.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channel` - The convolution channel whose growth function parameter is made uniform.
+    ///
+    /// * `parameter` - Name of the parameter, as in the growth function's `parameter_names()`.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the growth function has no parameter called `parameter`.
+    pub fn clear_growth_parameter_map(&mut self, convolution_channel: usize, parameter: &str) {
+        if let Err(error) = self.try_clear_growth_parameter_map(convolution_channel, parameter) {
+            panic!("Simulator::clear_growth_parameter_map: {}", error);
+        }
+    }
+
+    /// Same as `clear_growth_parameter_map()`, but returns an error instead of panicking.
+    pub fn try_clear_growth_parameter_map(
+        &mut self,
+        convolution_channel: usize,
+        parameter: &str,
+    ) -> Result<(), LeniaError> {
+        let index = self.growth_parameter_index(convolution_channel, parameter)?;
+        self.sim.set_parameter_map(None, index, convolution_channel)
+    }
+
+    /// Index of the growth function parameter called `parameter` of the specified convolution channel.
+    fn growth_parameter_index(
+        &self,
+        convolution_channel: usize,
+        parameter: &str,
+    ) -> Result<usize, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        let growth_function = self.sim.growth_function(convolution_channel);
+        let names = growth_function.parameter_names();
+        match names.iter().position(|name| name == parameter) {
+            Some(index) => Ok(index),
+            None => Err(LeniaError::UnknownParameter {
+                growth_function: growth_function.name().to_string(),
+                parameter: parameter.to_string(),
+                available: names,
+            }),
+        }
+    }
+
+    /// Set the way the convolution of the specified convolution channel is computed.
+    ///
+    /// By default `ConvolutionBackend::Automatic` is used, which picks direct convolution
+    /// for small kernels and fft-based convolution for everything else.
+    ///
+    /// ### Parameters
+    ///
+    /// * `backend` - Convolution method to use.
+    ///
+    /// * `convolution_channel` - The convolution channel whose convolution method is set.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn set_convolution_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        convolution_channel: usize,
+    ) {
+        if let Err(error) = self.try_set_convolution_backend(backend, convolution_channel) {
+            panic!("Simulator::set_convolution_backend: {}", error);
+        }
+    }
+
+    /// Same as `set_convolution_backend()`, but returns an error instead of panicking.
+    pub fn try_set_convolution_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        convolution_channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        self.sim.set_backend(backend, convolution_channel)
+    }
+
+    /// Set the boundary condition of each axis of the channels.
+    ///
+    /// By default every axis is `BoundaryCondition::Periodic`, making the world a torus.
+    /// Non-periodic axes get padded internally before fft-based convolution, making the
+    /// simulation slower the larger the kernels on that axis are.
+    ///
+    /// ### Parameters
+    ///
+    /// * `boundaries` - Boundary condition for each axis of the channels.
+    ///
+    /// ### Panics
+    ///
+    /// If the length of `boundaries` does not match the dimensionality of the channels.
+    pub fn set_boundary_conditions(&mut self, boundaries: &[BoundaryCondition]) {
+        if let Err(error) = self.try_set_boundary_conditions(boundaries) {
+            panic!("Simulator::set_boundary_conditions: {}", error);
+        }
+    }
+
+    /// Same as `set_boundary_conditions()`, but returns an error instead of panicking.
+    pub fn try_set_boundary_conditions(
+        &mut self,
+        boundaries: &[BoundaryCondition],
+    ) -> Result<(), LeniaError> {
+        self.sim.set_boundaries(boundaries)
+    }
+
+    /// Set the scheme used to advance the channels by one integration step.
+    ///
+    /// By default `Integrator::Euler` is used. Higher-order schemes recompute the convolutions
+    /// once per stage, see `Integrator` for how each of them clamps the channels.
+    ///
+    /// ### Parameters
+    ///
+    /// * `integrator` - The integration scheme for the `Lenia` instance to use.
+    ///
+    /// ### Panics
+    ///
+    /// If the `Lenia` instance does not support changing the integrator.
+    pub fn set_integrator(&mut self, integrator: Integrator) {
+        if let Err(error) = self.try_set_integrator(integrator) {
+            panic!("Simulator::set_integrator: {}", error);
+        }
+    }
+
+    /// Same as `set_integrator()`, but returns an error instead of panicking.
+    pub fn try_set_integrator(&mut self, integrator: Integrator) -> Result<(), LeniaError> {
+        self.sim.set_integrator(integrator)
+    }
+
+    /// Set the way the weighted sum of the convolution channels updates a channel.
+    ///
+    /// By default every channel is `UpdateMode::Additive`, adding the weighted sum times `dt` onto the channel
+    /// and clamping the result. With `UpdateMode::Asymptotic` the weighted sum is a target value that the
+    /// channel approaches, see `UpdateMode` for details.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - Index of the channel to change the update mode of.
+    ///
+    /// * `mode` - The new update mode of the channel.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `channel` does not exist.
+    ///
+    /// * If the `Lenia` instance does not support changing the update mode.
+    pub fn set_update_mode(&mut self, channel: usize, mode: UpdateMode) {
+        if let Err(error) = self.try_set_update_mode(channel, mode) {
+            panic!("Simulator::set_update_mode: {}", error);
+        }
+    }
+
+    /// Same as `set_update_mode()`, but returns an error instead of panicking.
+    pub fn try_set_update_mode(
+        &mut self,
+        channel: usize,
+        mode: UpdateMode,
+    ) -> Result<(), LeniaError> {
+        self.check_channel(channel)?;
+        self.sim.set_update_mode(mode, channel)
+    }
+
+    /// Set the range of values that a channel is kept within.
+    ///
+    /// By default every channel is clamped to `0.0..1.0`. Other ranges, such as negative or unbounded ones
+    /// (see `ValueRange::unbounded()`), allow for signed fields like activators and inhibitors.
+    /// Soft saturations keep the channel within the range smoothly, see `Saturation`.
+    ///
+    /// Only channels with `UpdateMode::Additive` are kept within the range.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - Index of the channel to change the range of.
+    ///
+    /// * `range` - The new range of values of the channel.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `channel` does not exist.
+    ///
+    /// * If the lower bound of `range` is not less than its upper bound.
+    ///
+    /// * If `range` uses a soft saturation, but either of its bounds is infinite.
+    ///
+    /// * If the `Lenia` instance does not support changing the range of values.
+    pub fn set_value_range(&mut self, channel: usize, range: ValueRange<L::Float>) {
+        if let Err(error) = self.try_set_value_range(channel, range) {
+            panic!("Simulator::set_value_range: {}", error);
+        }
+    }
+
+    /// Same as `set_value_range()`, but returns an error instead of panicking.
+    pub fn try_set_value_range(
+        &mut self,
+        channel: usize,
+        range: ValueRange<L::Float>,
+    ) -> Result<(), LeniaError> {
+        self.check_channel(channel)?;
+        check_value_range(&range)?;
+        self.sim.set_value_range(range, channel)
     }
 
     /// Set the convolution channel weights for a specific channel.
@@ -625,8 +1627,26 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// * `weights` - The weights to assign. Index in the array corresponds to
     /// the index of the convoution channel.
-    pub fn set_weights(&mut self, channel: usize, weights: &[f64]) {
-        self.sim.set_weights(weights, channel);
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `channel` does not exist.
+    ///
+    /// * If the `Lenia` instance does not support changing the weights.
+    pub fn set_weights(&mut self, channel: usize, weights: &[L::Float]) {
+        if let Err(error) = self.try_set_weights(channel, weights) {
+            panic!("Simulator::set_weights: {}", error);
+        }
+    }
+
+    /// Same as `set_weights()`, but returns an error instead of panicking.
+    pub fn try_set_weights(
+        &mut self,
+        channel: usize,
+        weights: &[L::Float],
+    ) -> Result<(), LeniaError> {
+        self.check_channel(channel)?;
+        self.sim.set_weights(weights, channel)
     }
 
     /// Set the integration step (a.k.a. timestep) parameter `dt` of the `Lenia` instance.
@@ -634,15 +1654,252 @@ impl<L: Lenia> Simulator<L> {
     /// ### Parameters
     ///
     /// * `dt` - The new dt value for the `Lenia` instance to use.
-    pub fn set_dt(&mut self, dt: f64) {
+    ///
+    /// ### Panics
+    ///
+    /// If `dt` is not positive and finite.
+    pub fn set_dt(&mut self, dt: L::Float) {
+        if let Err(error) = self.try_set_dt(dt) {
+            panic!("Simulator::set_dt: {}", error);
+        }
+    }
+
+    /// Same as `set_dt()`, but returns an error instead of panicking.
+    pub fn try_set_dt(&mut self, dt: L::Float) -> Result<(), LeniaError> {
+        check_dt(dt)?;
         self.sim.set_dt(dt);
+        Ok(())
+    }
+
+    /// Let the `Simulator` choose the integration step `dt` of every iteration.
+    ///
+    /// Each iteration is computed both as a single step of `dt` and as two steps of `dt / 2` (step doubling).
+    /// The difference between the two gives an estimate of the local error, the largest absolute error of
+    /// any value in any channel. If the error exceeds `tolerance` the iteration is retried with a smaller `dt`,
+    /// otherwise the result of the two half-steps is kept and `dt` is adjusted for the next iteration.
+    /// Steps at `min_dt` are always accepted, even if they produce non-finite values.
+    ///
+    /// An adaptive iteration costs 3 fixed iterations, or more if steps get rejected.
+    /// The current `dt` is used as the first attempted step, see `set_dt()`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `tolerance` - Largest allowed local error per iteration.
+    ///
+    /// * `min_dt` - Smallest allowed integration step.
+    ///
+    /// * `max_dt` - Largest allowed integration step.
+    ///
+    /// ### Panics
+    ///
+    /// * If `tolerance` or `min_dt` is not positive.
+    ///
+    /// * If `max_dt` is less than `min_dt`.
+    ///
+    /// * If stochastic terms are enabled, see `set_stochasticity()`.
+    pub fn set_adaptive_dt(&mut self, tolerance: L::Float, min_dt: L::Float, max_dt: L::Float) {
+        if let Err(error) = self.try_set_adaptive_dt(tolerance, min_dt, max_dt) {
+            panic!("Simulator::set_adaptive_dt: {}", error);
+        }
+    }
+
+    /// Same as `set_adaptive_dt()`, but returns an error instead of panicking.
+    pub fn try_set_adaptive_dt(
+        &mut self,
+        tolerance: L::Float,
+        min_dt: L::Float,
+        max_dt: L::Float,
+    ) -> Result<(), LeniaError> {
+        if tolerance <= L::Float::zero() {
+            return Err(LeniaError::InvalidParameter(
+                "The tolerance must be positive.".to_string(),
+            ));
+        }
+        if min_dt <= L::Float::zero() {
+            return Err(LeniaError::InvalidParameter(
+                "The minimum dt must be positive.".to_string(),
+            ));
+        }
+        if max_dt < min_dt {
+            return Err(LeniaError::InvalidParameter(format!(
+                "The maximum dt ({}) is less than the minimum dt ({}).",
+                max_dt, min_dt
+            )));
+        }
+        if self.sim.stochasticity().is_some() {
+            return Err(LeniaError::Unsupported("Adaptive integration steps are not available with stochastic terms, as the error estimate would measure the noise! Disable the stochastic terms first.".to_string()));
+        }
+        self.adaptive_dt = Some(AdaptiveDt {
+            tolerance,
+            min_dt,
+            max_dt,
+        });
+        Ok(())
+    }
+
+    /// Go back to using the fixed integration step `dt`, starting from the last adaptively chosen `dt`.
+    pub fn disable_adaptive_dt(&mut self) {
+        self.adaptive_dt = None;
+    }
+
+    /// Add stochastic terms to the update step, to see how robust the simulation is to perturbation.
+    ///
+    /// Every iteration gaussian noise is added onto the deltas of every cell, scaled such that it changes a channel
+    /// by a standard deviation of `sqrt(dt) * noise` (Euler–Maruyama), so the spread of the noise over a stretch of
+    /// simulated time does not depend on `dt`. Then only a random fraction
+    /// `update_probability` of the cells is updated (asynchronous update), the rest keep their previous values.
+    /// All integrator stages of an iteration see the same noise.
+    ///
+    /// Runs are reproducible, as both terms are drawn from a random number generator seeded with `seed`.
+    /// Stochastic terms make the error estimate of `set_adaptive_dt()` meaningless, so they require a fixed `dt`.
+    ///
+    /// Only `ExpandedLenia` supports stochastic terms.
+    ///
+    /// ### Parameters
+    ///
+    /// * `noise` - Standard deviation of the noise over a unit of simulated time, `0.0` for no noise.
+    ///
+    /// * `update_probability` - Probability of a cell being updated in an iteration, `1.0` for every cell.
+    ///
+    /// * `seed` - Seed of the random number generator.
+    ///
+    /// ### Panics
+    ///
+    /// * If `noise` is negative.
+    ///
+    /// * If `update_probability` is not in range `(0.0..1.0]`.
+    ///
+    /// * If the integration step is chosen adaptively, see `set_adaptive_dt()`.
+    ///
+    /// * If the `Lenia` instance does not support stochastic terms.
+    pub fn set_stochasticity(&mut self, noise: L::Float, update_probability: L::Float, seed: u64) {
+        if let Err(error) = self.try_set_stochasticity(noise, update_probability, seed) {
+            panic!("Simulator::set_stochasticity: {}", error);
+        }
+    }
+
+    /// Same as `set_stochasticity()`, but returns an error instead of panicking.
+    pub fn try_set_stochasticity(
+        &mut self,
+        noise: L::Float,
+        update_probability: L::Float,
+        seed: u64,
+    ) -> Result<(), LeniaError> {
+        if noise < L::Float::zero() {
+            return Err(LeniaError::InvalidParameter(
+                "The noise must not be negative.".to_string(),
+            ));
+        }
+        if update_probability <= L::Float::zero() || update_probability > L::Float::one() {
+            return Err(LeniaError::InvalidParameter(format!(
+                "The update probability ({}) must be in range (0.0..1.0].",
+                update_probability
+            )));
+        }
+        if self.adaptive_dt.is_some() {
+            return Err(LeniaError::Unsupported("Stochastic terms are not available with adaptive integration steps, as the error estimate would measure the noise! Disable the adaptive integration step first.".to_string()));
+        }
+        self.sim.set_stochasticity(Some(Stochasticity {
+            noise,
+            update_probability,
+            seed,
+        }))
+    }
+
+    /// Go back to deterministic iterations, removing the stochastic terms of the update step.
+    pub fn disable_stochasticity(&mut self) {
+        // Deterministic iterations are supported by every `Lenia` instance.
+        let _ = self.sim.set_stochasticity(None);
+    }
+
+    /// Set the simulated time, such as when restarting a simulation from a saved state.
+    pub fn set_time(&mut self, time: L::Float) {
+        self.time = time;
     }
 
     /// Performs a single iteration of the `Lenia` instance.
     ///
-    /// Channels are updated with the resulting new state of the simulation.
+    /// Channels are updated with the resulting new state of the simulation, and the simulated time
+    /// is advanced by the integration step of the iteration.
     pub fn iterate(&mut self) {
-        self.sim.iterate();
+        self.steps += 1;
+        match self.adaptive_dt {
+            None => {
+                self.sim.iterate();
+                self.time += self.sim.dt();
+            }
+            Some(adaptive_dt) => self.iterate_adaptive(adaptive_dt),
+        }
+    }
+
+    /// Performs a single iteration with an integration step chosen by step doubling.
+    fn iterate_adaptive(&mut self, adaptive_dt: AdaptiveDt<L::Float>) {
+        let initial: Vec<ndarray::ArrayD<L::Float>> = (0..self.sim.channels())
+            .map(|channel| self.sim.get_channel_as_ref(channel).clone())
+            .collect();
+        let order = self.sim.integrator().order();
+        // The two half-steps are more accurate than the single step by a factor of 2^order,
+        // the error of the half-steps is thus the difference divided by 2^order - 1.
+        let error_scale = float::<L::Float>(2.0).powi(order) - L::Float::one();
+        let half: L::Float = float(0.5);
+        loop {
+            let dt = num_traits::clamp(self.sim.dt(), adaptive_dt.min_dt, adaptive_dt.max_dt);
+            self.sim.set_dt(dt);
+            self.sim.iterate();
+            let single: Vec<ndarray::ArrayD<L::Float>> = (0..self.sim.channels())
+                .map(|channel| self.sim.get_channel_as_ref(channel).clone())
+                .collect();
+            self.restore_channels(&initial);
+            self.sim.set_dt(dt * half);
+            self.sim.iterate();
+            self.sim.iterate();
+
+            let mut error = L::Float::zero();
+            for (channel, single) in single.iter().enumerate() {
+                for (a, b) in self.sim.get_channel_as_ref(channel).iter().zip(single) {
+                    let difference = (*a - *b).abs();
+                    // `max()` skips NaN, which must count as an error however.
+                    error = if difference.is_nan() {
+                        L::Float::infinity()
+                    } else {
+                        error.max(difference)
+                    };
+                }
+            }
+            error /= error_scale;
+
+            let factor = if !error.is_finite() {
+                // A blown up step says nothing about the optimal step, shrink as fast as allowed.
+                float(ADAPTIVE_DT_MIN_SHRINK)
+            } else if error == L::Float::zero() {
+                float(ADAPTIVE_DT_MAX_GROWTH)
+            } else {
+                num_traits::clamp(
+                    float::<L::Float>(ADAPTIVE_DT_SAFETY)
+                        * (adaptive_dt.tolerance / error)
+                            .powf(L::Float::one() / float((order + 1) as f64)),
+                    float(ADAPTIVE_DT_MIN_SHRINK),
+                    float(ADAPTIVE_DT_MAX_GROWTH),
+                )
+            };
+            self.sim.set_dt(num_traits::clamp(
+                dt * factor,
+                adaptive_dt.min_dt,
+                adaptive_dt.max_dt,
+            ));
+            if error <= adaptive_dt.tolerance || dt <= adaptive_dt.min_dt {
+                self.time += dt;
+                return;
+            }
+            self.restore_channels(&initial);
+        }
+    }
+
+    /// Overwrites the data of all channels.
+    fn restore_channels(&mut self, channels: &[ndarray::ArrayD<L::Float>]) {
+        for (channel, data) in channels.iter().enumerate() {
+            self.sim.get_channel_as_mut_ref(channel).assign(data);
+        }
     }
 
     /// Fills a channel with user data.
@@ -651,22 +1908,40 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// ### Parameters
     ///
-    /// * `data` - Reference to the n-dimensional array (`ndarray::ArrayD`) of `f64` values
+    /// * `data` - Reference to the n-dimensional array (`ndarray::ArrayD`) of values
     /// from which to fill the channel's data.
     ///
     /// * `channel` - Index of the channel to fill.
     ///
     /// ### Panics
     ///
-    /// If the specified `channel` does not exist.
-    pub fn fill_channel(&mut self, data: &ndarray::ArrayD<f64>, channel: usize) {
-        if channel >= self.sim.channels() {
-            panic!("Simulator::fill_channel: Specified channel (index {}) does not exist. Current number of channels: {}.", channel, self.sim.channels());
+    /// * If the specified `channel` does not exist.
+    ///
+    /// * If the shape of `data` is not the same as the shape of the channels.
+    pub fn fill_channel(&mut self, data: &ndarray::ArrayD<L::Float>, channel: usize) {
+        if let Err(error) = self.try_fill_channel(data, channel) {
+            panic!("Simulator::fill_channel: {}", error);
         }
+    }
+
+    /// Same as `fill_channel()`, but returns an error instead of panicking.
+    pub fn try_fill_channel(
+        &mut self,
+        data: &ndarray::ArrayD<L::Float>,
+        channel: usize,
+    ) -> Result<(), LeniaError> {
+        self.check_channel(channel)?;
         let channel_data = self.sim.get_channel_as_mut_ref(channel);
+        if data.broadcast(channel_data.raw_dim()).is_none() {
+            return Err(LeniaError::ShapeMismatch {
+                expected: channel_data.shape().to_vec(),
+                found: data.shape().to_vec(),
+            });
+        }
         channel_data.zip_mut_with(data, |a, b| {
             *a = *b;
         });
+        Ok(())
     }
 
     /// Retrieve a referenced to the specified channel's data.
@@ -678,19 +1953,37 @@ impl<L: Lenia> Simulator<L> {
     /// ### Panics
     ///
     /// If the specified `channel` does not exist.
-    pub fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64> {
-        if channel >= self.sim.channels() {
-            panic!("Simulator::get_channel_data_as_ref: Specified channel (index {}) does not exist. Current number of channels: {}.", channel, self.sim.channels());
+    pub fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<L::Float> {
+        match self.try_get_channel_as_ref(channel) {
+            Ok(data) => data,
+            Err(error) => panic!("Simulator::get_channel_as_ref: {}", error),
         }
-        self.sim.get_channel_as_ref(channel)
+    }
+
+    /// Same as `get_channel_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_channel_as_ref(
+        &self,
+        channel: usize,
+    ) -> Result<&ndarray::ArrayD<L::Float>, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.sim.get_channel_as_ref(channel))
     }
 
     /// Mutable version of `get_channel_as_ref()`.
-    pub fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<f64> {
-        if channel >= self.sim.channels() {
-            panic!("Simulator::get_channel_data_as_ref() - Specified channel (index {}) does not exist. Current number of channels: {}.", channel, self.sim.channels());
+    pub fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<L::Float> {
+        match self.try_get_channel_as_mut_ref(channel) {
+            Ok(data) => data,
+            Err(error) => panic!("Simulator::get_channel_as_mut_ref: {}", error),
         }
-        self.sim.get_channel_as_mut_ref(channel)
+    }
+
+    /// Same as `get_channel_as_mut_ref()`, but returns an error instead of panicking.
+    pub fn try_get_channel_as_mut_ref(
+        &mut self,
+        channel: usize,
+    ) -> Result<&mut ndarray::ArrayD<L::Float>, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.sim.get_channel_as_mut_ref(channel))
     }
 
     /// Retrieve a reference to the specified channel's "deltas".
@@ -708,79 +2001,85 @@ impl<L: Lenia> Simulator<L> {
     /// ### Panics
     ///
     /// If the specified `channel` does not exist.
-    pub fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64> {
-        if channel >= self.sim.channels() {
-            panic!("Simulator::get_deltas_as_ref() - Specified channel (index {}) does not exist. Current number of channels: {}.", channel, self.sim.channels());
+    pub fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<L::Float> {
+        match self.try_get_deltas_as_ref(channel) {
+            Ok(data) => data,
+            Err(error) => panic!("Simulator::get_deltas_as_ref: {}", error),
         }
-        self.sim.get_deltas_as_ref(channel)
+    }
+
+    /// Same as `get_deltas_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_deltas_as_ref(
+        &self,
+        channel: usize,
+    ) -> Result<&ndarray::ArrayD<L::Float>, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.sim.get_deltas_as_ref(channel))
     }
 
     /// Exists for backwards-compatibility with `lenia_ca 0.1.0`; use `get_convolved()` instead.
     ///
-    /// Retrieves a `convolution_channel`'s convoution result and extracts only the real component.
+    /// Retrieves a copy of a `convolution_channel`'s convoution result.
     ///
     /// Convolution result is also called the "potential distribution".
     ///
     /// ### Parameters
     ///
     /// * `convolution_channel` - Index of the convolution channel from which to
-    /// produce the `f64` `ndarray`.
+    /// produce the `ndarray`.
     ///
     /// ### Panics
     ///
-    /// If the specified `channel` does not exist.
-    pub fn get_convoluted(&self, convolution_channel: usize) -> ndarray::ArrayD<f64> {
-        if convolution_channel >= self.sim.channels() {
-            panic!("Simulator::get_convoluted() - Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, self.sim.conv_channels());
-        }
-        self.sim
-            .get_convoluted_as_ref(convolution_channel)
-            .map(|a| a.re)
+    /// If the specified `convolution_channel` does not exist.
+    pub fn get_convoluted(&self, convolution_channel: usize) -> ndarray::ArrayD<L::Float> {
+        self.get_convolved(convolution_channel)
     }
 
-    /// Retrieves a `convolution_channel`'s convoution result and extracts only the real component.
+    /// Retrieves a copy of a `convolution_channel`'s convoution result.
     ///
     /// Convolution result is also called the "potential distribution".
     ///
     /// ### Parameters
     ///
     /// * `convolution_channel` - Index of the convolution channel from which to
-    /// produce the `f64` `ndarray`.
+    /// produce the `ndarray`.
     ///
     /// ### Panics
     ///
-    /// If the specified `channel` does not exist.
-    pub fn get_convolved(&self, convolution_channel: usize) -> ndarray::ArrayD<f64> {
-        if convolution_channel >= self.sim.channels() {
-            panic!("Simulator::get_convolved() - Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, self.sim.conv_channels());
+    /// If the specified `convolution_channel` does not exist.
+    pub fn get_convolved(&self, convolution_channel: usize) -> ndarray::ArrayD<L::Float> {
+        match self.try_get_convolved_as_ref(convolution_channel) {
+            Ok(data) => data.clone(),
+            Err(error) => panic!("Simulator::get_convolved: {}", error),
         }
-        self.sim
-            .get_convoluted_as_ref(convolution_channel)
-            .map(|a| a.re)
     }
 
     /// Retrieve a reference to the specified convolution channel's convolution result.
     ///
     /// Convolution result is also called the "potential distribution".
     ///
-    /// Note that the referenced array holds `Complex<f64>` type values.
-    ///
     /// ### Parameters
     ///
     /// * `convolution_channel` - Index of the convolution channel from which to
-    /// produce the `f64` `ndarray`.
+    /// produce the `ndarray`.
     ///
     /// ### Panics
     ///
-    /// If the specified `channel` does not exist.
-    pub fn get_convolved_as_ref(
+    /// If the specified `convolution_channel` does not exist.
+    pub fn get_convolved_as_ref(&self, convolution_channel: usize) -> &ndarray::ArrayD<L::Float> {
+        match self.try_get_convolved_as_ref(convolution_channel) {
+            Ok(data) => data,
+            Err(error) => panic!("Simulator::get_convolved_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_convolved_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_convolved_as_ref(
         &self,
         convolution_channel: usize,
-    ) -> &ndarray::ArrayD<Complex<f64>> {
-        if convolution_channel >= self.sim.channels() {
-            panic!("Simulator::get_convolved_as_ref() - Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, self.sim.conv_channels());
-        }
-        self.sim.get_convoluted_as_ref(convolution_channel)
+    ) -> Result<&ndarray::ArrayD<L::Float>, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.sim.get_convoluted_as_ref(convolution_channel))
     }
 
     /// Retrieve a reference to the specified convolution channel's "activations".
@@ -794,12 +2093,80 @@ impl<L: Lenia> Simulator<L> {
     ///
     /// ### Panics
     ///
-    /// If the specified `channel` does not exist.
-    pub fn get_activated_as_ref(&self, convolution_channel: usize) -> &ndarray::ArrayD<f64> {
-        if convolution_channel >= self.sim.channels() {
-            panic!("Simulator::get_grown_as_ref() - Specified convolution channel (index {}) does not exist. Current number of convolution channels: {}.", convolution_channel, self.sim.conv_channels());
+    /// If the specified `convolution_channel` does not exist.
+    pub fn get_activated_as_ref(&self, convolution_channel: usize) -> &ndarray::ArrayD<L::Float> {
+        match self.try_get_activated_as_ref(convolution_channel) {
+            Ok(data) => data,
+            Err(error) => panic!("Simulator::get_activated_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_activated_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_activated_as_ref(
+        &self,
+        convolution_channel: usize,
+    ) -> Result<&ndarray::ArrayD<L::Float>, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.sim.get_grown_as_ref(convolution_channel))
+    }
+
+    /// Retrieve the growth function of the specified convolution channel.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channel` - Index of the convolution channel from which the growth function will be supplied.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn growth_function(&self, convolution_channel: usize) -> &dyn GrowthFunction<L::Float> {
+        match self.try_growth_function(convolution_channel) {
+            Ok(growth_function) => growth_function,
+            Err(error) => panic!("Simulator::growth_function: {}", error),
+        }
+    }
+
+    /// Same as `growth_function()`, but returns an error instead of panicking.
+    pub fn try_growth_function(
+        &self,
+        convolution_channel: usize,
+    ) -> Result<&dyn GrowthFunction<L::Float>, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.sim.growth_function(convolution_channel))
+    }
+
+    /// Retrieve the parameter map of a growth function parameter of the specified convolution channel, if it has one.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channel` - Index of the convolution channel whose growth function parameter map will be supplied.
+    ///
+    /// * `parameter` - Name of the parameter, as in the growth function's `parameter_names()`.
+    ///
+    /// ### Panics
+    ///
+    /// * If the specified `convolution_channel` does not exist.
+    ///
+    /// * If the growth function has no parameter called `parameter`.
+    pub fn growth_parameter_map(
+        &self,
+        convolution_channel: usize,
+        parameter: &str,
+    ) -> Option<&ndarray::ArrayD<L::Float>> {
+        match self.try_growth_parameter_map(convolution_channel, parameter) {
+            Ok(map) => map,
+            Err(error) => panic!("Simulator::growth_parameter_map: {}", error),
         }
-        self.sim.get_grown_as_ref(convolution_channel)
+    }
+
+    /// Same as `growth_parameter_map()`, but returns an error instead of panicking.
+    pub fn try_growth_parameter_map(
+        &self,
+        convolution_channel: usize,
+        parameter: &str,
+    ) -> Result<Option<&ndarray::ArrayD<L::Float>>, LeniaError> {
+        let index = self.growth_parameter_index(convolution_channel, parameter)?;
+        Ok(self.sim.parameter_map(index, convolution_channel))
     }
 
     /// Retrieve the kernel being used for the specified convolution channels' convolution.
@@ -807,12 +2174,176 @@ impl<L: Lenia> Simulator<L> {
     /// ### Parameters
     ///
     /// * `convolution_channel` - Index of the convolution channel from which the kernel will be supplied.
-    pub fn get_kernel_as_ref(&self, convolution_channel: usize) -> &Kernel {
-        self.sim.get_kernel_as_ref(convolution_channel)
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn get_kernel_as_ref(&self, convolution_channel: usize) -> &Kernel<L::Float> {
+        match self.try_get_kernel_as_ref(convolution_channel) {
+            Ok(kernel) => kernel,
+            Err(error) => panic!("Simulator::get_kernel_as_ref: {}", error),
+        }
+    }
+
+    /// Same as `get_kernel_as_ref()`, but returns an error instead of panicking.
+    pub fn try_get_kernel_as_ref(
+        &self,
+        convolution_channel: usize,
+    ) -> Result<&Kernel<L::Float>, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.sim.get_kernel_as_ref(convolution_channel))
+    }
+
+    /// Get the convolution method set for the specified convolution channel.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channel` - Index of the convolution channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn convolution_backend(&self, convolution_channel: usize) -> ConvolutionBackend {
+        match self.try_convolution_backend(convolution_channel) {
+            Ok(backend) => backend,
+            Err(error) => panic!("Simulator::convolution_backend: {}", error),
+        }
+    }
+
+    /// Same as `convolution_backend()`, but returns an error instead of panicking.
+    pub fn try_convolution_backend(
+        &self,
+        convolution_channel: usize,
+    ) -> Result<ConvolutionBackend, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.sim.backend(convolution_channel))
+    }
+
+    /// Get the channel that the specified convolution channel convolutes.
+    ///
+    /// ### Parameters
+    ///
+    /// * `convolution_channel` - Index of the convolution channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `convolution_channel` does not exist.
+    pub fn convolution_channel_source(&self, convolution_channel: usize) -> usize {
+        match self.try_convolution_channel_source(convolution_channel) {
+            Ok(source) => source,
+            Err(error) => panic!("Simulator::convolution_channel_source: {}", error),
+        }
+    }
+
+    /// Same as `convolution_channel_source()`, but returns an error instead of panicking.
+    pub fn try_convolution_channel_source(
+        &self,
+        convolution_channel: usize,
+    ) -> Result<usize, LeniaError> {
+        self.check_convolution_channel(convolution_channel)?;
+        Ok(self.sim.source_channel(convolution_channel))
+    }
+
+    /// Get the boundary condition of each axis of the channels.
+    pub fn boundary_conditions(&self) -> &[BoundaryCondition] {
+        self.sim.boundaries()
+    }
+
+    /// Get the scheme used to advance the channels by one integration step.
+    pub fn integrator(&self) -> Integrator {
+        self.sim.integrator()
+    }
+
+    /// Get the way the weighted sum of the convolution channels updates a channel.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - Index of the channel.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn update_mode(&self, channel: usize) -> UpdateMode {
+        match self.try_update_mode(channel) {
+            Ok(mode) => mode,
+            Err(error) => panic!("Simulator::update_mode: {}", error),
+        }
+    }
+
+    /// Same as `update_mode()`, but returns an error instead of panicking.
+    pub fn try_update_mode(&self, channel: usize) -> Result<UpdateMode, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.sim.update_mode(channel))
+    }
+
+    /// Get the range of values that a channel is kept within.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - Index of the channel whose range of values is supplied.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn value_range(&self, channel: usize) -> ValueRange<L::Float> {
+        match self.try_value_range(channel) {
+            Ok(range) => range,
+            Err(error) => panic!("Simulator::value_range: {}", error),
+        }
+    }
+
+    /// Same as `value_range()`, but returns an error instead of panicking.
+    pub fn try_value_range(&self, channel: usize) -> Result<ValueRange<L::Float>, LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.sim.value_range(channel))
+    }
+
+    /// Get the weights of the convolution channels for the specified channel.
+    ///
+    /// ### Parameters
+    ///
+    /// * `channel` - Index of the channel whose weights are supplied.
+    ///
+    /// ### Panics
+    ///
+    /// If the specified `channel` does not exist.
+    pub fn weights(&self, channel: usize) -> &[L::Float] {
+        match self.try_weights(channel) {
+            Ok(weights) => weights,
+            Err(error) => panic!("Simulator::weights: {}", error),
+        }
+    }
+
+    /// Same as `weights()`, but returns an error instead of panicking.
+    pub fn try_weights(&self, channel: usize) -> Result<&[L::Float], LeniaError> {
+        self.check_channel(channel)?;
+        Ok(self.sim.weights(channel))
+    }
+
+    /// Get the stochastic terms of the update step, if any.
+    pub fn stochasticity(&self) -> Option<Stochasticity<L::Float>> {
+        self.sim.stochasticity()
+    }
+
+    /// Get the simulated time, the sum of the integration steps of all iterations so far.
+    pub fn time(&self) -> L::Float {
+        self.time
+    }
+
+    /// Get the number of iterations so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Get whether the integration step is chosen adaptively, see `set_adaptive_dt()`.
+    pub fn is_adaptive_dt(&self) -> bool {
+        self.adaptive_dt.is_some()
     }
 
     /// Get the current integration step (a.k.a. timestep) parameter `dt` of the `Lenia` instance.
-    pub fn dt(&self) -> f64 {
+    ///
+    /// With an adaptive integration step, this is the step that the next iteration will attempt first.
+    pub fn dt(&self) -> L::Float {
         self.sim.dt()
     }
 
@@ -830,20 +2361,130 @@ impl<L: Lenia> Simulator<L> {
     pub fn convolution_channels(&self) -> usize {
         self.sim.conv_channels()
     }
+
+    fn check_channel(&self, channel: usize) -> Result<(), LeniaError> {
+        check_channel_index(channel, self.sim.channels())
+    }
+
+    fn check_convolution_channel(&self, convolution_channel: usize) -> Result<(), LeniaError> {
+        check_convolution_channel_index(convolution_channel, self.sim.conv_channels())
+    }
+}
+
+/// Checks that `channel` is the index of one of `channels` channels.
+fn check_channel_index(channel: usize, channels: usize) -> Result<(), LeniaError> {
+    if channel >= channels {
+        return Err(LeniaError::ChannelOutOfRange { channel, channels });
+    }
+    Ok(())
+}
+
+/// Checks that `convolution_channel` is the index of one of `convolution_channels` convolution channels.
+fn check_convolution_channel_index(
+    convolution_channel: usize,
+    convolution_channels: usize,
+) -> Result<(), LeniaError> {
+    if convolution_channel >= convolution_channels {
+        return Err(LeniaError::ConvolutionChannelOutOfRange {
+            convolution_channel,
+            convolution_channels,
+        });
+    }
+    Ok(())
+}
+
+/// Checks that a kernel of `kernel_shape` can act on channels of `channel_shape`.
+fn check_kernel_shape(kernel_shape: &[usize], channel_shape: &[usize]) -> Result<(), LeniaError> {
+    if kernel_shape.len() != channel_shape.len() {
+        return Err(LeniaError::DimensionalityMismatch {
+            expected: channel_shape.len(),
+            found: kernel_shape.len(),
+        });
+    }
+    for (i, (kernel, channel)) in kernel_shape.iter().zip(channel_shape).enumerate() {
+        if kernel > channel {
+            return Err(LeniaError::InvalidShape(format!(
+                "Supplied kernel is larger than the channel it acts on in axis {}!",
+                i
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that there is one boundary condition for every axis of channels of `channel_shape`.
+fn check_boundaries(
+    boundaries: &[BoundaryCondition],
+    channel_shape: &[usize],
+) -> Result<(), LeniaError> {
+    if boundaries.len() != channel_shape.len() {
+        return Err(LeniaError::DimensionalityMismatch {
+            expected: channel_shape.len(),
+            found: boundaries.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the elements of a kernel sum up to a finite value other than `0.0`, so that it can be normalized.
+fn check_kernel_sum<F: LeniaFloat>(kernel: &ndarray::ArrayD<F>) -> Result<(), LeniaError> {
+    let sum = kernel.sum();
+    if sum == F::zero() || !sum.is_finite() {
+        return Err(LeniaError::InvalidParameter(format!(
+            "The elements of the kernel sum up to {}, so it cannot be normalized!",
+            sum
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the parameters of a growth function.
+fn check_growth_function<F: LeniaFloat, G: GrowthFunction<F> + ?Sized>(
+    growth_function: &G,
+) -> Result<(), LeniaError> {
+    growth_function.validate().map_err(|error| {
+        LeniaError::InvalidParameter(format!(
+            "Invalid parameters for growth function \"{}\": {}.",
+            growth_function.name(),
+            error
+        ))
+    })
+}
+
+/// Checks that a range of values is usable.
+fn check_value_range<F: LeniaFloat>(range: &ValueRange<F>) -> Result<(), LeniaError> {
+    range
+        .validate()
+        .map_err(|error| LeniaError::InvalidParameter(format!("Invalid value range: {}.", error)))
+}
+
+/// Checks that an integration step is positive and finite.
+fn check_dt<F: LeniaFloat>(dt: F) -> Result<(), LeniaError> {
+    if !(dt > F::zero() && dt.is_finite()) {
+        return Err(LeniaError::InvalidParameter(format!(
+            "The integration step dt ({}) must be positive and finite.",
+            dt
+        )));
+    }
+    Ok(())
 }
 
 /// Lenia functionality trait.
 ///
 /// Lenia trait organizes together all the functionality to interact with a Lenia simulation.
+///
+/// The setters return an error instead of panicking if the specified channel or convolution channel
+/// does not exist, or if the `Lenia` instance can not use the new value.
 pub trait Lenia {
-    /// Creates a new `Lenia` instance.
-    fn new(shape: &[usize]) -> Self;
+    /// The floating-point type (`f32` or `f64`) the `Lenia` instance is simulated at.
+    type Float: LeniaFloat;
     /// Sets the number of channels in the `Lenia` instance.
     ///
-    /// **If the new number of channels is fewer than currently then the user is responsible for re-making
-    /// the convolution channels or deleting invalidated convolution channels and
-    /// make sure that no convolution channel tries to convolute a non-existent channel!**
-    fn set_channels(&mut self, num_channels: usize);
+    /// Returns `LeniaError::ChannelOutOfRange` if the new number of channels is fewer than currently
+    /// and a convolution channel still uses one of the dropped channels as its source.
+    ///
+    /// Returns `LeniaError::Unsupported` if the `Lenia` instance can only have a single channel.
+    fn set_channels(&mut self, num_channels: usize) -> Result<(), LeniaError>;
     /// Sets the number of convolution channels in the `Lenia` instance.
     ///
     /// * Any convolution channels
@@ -854,18 +2495,70 @@ pub trait Lenia {
     /// * Any newly initialized convolution channels will have to have their kernels and
     /// growth functions added. By default all channels will use a weight of `0.0` for the new
     /// channels.
-    fn set_conv_channels(&mut self, num_conv_channels: usize);
+    ///
+    /// Returns `LeniaError::Unsupported` if the `Lenia` instance can only have a single convolution channel.
+    fn set_conv_channels(&mut self, num_conv_channels: usize) -> Result<(), LeniaError>;
     /// Sets the source channel for a convolution channel.
-    fn set_source_channel(&mut self, conv_channel: usize, src_channel: usize);
+    fn set_source_channel(
+        &mut self,
+        conv_channel: usize,
+        src_channel: usize,
+    ) -> Result<(), LeniaError>;
     /// Sets the convolution kernel for a convolution channel.
-    fn set_kernel(&mut self, kernel: ndarray::ArrayD<f64>, conv_channel: usize);
+    ///
+    /// Returns an error if the kernel does not fit the channels or can not be normalized, see `Kernel::from()`.
+    fn set_kernel(
+        &mut self,
+        kernel: ndarray::ArrayD<Self::Float>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError>;
     /// Sets the growth function for a convolution channel.
+    ///
+    /// Returns `LeniaError::InvalidParameter` if the parameters of the growth function are invalid.
     fn set_growth(
         &mut self,
-        f: fn(f64, &[f64]) -> f64,
-        growth_params: Vec<f64>,
-        conv_channel: usize,
-    );
+        growth: Arc<dyn GrowthFunction<Self::Float>>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError>;
+    /// Sets (or removes with `None`) the spatially varying values of the `parameter`-th growth function parameter.
+    ///
+    /// Setting the growth function removes all of the parameter maps of the convolution channel.
+    ///
+    /// Returns an error if the growth function has no `parameter`-th parameter, or if the shape of `map`
+    /// is not the same as the shape of the channels.
+    fn set_parameter_map(
+        &mut self,
+        map: Option<ndarray::ArrayD<Self::Float>>,
+        parameter: usize,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError>;
+    /// Sets the way the convolution of a convolution channel is computed.
+    fn set_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError>;
+    /// Sets the boundary condition of each axis of the channels.
+    ///
+    /// Returns an error if the length of `boundaries` does not match the dimensionality of the channels.
+    fn set_boundaries(&mut self, boundaries: &[BoundaryCondition]) -> Result<(), LeniaError>;
+    /// Sets the scheme used to advance the channels by one integration step.
+    fn set_integrator(&mut self, integrator: Integrator) -> Result<(), LeniaError>;
+    /// Sets the way the weighted sum of the convolution channels updates a channel.
+    fn set_update_mode(&mut self, mode: UpdateMode, channel: usize) -> Result<(), LeniaError>;
+    /// Sets the range of values a channel is kept within.
+    fn set_value_range(
+        &mut self,
+        range: ValueRange<Self::Float>,
+        channel: usize,
+    ) -> Result<(), LeniaError>;
+    /// Sets (or removes with `None`) the stochastic terms of the update step, re-seeding the random number generator.
+    ///
+    /// Removing the stochastic terms always succeeds.
+    fn set_stochasticity(
+        &mut self,
+        stochasticity: Option<Stochasticity<Self::Float>>,
+    ) -> Result<(), LeniaError>;
     /// Sets the weights for input into a channel from convolution channels for summing.
     ///
     /// * If the length of `new weights` is less than the number of convolution channels then
@@ -874,33 +2567,320 @@ pub trait Lenia {
     /// * If the length of `new weights` is greater than the number of convolution channels then
     /// the excess weights will be disregarded, and their effect for the weighted average on the
     /// channel is not taken into account.
-    fn set_weights(&mut self, new_weights: &[f64], channel: usize);
+    fn set_weights(
+        &mut self,
+        new_weights: &[Self::Float],
+        channel: usize,
+    ) -> Result<(), LeniaError>;
     /// Sets the dt parameter of the `Lenia` instance.
-    fn set_dt(&mut self, new_dt: f64);
+    fn set_dt(&mut self, new_dt: Self::Float);
     /// Returns a reference to a convolution channel's kernel.
-    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel;
+    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel<Self::Float>;
+    /// Returns a reference to a convolution channel's growth function.
+    fn growth_function(&self, conv_channel: usize) -> &dyn GrowthFunction<Self::Float>;
+    /// Returns the spatially varying values of the `parameter`-th growth function parameter, if any.
+    fn parameter_map(
+        &self,
+        parameter: usize,
+        conv_channel: usize,
+    ) -> Option<&ndarray::ArrayD<Self::Float>>;
     /// Returns a reference to a channel's current data.
-    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64>;
+    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<Self::Float>;
     /// Returns a mutable reference to a channel's current data.
-    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<f64>;
+    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<Self::Float>;
     /// Returns a reference to the convolution result.
-    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<Complex<f64>>;
+    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<Self::Float>;
     /// Returns a reference to the field with growth function applied.
-    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<f64>;
+    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<Self::Float>;
     /// Returns a reference to the results to be added to previous channel state. Lacks `dt` scaling.
-    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<f64>;
+    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<Self::Float>;
     /// Returns the shape of the channels and convolution channels (as reference).
     fn shape(&self) -> &[usize];
     /// Returns the current `dt` parameter of the `Lenia` instance.
-    fn dt(&self) -> f64;
+    fn dt(&self) -> Self::Float;
     /// Returns the number of channels in the `Lenia` instance.
     fn channels(&self) -> usize;
     /// Returns the number of convolution channels in the `Lenia` instance.
     fn conv_channels(&self) -> usize;
     /// Returns the weights of the specified channel.
-    fn weights(&self, channel: usize) -> &[f64];
+    fn weights(&self, channel: usize) -> &[Self::Float];
+    /// Returns the way the convolution of a convolution channel is computed.
+    fn backend(&self, conv_channel: usize) -> ConvolutionBackend;
+    /// Returns the channel that a convolution channel convolutes.
+    fn source_channel(&self, conv_channel: usize) -> usize;
+    /// Returns the boundary condition of each axis of the channels.
+    fn boundaries(&self) -> &[BoundaryCondition];
+    /// Returns the scheme used to advance the channels by one integration step.
+    fn integrator(&self) -> Integrator;
+    /// Returns the way the weighted sum of the convolution channels updates a channel.
+    fn update_mode(&self, channel: usize) -> UpdateMode;
+    /// Returns the range of values a channel is kept within.
+    fn value_range(&self, channel: usize) -> ValueRange<Self::Float>;
+    /// Returns the stochastic terms of the update step, if any.
+    fn stochasticity(&self) -> Option<Stochasticity<Self::Float>>;
     /// Calculates the next state of the `Lenia` instance, and updates the data in channels accordingly.
     fn iterate(&mut self);
+    /// Writes the complete state of the `Lenia` instance into a checkpoint, see the `checkpoint` module.
+    fn write_state(&self, writer: &mut checkpoint::StateWriter) -> Result<(), LeniaError>;
+    /// Restores the complete state of the `Lenia` instance from a checkpoint written by `write_state()`,
+    /// re-making its kernels and ffts.
+    ///
+    /// Returns `LeniaError::Format` if the checkpoint was written by a different type of Lenia.
+    fn read_state(
+        &mut self,
+        reader: &mut checkpoint::StateReader,
+        registry: &rules::Registry<Self::Float>,
+    ) -> Result<(), LeniaError>;
+}
+
+/// Construction of `Lenia` instances.
+///
+/// Kept apart from the `Lenia` trait so that `Lenia` can be used as a trait object. A `Simulator` made with
+/// `Simulator::new()` needs its `Lenia` type to implement `NewLenia`, while a boxed `Lenia` instance of a type chosen
+/// at runtime can be wrapped with `Simulator::from_lenia()`.
+pub trait NewLenia: Lenia + Sized {
+    /// Creates a new `Lenia` instance.
+    fn new(shape: &[usize]) -> Self;
+    /// Same as `new()`, but returns an error instead of panicking if the `Lenia` instance can not have channels of `shape`.
+    fn try_new(shape: &[usize]) -> Result<Self, LeniaError>;
+}
+
+/// Boxed `Lenia` instances, such as `Box<dyn Lenia<Float = f64>>`, are `Lenia` instances themselves.
+impl<L: Lenia + ?Sized> Lenia for Box<L> {
+    type Float = L::Float;
+
+    fn set_channels(&mut self, num_channels: usize) -> Result<(), LeniaError> {
+        (**self).set_channels(num_channels)
+    }
+
+    fn set_conv_channels(&mut self, num_conv_channels: usize) -> Result<(), LeniaError> {
+        (**self).set_conv_channels(num_conv_channels)
+    }
+
+    fn set_source_channel(
+        &mut self,
+        conv_channel: usize,
+        src_channel: usize,
+    ) -> Result<(), LeniaError> {
+        (**self).set_source_channel(conv_channel, src_channel)
+    }
+
+    fn set_kernel(
+        &mut self,
+        kernel: ndarray::ArrayD<L::Float>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        (**self).set_kernel(kernel, conv_channel)
+    }
+
+    fn set_growth(
+        &mut self,
+        growth: Arc<dyn GrowthFunction<L::Float>>,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        (**self).set_growth(growth, conv_channel)
+    }
+
+    fn set_parameter_map(
+        &mut self,
+        map: Option<ndarray::ArrayD<L::Float>>,
+        parameter: usize,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        (**self).set_parameter_map(map, parameter, conv_channel)
+    }
+
+    fn set_backend(
+        &mut self,
+        backend: ConvolutionBackend,
+        conv_channel: usize,
+    ) -> Result<(), LeniaError> {
+        (**self).set_backend(backend, conv_channel)
+    }
+
+    fn set_boundaries(&mut self, boundaries: &[BoundaryCondition]) -> Result<(), LeniaError> {
+        (**self).set_boundaries(boundaries)
+    }
+
+    fn set_integrator(&mut self, integrator: Integrator) -> Result<(), LeniaError> {
+        (**self).set_integrator(integrator)
+    }
+
+    fn set_update_mode(&mut self, mode: UpdateMode, channel: usize) -> Result<(), LeniaError> {
+        (**self).set_update_mode(mode, channel)
+    }
+
+    fn set_value_range(
+        &mut self,
+        range: ValueRange<L::Float>,
+        channel: usize,
+    ) -> Result<(), LeniaError> {
+        (**self).set_value_range(range, channel)
+    }
+
+    fn set_stochasticity(
+        &mut self,
+        stochasticity: Option<Stochasticity<L::Float>>,
+    ) -> Result<(), LeniaError> {
+        (**self).set_stochasticity(stochasticity)
+    }
+
+    fn set_weights(&mut self, new_weights: &[L::Float], channel: usize) -> Result<(), LeniaError> {
+        (**self).set_weights(new_weights, channel)
+    }
+
+    fn set_dt(&mut self, new_dt: L::Float) {
+        (**self).set_dt(new_dt)
+    }
+
+    fn get_kernel_as_ref(&self, conv_channel: usize) -> &Kernel<L::Float> {
+        (**self).get_kernel_as_ref(conv_channel)
+    }
+
+    fn growth_function(&self, conv_channel: usize) -> &dyn GrowthFunction<L::Float> {
+        (**self).growth_function(conv_channel)
+    }
+
+    fn parameter_map(
+        &self,
+        parameter: usize,
+        conv_channel: usize,
+    ) -> Option<&ndarray::ArrayD<L::Float>> {
+        (**self).parameter_map(parameter, conv_channel)
+    }
+
+    fn get_channel_as_ref(&self, channel: usize) -> &ndarray::ArrayD<L::Float> {
+        (**self).get_channel_as_ref(channel)
+    }
+
+    fn get_channel_as_mut_ref(&mut self, channel: usize) -> &mut ndarray::ArrayD<L::Float> {
+        (**self).get_channel_as_mut_ref(channel)
+    }
+
+    fn get_convoluted_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<L::Float> {
+        (**self).get_convoluted_as_ref(conv_channel)
+    }
+
+    fn get_grown_as_ref(&self, conv_channel: usize) -> &ndarray::ArrayD<L::Float> {
+        (**self).get_grown_as_ref(conv_channel)
+    }
+
+    fn get_deltas_as_ref(&self, channel: usize) -> &ndarray::ArrayD<L::Float> {
+        (**self).get_deltas_as_ref(channel)
+    }
+
+    fn shape(&self) -> &[usize] {
+        (**self).shape()
+    }
+
+    fn dt(&self) -> L::Float {
+        (**self).dt()
+    }
+
+    fn channels(&self) -> usize {
+        (**self).channels()
+    }
+
+    fn conv_channels(&self) -> usize {
+        (**self).conv_channels()
+    }
+
+    fn weights(&self, channel: usize) -> &[L::Float] {
+        (**self).weights(channel)
+    }
+
+    fn backend(&self, conv_channel: usize) -> ConvolutionBackend {
+        (**self).backend(conv_channel)
+    }
+
+    fn source_channel(&self, conv_channel: usize) -> usize {
+        (**self).source_channel(conv_channel)
+    }
+
+    fn boundaries(&self) -> &[BoundaryCondition] {
+        (**self).boundaries()
+    }
+
+    fn integrator(&self) -> Integrator {
+        (**self).integrator()
+    }
+
+    fn update_mode(&self, channel: usize) -> UpdateMode {
+        (**self).update_mode(channel)
+    }
+
+    fn value_range(&self, channel: usize) -> ValueRange<L::Float> {
+        (**self).value_range(channel)
+    }
+
+    fn stochasticity(&self) -> Option<Stochasticity<L::Float>> {
+        (**self).stochasticity()
+    }
+
+    fn iterate(&mut self) {
+        (**self).iterate()
+    }
+
+    fn write_state(&self, writer: &mut checkpoint::StateWriter) -> Result<(), LeniaError> {
+        (**self).write_state(writer)
+    }
+
+    fn read_state(
+        &mut self,
+        reader: &mut checkpoint::StateReader,
+        registry: &rules::Registry<L::Float>,
+    ) -> Result<(), LeniaError> {
+        (**self).read_state(reader, registry)
+    }
+}
+
+/// A growth function, applied onto every value of the convolution result of a convolution channel.
+///
+/// The `growth_functions` module contains typed implementors of every growth function in it, such as
+/// `growth_functions::StandardLenia`. Closures taking and returning a single value are growth functions as well,
+/// which allows capturing lookup tables or precomputed constants.
+pub trait GrowthFunction<F: LeniaFloat>: Send + Sync {
+    /// Applies the growth function onto a single value.
+    fn growth(&self, num: F) -> F;
+    /// Returns the name of the growth function.
+    fn name(&self) -> &str;
+    /// Applies the growth function onto a single value, with `parameters` in place of its own.
+    ///
+    /// Used for spatially varying parameters, `parameters` being in the same order as `parameters()`.
+    /// Growth functions without parameters ignore them.
+    fn growth_with_parameters(&self, num: F, parameters: &[F]) -> F {
+        self.growth(num)
+    }
+    /// Returns the names of the parameters, in the same order as `parameters()`.
+    fn parameter_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Returns the values of the parameters.
+    fn parameters(&self) -> Vec<F> {
+        Vec::new()
+    }
+    /// Checks whether the parameters are usable, returning a description of the problem if not.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl<F: LeniaFloat> fmt::Debug for dyn GrowthFunction<F> + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}(", self.name())?;
+        for (i, (name, value)) in self
+            .parameter_names()
+            .iter()
+            .zip(self.parameters())
+            .enumerate()
+        {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", name, value)?;
+        }
+        write!(f, ")")
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -908,13 +2888,17 @@ pub trait Lenia {
 ///
 /// The `Channel` struct is a wrapper for holding the data of a single channel in a
 /// `Lenia` simulation.
-pub struct Channel {
+pub struct Channel<F: LeniaFloat = f64> {
     /// The data of the channel
-    pub field: ndarray::ArrayD<f64>,
+    pub field: ndarray::ArrayD<F>,
     /// The weights of the channel
-    pub weights: Vec<f64>,
+    pub weights: Vec<F>,
     /// The reciprocal of the sum of the weights, used for optimized calculations
-    pub weight_sum_reciprocal: f64,
+    pub weight_sum_reciprocal: F,
+    /// The way the weighted sum of the convolution channels updates the channel
+    pub update_mode: UpdateMode,
+    /// The range of values the channel is kept within, if updated additively
+    pub value_range: ValueRange<F>,
 }
 
 #[derive(Clone)]
@@ -923,51 +2907,314 @@ pub struct Channel {
 /// The `ConvolutionChannel` struct holds relevant data for the convolution step of the
 /// Lenia simulation. This includes the kernel, the convolution result, and the
 /// growth function.
-pub struct ConvolutionChannel {
+pub struct ConvolutionChannel<F: LeniaFloat = f64> {
     /// The channel index which is convoluted with the kernel
     pub input_channel: usize,
     /// The data of the convolution channel (holds the convolution result)
-    pub field: ndarray::ArrayD<f64>,
+    pub field: ndarray::ArrayD<F>,
     /// The kernel used for convolution
-    pub kernel: Kernel,
-    /// The growth function container
-    pub growth: fn(f64, &[f64]) -> f64,
-    /// The parameters to use while applying the growth function
-    pub growth_params: Vec<f64>,
+    pub kernel: Kernel<F>,
+    /// The growth function, along with its parameters
+    pub growth: Arc<dyn GrowthFunction<F>>,
+    /// Spatially varying values of the growth function's parameters, indexed like its `parameters()`.
+    ///
+    /// Parameters without a map (or all of them, if empty) use the growth function's own value everywhere.
+    pub parameter_maps: Vec<Option<ndarray::ArrayD<F>>>,
+    /// The way the convolution with the kernel is computed
+    pub backend: ConvolutionBackend,
 }
 
-impl fmt::Debug for ConvolutionChannel {
+impl<F: LeniaFloat> fmt::Debug for ConvolutionChannel<F> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ConvolutionChannel")
             .field("input_channel", &self.input_channel)
             .field("field", &self.field)
             .field("kernel", &self.kernel)
-            .field("growth", &"fn(f64, &[f64]) -> f64")
-            .field("growth_params", &self.growth_params)
+            .field("growth", &self.growth)
+            .field("parameter_maps", &self.parameter_maps)
+            .field("backend", &self.backend)
             .finish()
     }
 }
 
+/// The way an axis of the channels behaves at its edges.
+///
+/// Only affects what the kernels see beyond the edges of the channels during convolution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryCondition {
+    /// The axis wraps around, such that the world is a torus along it.
+    #[default]
+    Periodic,
+    /// Everything beyond the edges is `0.0`, anything crossing the edge is effectively absorbed.
+    Zero,
+    /// The edges act as mirrors, reflecting the contents of the channel.
+    Mirror,
+}
+
+/// Method used to compute the convolution of a channel with a kernel.
+///
+/// Both methods respect the `BoundaryCondition`s of the channel and give the same result
+/// (up to floating-point rounding), but differ greatly in speed depending on the size of the kernel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvolutionBackend {
+    /// Pick `Direct` for kernels with few elements compared to the size of the channel, `Fft` otherwise.
+    #[default]
+    Automatic,
+    /// Fast-fourier-transform based convolution, cost does not depend on the size of the kernel.
+    Fft,
+    /// Sliding-window convolution over every non-zero element of the kernel.
+    /// Much faster than `Fft` for very small kernels, such as `kernels::conway_game_of_life()`.
+    Direct,
+}
+
+impl ConvolutionBackend {
+    /// Whether a convolution with `kernel` over a channel with `channel_size` elements
+    /// should be computed using direct convolution.
+    fn is_direct<F: LeniaFloat>(&self, kernel: &Kernel<F>, channel_size: usize) -> bool {
+        match self {
+            ConvolutionBackend::Automatic => {
+                kernel.normalized.len()
+                    <= DIRECT_CONVOLUTION_MAX_COST * channel_size.ilog2() as usize
+            }
+            ConvolutionBackend::Fft => false,
+            ConvolutionBackend::Direct => true,
+        }
+    }
+}
+
+/// Scheme used to advance the channels by one integration step `dt`.
+///
+/// Every scheme evaluates the whole convolution, growth and weighted-sum pipeline once per stage, so
+/// higher-order schemes are proportionally slower per iteration, but far more accurate at a given `dt`.
+/// Channels with `UpdateMode::Additive` are always kept within their `ValueRange`, `0.0..1.0` by default.
+/// Intermediate stages are evaluated on clamped states, while the deltas of each stage are combined unclamped, and the result is clamped once more.
+/// Channels with `UpdateMode::Asymptotic` are never clamped, neither in the intermediate stages nor in the result.
+/// After an iteration the convolution results, growth and deltas are those of the last stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Integrator {
+    /// Explicit Euler, 1 stage. `field = clamp(field + dt * delta(field))` for additive channels.
+    #[default]
+    Euler,
+    /// Explicit midpoint method, 2 stages. The channels are first advanced by half a step and clamped,
+    /// then `field = clamp(field + dt * delta(half_step))`.
+    Midpoint,
+    /// Heun's method, 2 stages. The channels are first advanced by a full Euler step and clamped,
+    /// then `field = clamp(field + dt * (delta(field) + delta(euler_step)) / 2)`.
+    Heun,
+    /// Classic 4th order Runge-Kutta, 4 stages. The 3 intermediate states (half step, half step and
+    /// full step, each from the original channels) are clamped, and the weighted average of the
+    /// 4 deltas is added onto the original channels before the final clamp.
+    RungeKutta4,
+}
+
+impl Integrator {
+    /// Order of accuracy of the scheme, the local error of a single step scales with `dt^(order + 1)`.
+    fn order(&self) -> i32 {
+        match self {
+            Integrator::Euler => 1,
+            Integrator::Midpoint | Integrator::Heun => 2,
+            Integrator::RungeKutta4 => 4,
+        }
+    }
+
+    /// The stages of the scheme. Each stage is a pair of the fraction of `dt` by which the deltas of the
+    /// previous stage advance the original channels to get the state to evaluate, and the weight of the
+    /// deltas of the stage in the final step.
+    fn stages<F: LeniaFloat>(&self) -> Vec<(F, F)> {
+        let stages: &[(f64, f64)] = match self {
+            Integrator::Euler => &[(0.0, 1.0)],
+            Integrator::Midpoint => &[(0.0, 0.0), (0.5, 1.0)],
+            Integrator::Heun => &[(0.0, 0.5), (1.0, 0.5)],
+            Integrator::RungeKutta4 => &[
+                (0.0, 1.0 / 6.0),
+                (0.5, 1.0 / 3.0),
+                (0.5, 1.0 / 3.0),
+                (1.0, 1.0 / 6.0),
+            ],
+        };
+        stages
+            .iter()
+            .map(|(offset, weight)| (float(*offset), float(*weight)))
+            .collect()
+    }
+}
+
+/// The way the weighted sum of the convolution channels (the deltas) updates a channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateMode {
+    /// The deltas are the rate of change of the channel, `field = clamp(field + dt * deltas, 0.0, 1.0)`,
+    /// or saturated to another `ValueRange` of the channel.
+    /// Used with growth functions in range `[-1.0..1.0]`, such as `growth_functions::StandardLenia`.
+    #[default]
+    Additive,
+    /// The deltas are a target value that the channel exponentially approaches,
+    /// `field = field + dt * (deltas - field)`, as in Asymptotic Lenia. The channel is not clamped,
+    /// and its `ValueRange` is disregarded.
+    ///
+    /// Used with growth functions in range `[0.0..1.0]`, such as `growth_functions::StandardLeniaTarget`.
+    /// If the weights of the channel sum up to `1.0` and `dt <= 1.0` the channel stays within `[0.0..1.0]` on its own.
+    Asymptotic,
+}
+
+impl UpdateMode {
+    /// Rate of change of a single `value` of a channel with the given `delta`.
+    fn rate<F: LeniaFloat>(&self, value: F, delta: F) -> F {
+        match self {
+            UpdateMode::Additive => delta,
+            UpdateMode::Asymptotic => delta - value,
+        }
+    }
+
+    /// Advances a single `value` of a channel by `step` times its `rate`, keeping it within `range` if additive.
+    fn advance<F: LeniaFloat>(&self, value: F, rate: F, step: F, range: &ValueRange<F>) -> F {
+        match self {
+            UpdateMode::Additive => range.saturate(value, rate * step),
+            UpdateMode::Asymptotic => value + (rate * step),
+        }
+    }
+}
+
+/// The way the values of a channel are kept within its `ValueRange`.
+///
+/// The soft saturations compress each change of a value by how much room is left towards the bound it heads to,
+/// `value + room * curve(change / room)`. Changes much smaller than the room pass through almost unchanged,
+/// larger ones approach the bound smoothly without reaching it. A value that does not change stays as it is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Saturation {
+    /// Values outside of the range are set to the nearest bound.
+    #[default]
+    Clamp,
+    /// Changes are compressed by the algebraic sigmoid `x / sqrt(1 + x^2)`, a "soft clip" that approaches
+    /// the bounds more gradually than `Tanh`. Requires finite bounds.
+    Sigmoid,
+    /// Changes are compressed by the hyperbolic tangent `tanh(x)`. Requires finite bounds.
+    Tanh,
+}
+
+/// Range of values that a channel is kept within after every update.
+///
+/// The soft saturations approach the bounds smoothly instead of cutting off at them, see `Saturation`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValueRange<F: LeniaFloat = f64> {
+    /// Lower bound of the range, may be negative or `-infinity`.
+    pub min: F,
+    /// Upper bound of the range, may be `infinity`.
+    pub max: F,
+    /// The way values are kept within the range.
+    pub saturation: Saturation,
+}
+
+impl<F: LeniaFloat> ValueRange<F> {
+    /// Creates a range from `min` to `max`, saturated by `saturation`.
+    pub fn new(min: F, max: F, saturation: Saturation) -> Self {
+        ValueRange {
+            min,
+            max,
+            saturation,
+        }
+    }
+
+    /// Creates a range without any bounds, leaving the values of the channel as they are.
+    pub fn unbounded() -> Self {
+        ValueRange::new(F::neg_infinity(), F::infinity(), Saturation::Clamp)
+    }
+
+    /// Checks whether the range is usable, returning a description of the problem if not.
+    fn validate(&self) -> Result<(), String> {
+        if self.min.is_nan() || self.max.is_nan() || self.min >= self.max {
+            return Err(format!(
+                "the lower bound ({}) must be less than the upper bound ({})",
+                self.min, self.max
+            ));
+        }
+        if self.saturation != Saturation::Clamp && !(self.min.is_finite() && self.max.is_finite()) {
+            return Err(format!(
+                "{:?} saturation requires finite bounds",
+                self.saturation
+            ));
+        }
+        Ok(())
+    }
+
+    /// Changes a single `value` by `change`, keeping it within the range.
+    fn saturate(&self, value: F, change: F) -> F {
+        if self.saturation == Saturation::Clamp {
+            return num_traits::clamp(value + change, self.min, self.max);
+        }
+        let value = num_traits::clamp(value, self.min, self.max);
+        let room = if change > F::zero() {
+            self.max - value
+        } else {
+            value - self.min
+        };
+        if room <= F::zero() || change == F::zero() {
+            return value;
+        }
+        let x = change / room;
+        let compressed = match self.saturation {
+            Saturation::Sigmoid => x / (F::one() + x * x).sqrt(),
+            _ => x.tanh(),
+        };
+        num_traits::clamp(value + room * compressed, self.min, self.max)
+    }
+}
+
+impl<F: LeniaFloat> Default for ValueRange<F> {
+    /// The range `0.0..1.0` of Lenia, clamped.
+    fn default() -> Self {
+        ValueRange::new(F::zero(), F::one(), Saturation::Clamp)
+    }
+}
+
+/// Stochastic terms of the update step, used to perturb a simulation.
+///
+/// Both terms are driven by a random number generator seeded with `seed`, making stochastic runs reproducible.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stochasticity<F: LeniaFloat = f64> {
+    /// Standard deviation of the gaussian noise over a unit of simulated time, drawn anew each iteration and
+    /// scaled by `1 / sqrt(dt)` on the deltas of every cell.
+    pub noise: F,
+    /// Probability of any single cell being updated in an iteration, the rest of the cells keep their values.
+    pub update_probability: F,
+    /// Seed of the random number generator.
+    pub seed: u64,
+}
+
+/// Roughly how many kernel elements a direct convolution can process per `log2` of the channel size
+/// while still being faster than an fft-based convolution.
+///
+/// Measured with `benches/convolution.rs`, where a full iteration breaks even between the two at
+/// about 2 to 3 kernel elements per `log2` of the channel size, for channels from `64x64` to `1024x1024`.
+const DIRECT_CONVOLUTION_MAX_COST: usize = 2;
+
 #[derive(Clone, Debug)]
 /// N-dimensional kernel.
 ///
 /// The `Kernel` struct holds the data of a specific kernel to be used for convolution in
 /// the Lenia simulation. It also implements the necessary conversions to normalize a
 /// kernel and prepare it for convolution using fast-fourier-transform.
-pub struct Kernel {
-    /// The original `ndarray::ArrayD<f64>` fom which the `Kernel` got made from
-    pub base: ndarray::ArrayD<f64>,
+pub struct Kernel<F: LeniaFloat = f64> {
+    /// The original `ndarray::ArrayD` fom which the `Kernel` got made from
+    pub base: ndarray::ArrayD<F>,
     /// The scaled down version of the base Kernel such that the sum of all the values of the kernel is `1.0`
-    pub normalized: ndarray::ArrayD<f64>,
+    pub normalized: ndarray::ArrayD<F>,
     /// Normalized kernel with its center shifted to the "top-right" corner and
     /// then re-sized to match the size of the `Lenia` instance channels
-    pub shifted: ndarray::ArrayD<f64>,
-    /// Fourier-transformed kernel
-    pub transformed: ndarray::ArrayD<Complex<f64>>,
+    /// (or of the padded channels, if any axis of the channels is not periodic)
+    pub shifted: ndarray::ArrayD<F>,
+    /// Fourier-transformed kernel.
+    ///
+    /// As the kernel is real-valued, only the non-redundant half of the spectrum is stored,
+    /// meaning that the last axis has a length of `n / 2 + 1` instead of `n`.
+    pub transformed: ndarray::ArrayD<Complex<F>>,
 }
 
-impl Kernel {
-    /// Creates a new Kernel struct from an n-dimensional array (`ndarray::ArrayD<f64>`).
+impl<F: LeniaFloat> Kernel<F> {
+    /// Creates a new Kernel struct from an n-dimensional array (`ndarray::ArrayD`).
     ///
     /// Creates the normalized version of the kernel.
     ///
@@ -985,37 +3232,88 @@ impl Kernel {
     /// * If the number of axes of the `kernel` and `channel_shape` are not equal.
     ///
     /// * If any of the corresponding axis lengths in `kernel` are greater than in `channel_shape`.
-    pub fn from(kernel: ndarray::ArrayD<f64>, channel_shape: &[usize]) -> Self {
+    ///
+    /// * If the elements of `kernel` sum up to `0.0` or to a non-finite value.
+    pub fn from(kernel: ndarray::ArrayD<F>, channel_shape: &[usize]) -> Self {
+        Self::from_padded(kernel, channel_shape, channel_shape)
+    }
+
+    /// Same as `from()`, but returns an error instead of panicking.
+    pub fn try_from(
+        kernel: ndarray::ArrayD<F>,
+        channel_shape: &[usize],
+    ) -> Result<Self, LeniaError> {
+        Self::try_from_padded(kernel, channel_shape, channel_shape)
+    }
+
+    /// Creates a new Kernel struct for convolving channels that get padded before the
+    /// fast-fourier-transform, as is done for axes that have a non-periodic `BoundaryCondition`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `kernel` - Base data for the kernel.
+    ///
+    /// * `channel_shape` - Shape of the channel the kernel is supposed to act on.
+    ///
+    /// * `fft_shape` - Shape of the channel after padding, the `shifted` and `transformed`
+    /// kernels are made to match it.
+    ///
+    /// ### Panics
+    ///
+    /// * If the number of axes of the `kernel`, `channel_shape` and `fft_shape` are not equal.
+    ///
+    /// * If any of the corresponding axis lengths in `kernel` are greater than in `channel_shape`.
+    ///
+    /// * If any of the corresponding axis lengths in `channel_shape` are greater than in `fft_shape`.
+    ///
+    /// * If the elements of `kernel` sum up to `0.0` or to a non-finite value.
+    pub fn from_padded(
+        kernel: ndarray::ArrayD<F>,
+        channel_shape: &[usize],
+        fft_shape: &[usize],
+    ) -> Self {
+        match Self::try_from_padded(kernel, channel_shape, fft_shape) {
+            Ok(kernel) => kernel,
+            Err(error) => panic!("Kernel::from_padded() - {}", error),
+        }
+    }
+
+    /// Same as `from_padded()`, but returns an error instead of panicking.
+    pub fn try_from_padded(
+        kernel: ndarray::ArrayD<F>,
+        channel_shape: &[usize],
+        fft_shape: &[usize],
+    ) -> Result<Self, LeniaError> {
         let mut normalized_kernel = kernel.clone();
-        let shifted_and_fft = ndarray::ArrayD::from_elem(channel_shape, Complex::new(0.0, 0.0));
 
         // Check for coherence in dimensionality and that the kernel is not
         // larger than the channel it is used to convolve with.
-        if normalized_kernel.shape().len() != shifted_and_fft.shape().len() {
-            panic!(
-                "Supplied kernel dimensionality does not match the supplied channel dimensionality!
-                \nkernel: {} dimensional vs. channel: {} dimensional",
-                normalized_kernel.shape().len(),
-                shifted_and_fft.shape().len()
-            );
+        if channel_shape.is_empty() {
+            return Err(LeniaError::InvalidShape(
+                "The channels must have at least 1 axis!".to_string(),
+            ));
         }
-        for (i, dim) in normalized_kernel.shape().iter().enumerate() {
-            if *dim > shifted_and_fft.shape()[i] {
-                panic!(
-                    "Supplied kernel is larger than the channel it acts on in axis {}!",
-                    i
-                );
-            }
+        check_shape(normalized_kernel.shape())?;
+        check_kernel_shape(normalized_kernel.shape(), channel_shape)?;
+        if fft_shape.len() != channel_shape.len()
+            || fft_shape.iter().zip(channel_shape).any(|(a, b)| a < b)
+        {
+            return Err(LeniaError::InvalidShape(
+                "The padded shape is smaller than the channel shape!".to_string(),
+            ));
         }
+        // From here on the kernel is laid out to match the padded channel.
+        let channel_shape = fft_shape;
 
         // Normalize the kernel
-        let scaler = 1.0 / normalized_kernel.sum();
+        check_kernel_sum(&normalized_kernel)?;
+        let scaler = F::one() / normalized_kernel.sum();
         for elem in &mut normalized_kernel {
             *elem *= scaler;
         }
 
         // Expand the kernel to match the size of the channel shape
-        let mut shifted = ndarray::ArrayD::from_elem(channel_shape, 0.0);
+        let mut shifted = ndarray::ArrayD::from_elem(channel_shape, F::zero());
 
         normalized_kernel.assign_to(shifted.slice_each_axis_mut(|a| Slice {
             start: (a.len / 2 - normalized_kernel.shape()[a.axis.index()] / 2) as isize,
@@ -1027,14 +3325,16 @@ impl Kernel {
             step: 1,
         }));
 
-        // Shift the kernel into the corner
+        // Shift the kernel into the corner, rolling each axis by half of its length
         for (i, axis) in channel_shape.iter().enumerate() {
+            let half = *axis as isize / 2;
+            let rest = *axis as isize - half;
             let mut shifted_buffer = shifted.clone();
             shifted
                 .slice_axis(
                     Axis(i),
                     Slice {
-                        start: -(*axis as isize / 2),
+                        start: half,
                         end: None,
                         step: 1,
                     },
@@ -1043,7 +3343,7 @@ impl Kernel {
                     Axis(i),
                     Slice {
                         start: 0,
-                        end: Some(*axis as isize / 2),
+                        end: Some(rest),
                         step: 1,
                     },
                 ));
@@ -1052,34 +3352,322 @@ impl Kernel {
                     Axis(i),
                     Slice {
                         start: 0,
-                        end: Some(*axis as isize / 2),
+                        end: Some(half),
                         step: 1,
                     },
                 )
                 .assign_to(shifted_buffer.slice_axis_mut(
                     Axis(i),
                     Slice {
-                        start: -(*axis as isize / 2),
+                        start: rest,
                         end: None,
                         step: 1,
                     },
                 ));
             shifted = shifted_buffer;
         }
-        let shifted_stored = shifted.clone();
 
         // Create the discrete-fourier-transformed representation of the kernel for fft-convolving.
-        let mut shifted_and_fft = shifted.mapv(|elem| Complex::new(elem, 0.0));
-
-        let mut fft_instance = fft::PlannedFFTND::new(&channel_shape, false);
-        fft_instance.transform(&mut shifted_and_fft);
+        let mut fft_instance = fft::PlannedFFTND::new_real(channel_shape, false);
+        let mut transformed = ndarray::ArrayD::from_elem(
+            fft_instance.spectrum_shape(),
+            Complex::new(F::zero(), F::zero()),
+        );
+        fft_instance.transform_real(&shifted, &mut transformed);
 
         // Create the kernel
-        Kernel {
+        Ok(Kernel {
             base: kernel,
             normalized: normalized_kernel,
-            shifted: shifted_stored,
-            transformed: shifted_and_fft,
+            shifted,
+            transformed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::ExpandedLenia;
+
+    /// A smooth bump in the middle of a `size` by `size` world.
+    fn bump(size: usize) -> ndarray::ArrayD<f64> {
+        let center = size as f64 / 2.0;
+        ndarray::ArrayD::from_shape_fn(vec![size, size], |index| {
+            let dx = (index[0] as f64 - center) / 6.0;
+            let dy = (index[1] as f64 - center) / 6.0;
+            (-(dx * dx + dy * dy)).exp()
+        })
+    }
+
+    fn adaptive(size: usize) -> Simulator<ExpandedLenia> {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[size, size]);
+        simulator.set_kernel(kernels::gaussian_donut_2d(8, 0.15), 0);
+        simulator.set_growth_function(growth_functions::StandardLenia::new(0.15, 0.015), 0);
+        simulator.set_value_range(0, ValueRange::unbounded());
+        simulator.fill_channel(&bump(size), 0);
+        simulator.set_dt(0.5);
+        simulator
+    }
+
+    #[test]
+    fn adaptive_steps_meet_the_tolerance() {
+        let tolerance = 1e-4;
+        let mut simulator = adaptive(32);
+        simulator.set_adaptive_dt(tolerance, 1e-3, 0.5);
+        for _ in 0..5 {
+            let mut reference = adaptive(32);
+            reference.fill_channel(simulator.get_channel_as_ref(0), 0);
+            let time = simulator.time();
+            simulator.iterate();
+            let dt = simulator.time() - time;
+            assert!(dt < 0.5, "the first step should have been rejected");
+
+            // The accepted step against the same step taken in 100 tiny fixed steps.
+            reference.set_dt(dt / 100.0);
+            for _ in 0..100 {
+                reference.iterate();
+            }
+            let error = simulator
+                .get_channel_as_ref(0)
+                .iter()
+                .zip(reference.get_channel_as_ref(0))
+                .fold(0.0f64, |error, (a, b)| error.max((a - b).abs()));
+            assert!(
+                error <= 2.0 * tolerance,
+                "error {} of a step of {} exceeds the tolerance",
+                error,
+                dt
+            );
+        }
+    }
+
+    #[test]
+    fn time_advances_by_the_accepted_steps() {
+        let mut simulator = adaptive(32);
+        simulator.set_adaptive_dt(1e-4, 1e-3, 0.5);
+        let mut fixed = adaptive(32);
+        for _ in 0..10 {
+            let time = simulator.time();
+            simulator.iterate();
+            let dt = simulator.time() - time;
+            assert!((1e-3..=0.5).contains(&dt));
+
+            // The accepted result is made of two half-steps of the accepted dt.
+            fixed.set_dt(dt / 2.0);
+            fixed.iterate();
+            fixed.iterate();
+            let difference = fixed
+                .get_channel_as_ref(0)
+                .iter()
+                .zip(simulator.get_channel_as_ref(0))
+                .fold(0.0f64, |difference, (a, b)| difference.max((a - b).abs()));
+            assert!(
+                difference < 1e-12,
+                "step of {} differs by {}",
+                dt,
+                difference
+            );
+            fixed.fill_channel(simulator.get_channel_as_ref(0), 0);
+        }
+        assert_eq!(simulator.steps(), 10);
+    }
+
+    #[test]
+    fn adaptive_steps_end_when_the_simulation_blows_up() {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[16, 16]);
+        simulator.set_growth_function(|_: f64| f64::NAN, 0);
+        simulator.set_value_range(0, ValueRange::unbounded());
+        simulator.set_adaptive_dt(1e-4, 1e-3, 0.5);
+        simulator.iterate();
+        assert_eq!(simulator.time(), 1e-3);
+        simulator.iterate();
+        assert_eq!(simulator.time(), 2e-3);
+    }
+
+    #[test]
+    fn adaptive_steps_exclude_stochastic_terms() {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[16, 16]);
+        simulator.set_adaptive_dt(1e-4, 1e-3, 0.5);
+        assert!(matches!(
+            simulator.try_set_stochasticity(0.1, 1.0, 7),
+            Err(LeniaError::Unsupported(_))
+        ));
+        simulator.disable_adaptive_dt();
+        simulator.set_stochasticity(0.1, 1.0, 7);
+        assert!(matches!(
+            simulator.try_set_adaptive_dt(1e-4, 1e-3, 0.5),
+            Err(LeniaError::Unsupported(_))
+        ));
+    }
+
+    /// Error at time `1.0` of a uniform channel decaying as `dA/dt = -A`, integrated in `steps` steps.
+    fn decay_error(integrator: Integrator, steps: usize) -> f64 {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[16, 16]);
+        simulator.set_kernel(kernels::gaussian_donut_2d(4, 0.15), 0);
+        simulator.set_growth_function(growth_functions::Pass::new(-1.0), 0);
+        simulator.set_value_range(0, ValueRange::unbounded());
+        simulator.set_integrator(integrator);
+        simulator.set_dt(1.0 / steps as f64);
+        simulator.fill_channel(&ndarray::ArrayD::from_elem(vec![16, 16], 0.5), 0);
+        for _ in 0..steps {
+            simulator.iterate();
+        }
+        let exact = 0.5 * (-1.0f64).exp();
+        simulator
+            .get_channel_as_ref(0)
+            .iter()
+            .map(|value| (value - exact).abs())
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn integrators_converge_at_their_order() {
+        for integrator in [
+            Integrator::Euler,
+            Integrator::Midpoint,
+            Integrator::Heun,
+            Integrator::RungeKutta4,
+        ] {
+            let order = (decay_error(integrator, 10) / decay_error(integrator, 20)).log2();
+            assert!(
+                (order - integrator.order() as f64).abs() < 0.15,
+                "{:?} converges at order {}",
+                integrator,
+                order
+            );
+        }
+    }
+
+    #[test]
+    fn asymptotic_channels_approach_their_target() {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[16, 16]);
+        simulator.set_kernel(kernels::gaussian_donut_2d(4, 0.15), 0);
+        simulator.set_growth_function(|_: f64| 1.5, 0);
+        simulator.set_update_mode(0, UpdateMode::Asymptotic);
+        simulator.set_dt(0.1);
+        let mut previous = 0.0;
+        for step in 1..=60 {
+            simulator.iterate();
+            let expected = 1.5 * (1.0 - 0.9f64.powi(step));
+            for value in simulator.get_channel_as_ref(0) {
+                assert!(
+                    (value - expected).abs() < 1e-12,
+                    "{} != {}",
+                    value,
+                    expected
+                );
+            }
+            let value = simulator.get_channel_as_ref(0)[[0, 0]];
+            assert!(value > previous && value < 1.5);
+            previous = value;
+        }
+        assert!(1.5 - previous < 0.01);
+    }
+
+    #[test]
+    fn parameter_maps_apply_to_their_own_convolution_channel() {
+        let shape = [32, 32];
+        let mut simulator = Simulator::<ExpandedLenia>::new(&shape);
+        simulator.set_convolution_channels(2);
+        for convolution_channel in 0..2 {
+            simulator.set_convolution_channel_source(convolution_channel, 0);
+            simulator.set_kernel(kernels::gaussian_donut_2d(4, 0.15), convolution_channel);
+            simulator.set_growth_function(
+                growth_functions::StandardLenia::new(0.15, 0.015),
+                convolution_channel,
+            );
+        }
+        simulator.set_weights(0, &[1.0, 1.0]);
+        let sigma =
+            ndarray::ArrayD::from_shape_fn(shape.to_vec(), |index| 0.01 + 0.002 * index[0] as f64);
+        let mu =
+            ndarray::ArrayD::from_shape_fn(
+                shape.to_vec(),
+                |index| {
+                    if index[1] < 16 {
+                        0.1
+                    } else {
+                        0.3
+                    }
+                },
+            );
+        simulator.set_growth_parameter_map(0, "sigma", sigma.clone());
+        simulator.set_growth_parameter_map(1, "mu", mu.clone());
+        simulator.fill_channel(&test_util::field(&shape, 5), 0);
+        simulator.iterate();
+
+        let growth = growth_functions::StandardLenia::new(0.15, 0.015);
+        let expected = [
+            ndarray::Zip::from(simulator.get_convolved_as_ref(0))
+                .and(&sigma)
+                .map_collect(|&value, &sigma| growth.growth_with_parameters(value, &[0.15, sigma])),
+            ndarray::Zip::from(simulator.get_convolved_as_ref(1))
+                .and(&mu)
+                .map_collect(|&value, &mu| growth.growth_with_parameters(value, &[mu, 0.015])),
+        ];
+        for (convolution_channel, expected) in expected.iter().enumerate() {
+            assert_eq!(
+                simulator.get_activated_as_ref(convolution_channel),
+                expected
+            );
+        }
+        assert_ne!(expected[0], expected[1]);
+
+        simulator.clear_growth_parameter_map(1, "mu");
+        simulator.iterate();
+        let uniform = simulator
+            .get_convolved_as_ref(1)
+            .mapv(|value| growth.growth(value));
+        assert_eq!(simulator.get_activated_as_ref(1), &uniform);
+    }
+
+    fn noisy(seed: u64) -> Simulator<ExpandedLenia> {
+        let mut simulator = adaptive(32);
+        simulator.set_value_range(0, ValueRange::default());
+        simulator.set_dt(0.1);
+        simulator.set_stochasticity(0.05, 0.5, seed);
+        simulator
+    }
+
+    #[test]
+    fn stochastic_runs_depend_only_on_their_seed() {
+        let mut simulators = [noisy(7), noisy(7), noisy(8)];
+        for _ in 0..5 {
+            for simulator in simulators.iter_mut() {
+                simulator.iterate();
+            }
+        }
+        let bits = |simulator: &Simulator<ExpandedLenia>| -> Vec<u64> {
+            simulator
+                .get_channel_as_ref(0)
+                .iter()
+                .map(|value| value.to_bits())
+                .collect()
+        };
+        assert_eq!(bits(&simulators[0]), bits(&simulators[1]));
+        assert_ne!(bits(&simulators[0]), bits(&simulators[2]));
+    }
+
+    #[test]
+    fn noise_spreads_with_the_square_root_of_time() {
+        for (dt, steps) in [(0.1, 10), (0.01, 100)] {
+            let mut simulator = Simulator::<ExpandedLenia>::new(&[64, 64]);
+            simulator.set_weights(0, &[0.0]);
+            simulator.set_value_range(0, ValueRange::unbounded());
+            simulator.set_dt(dt);
+            simulator.set_stochasticity(0.5, 1.0, 3);
+            for _ in 0..steps {
+                simulator.iterate();
+            }
+            let channel = simulator.get_channel_as_ref(0);
+            let mean = channel.mean().unwrap();
+            let deviation = channel
+                .mapv(|value| (value - mean).powi(2))
+                .mean()
+                .unwrap()
+                .sqrt();
+            assert!((deviation - 0.5).abs() < 0.025, "dt {}: {}", dt, deviation);
         }
     }
 }
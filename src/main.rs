@@ -2,12 +2,12 @@
 
 use ggez::event;
 use ggez::event::EventHandler;
-use ggez::graphics::{Canvas, Color, DrawMode, DrawParam, Mesh, MeshBuilder, Rect};
+use ggez::graphics::{Canvas, Color, DrawParam, Image, ImageFormat, Sampler};
 use ggez::{Context, ContextBuilder, GameResult};
-use ndarray::Array2;
-use rand::{thread_rng, Rng};
+use ndarray::{Array2, Ix2};
 use road_to_lenia::lenias::StandardLenia;
-use road_to_lenia::{self, load_from_png, Lenia, Simulator};
+use road_to_lenia::rendering::{self, Alpha};
+use road_to_lenia::{self, load_from_png, Lenia, NewLenia, Normalization, Simulator};
 
 #[macro_use]
 extern crate ndarray;
@@ -19,10 +19,15 @@ struct MainState<L: Lenia> {
     game: Simulator<L>,
 }
 
-impl<L: Lenia> MainState<L> {
+impl<L: NewLenia> MainState<L> {
     pub fn new(screen_size: f32, fps: u32, shape: usize, initial_state: Array2<f64>) -> Self {
         let mut game = Simulator::<L>::new(&[shape, shape]);
-        game.fill_channel(&initial_state.into_dyn(), 0);
+        game.fill_channel(
+            &initial_state
+                .mapv(|x| num_traits::cast(x).unwrap())
+                .into_dyn(),
+            0,
+        );
         MainState {
             game,
             shape,
@@ -42,28 +47,28 @@ impl<L: Lenia> EventHandler for MainState<L> {
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
         let mut canvas = Canvas::from_frame(ctx, Color::BLACK);
-        let mut builder = MeshBuilder::new();
+        canvas.set_sampler(Sampler::nearest_clamp());
 
         let cells = self.game.get_channel_as_ref(0);
         let cell_size = self.screen_size / self.shape as f32;
 
-        // Draw cells
-        cells
-            .iter()
-            .enumerate()
-            .filter(|(_, &x)| x > 0.)
-            .for_each(|(i, &x)| {
-                let pos_x = (i % self.shape) as f32;
-                let pos_y = (i / self.shape) as f32;
-                let color = Color::new(0., 1., 0., x as f32); // Green
-                let draw_mode = DrawMode::fill();
-                let rect = Rect::new(pos_x * cell_size, pos_y * cell_size, cell_size, cell_size);
-                builder.rectangle(draw_mode, rect, color).unwrap();
-            });
-
-        let mesh = builder.build();
-        let mesh = Mesh::from_data(ctx, mesh);
-        canvas.draw(&mesh, DrawParam::default());
+        // Draw cells in green, the second axis running along the width of the window
+        let frame = cells.view().into_dimensionality::<Ix2>().unwrap();
+        let image = rendering::composite(
+            &[frame.t()],
+            &[[0.0, 1.0, 0.0]],
+            Normalization::None,
+            Alpha::Opaque,
+        )
+        .unwrap();
+        let image = Image::from_pixels(
+            ctx,
+            image.rgba(),
+            ImageFormat::Rgba8UnormSrgb,
+            image.width() as u32,
+            image.height() as u32,
+        );
+        canvas.draw(&image, DrawParam::default().scale([cell_size, cell_size]));
         canvas.finish(ctx)
     }
 }
@@ -73,9 +78,9 @@ fn main() -> GameResult {
     let fps = 60;
     let shape = 300;
 
-    // let rng = &mut thread_rng();
+    // let rng = &mut rand::thread_rng();
     let mut initial_state = Array2::<f64>::zeros([shape, shape]);
-    // initial_state.map_mut(|x| *x = rng.gen::<f64>());
+    // initial_state.map_mut(|x| *x = rand::Rng::gen::<f64>(rng));
 
     let glider = load_from_png("./images/glider.png");
     initial_state.slice_mut(s![..100, ..100]).assign(&glider);
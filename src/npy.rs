@@ -0,0 +1,661 @@
+//! Reading and writing n-dimensional arrays in the NumPy `.npy` and `.npz` formats.
+//!
+//! Unlike the 8 and 16 bit PNGs of `export_frame_as_png()`, the arrays are stored at full precision and
+//! with any number of dimensions, such that channels, kernels and the intermediate results of a simulation can be
+//! analyzed with `numpy.load()`. An `.npz` file is a zip archive of `.npy` files, and holds a whole snapshot of a
+//! `Simulator` with `Simulator::export_npz()`.
+//!
+//! Arrays are written as little-endian `f4` or `f8`, the precision they were simulated at. Arrays of any
+//! floating-point, integer or boolean type can be read, in C or Fortran order, and are converted to the
+//! floating-point type of the simulation.
+
+use super::*;
+use ndarray::ShapeBuilder;
+use std::collections::BTreeMap;
+use std::io::{BufReader, BufWriter, Read, Seek, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of every `.npy` file.
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+
+/// The header of a `.npy` file is padded such that the data starts at a multiple of this many bytes.
+const HEADER_ALIGNMENT: usize = 64;
+
+/// Largest number of bytes allocated up front when reading an array, such that a corrupted shape
+/// fails on the end of the file instead of on allocation.
+const MAX_PREALLOCATION: usize = 1 << 24;
+
+/// Write an array in the `.npy` format.
+///
+/// ### Parameters
+///
+/// * `writer` - Where to write the array to.
+///
+/// * `array` - The array to write, at the precision of `F`.
+pub fn write_npy<F: LeniaFloat, W: Write>(
+    writer: &mut W,
+    array: &ndarray::ArrayD<F>,
+) -> Result<(), LeniaError> {
+    let size = std::mem::size_of::<F>();
+    let shape = match array.shape() {
+        [len] => format!("({},)", len),
+        shape => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|len| len.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '<f{}', 'fortran_order': False, 'shape': {}, }}",
+        size, shape
+    );
+    // Version 1.0 stores the length of the header in 2 bytes, version 2.0 in 4 bytes.
+    let (version, preamble): (u8, usize) = if header.len() + HEADER_ALIGNMENT < 1 << 16 {
+        (1, MAGIC.len() + 4)
+    } else {
+        (2, MAGIC.len() + 6)
+    };
+    let padding =
+        (HEADER_ALIGNMENT - (preamble + header.len() + 1) % HEADER_ALIGNMENT) % HEADER_ALIGNMENT;
+    header.push_str(&" ".repeat(padding));
+    header.push('\n');
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[version, 0])?;
+    if version == 1 {
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    } else {
+        writer.write_all(&(header.len() as u32).to_le_bytes())?;
+    }
+    writer.write_all(header.as_bytes())?;
+    for value in array.iter() {
+        if size == 4 {
+            writer.write_all(&value.to_f32().unwrap().to_le_bytes())?;
+        } else {
+            writer.write_all(&value.to_f64().unwrap().to_le_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// Read an array in the `.npy` format.
+///
+/// ### Parameters
+///
+/// * `reader` - Where to read the array from.
+///
+/// ### Errors
+///
+/// * `LeniaError::Format` if the array is not a valid `.npy` array of a floating-point, integer or boolean type.
+pub fn read_npy<F: LeniaFloat, R: Read>(reader: &mut R) -> Result<ndarray::ArrayD<F>, LeniaError> {
+    let mut preamble = [0; 8];
+    read_exact(reader, &mut preamble)?;
+    if &preamble[..6] != MAGIC {
+        return Err(LeniaError::Format(
+            "Not a .npy array, the magic bytes are missing.".to_string(),
+        ));
+    }
+    let header_len = match preamble[6] {
+        1 => {
+            let mut len = [0; 2];
+            read_exact(reader, &mut len)?;
+            u16::from_le_bytes(len) as usize
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            read_exact(reader, &mut len)?;
+            u32::from_le_bytes(len) as usize
+        }
+        version => {
+            return Err(LeniaError::Format(format!(
+                "Unsupported .npy format version {}.",
+                version
+            )))
+        }
+    };
+    let mut header = Vec::with_capacity(header_len.min(MAX_PREALLOCATION));
+    reader.take(header_len as u64).read_to_end(&mut header)?;
+    if header.len() != header_len {
+        return Err(truncated());
+    }
+    let header = String::from_utf8(header)
+        .map_err(|_| LeniaError::Format("The .npy header is not valid text.".to_string()))?;
+    let dtype = DataType::parse(header_value(&header, "descr")?)?;
+    let fortran_order = match header_value(&header, "fortran_order")? {
+        "False" => false,
+        "True" => true,
+        value => {
+            return Err(LeniaError::Format(format!(
+                "Invalid fortran_order {} in the .npy header.",
+                value
+            )))
+        }
+    };
+    let shape = parse_shape(header_value(&header, "shape")?)?;
+    let len = shape
+        .iter()
+        .try_fold(1_usize, |len, axis| len.checked_mul(*axis))
+        .and_then(|len| len.checked_mul(dtype.size))
+        .ok_or_else(|| {
+            LeniaError::Format("The .npy array is too large for this platform.".to_string())
+        })?;
+    let mut data = Vec::with_capacity(len.min(MAX_PREALLOCATION));
+    reader.take(len as u64).read_to_end(&mut data)?;
+    if data.len() != len {
+        return Err(truncated());
+    }
+    let values: Vec<F> = data
+        .chunks_exact(dtype.size)
+        .map(|bytes| dtype.value(bytes))
+        .collect();
+    let array = if fortran_order {
+        ndarray::ArrayD::from_shape_vec(ndarray::IxDyn(&shape).f(), values)
+    } else {
+        ndarray::ArrayD::from_shape_vec(ndarray::IxDyn(&shape), values)
+    };
+    array.map_err(|error| LeniaError::Format(format!("Invalid .npy array: {}.", error)))
+}
+
+/// Write an array to a `.npy` file, see `write_npy()`.
+///
+/// ### Parameters
+///
+/// * `file_path` - Path to the `.npy` file, which is overwritten if it exists.
+///
+/// * `array` - The array to write, at the precision of `F`.
+pub fn save_npy<F: LeniaFloat, P: AsRef<Path>>(
+    file_path: P,
+    array: &ndarray::ArrayD<F>,
+) -> Result<(), LeniaError> {
+    let mut file = BufWriter::new(std::fs::File::create(file_path)?);
+    write_npy(&mut file, array)?;
+    file.flush()?;
+    Ok(())
+}
+
+/// Read an array from a `.npy` file, see `read_npy()`.
+///
+/// ### Parameters
+///
+/// * `file_path` - Path to the `.npy` file.
+pub fn load_npy<F: LeniaFloat, P: AsRef<Path>>(
+    file_path: P,
+) -> Result<ndarray::ArrayD<F>, LeniaError> {
+    read_npy(&mut BufReader::new(std::fs::File::open(file_path)?))
+}
+
+/// Write named arrays into an `.npz` archive, like `numpy.savez()`.
+///
+/// ### Parameters
+///
+/// * `writer` - Where to write the archive to.
+///
+/// * `arrays` - The arrays to write along with their names, which `numpy.load()` gives them by.
+///
+/// * `compressed` - Whether to compress the arrays, like `numpy.savez_compressed()`.
+pub fn write_npz<F: LeniaFloat, W: Write + Seek>(
+    writer: W,
+    arrays: &[(&str, &ndarray::ArrayD<F>)],
+    compressed: bool,
+) -> Result<(), LeniaError> {
+    let mut archive = zip::ZipWriter::new(writer);
+    for (name, array) in arrays {
+        let options = zip::write::FileOptions::default()
+            .compression_method(if compressed {
+                zip::CompressionMethod::Deflated
+            } else {
+                zip::CompressionMethod::Stored
+            })
+            .large_file(array.len() * std::mem::size_of::<F>() >= u32::MAX as usize);
+        archive
+            .start_file(format!("{}.npy", name), options)
+            .map_err(zip_error)?;
+        write_npy(&mut archive, array)?;
+    }
+    archive.finish().map_err(zip_error)?;
+    Ok(())
+}
+
+/// Read every array of an `.npz` archive, by their names.
+///
+/// ### Parameters
+///
+/// * `reader` - Where to read the archive from.
+///
+/// ### Errors
+///
+/// * `LeniaError::Format` if the archive or any array in it is not valid.
+pub fn read_npz<F: LeniaFloat, R: Read + Seek>(
+    reader: R,
+) -> Result<BTreeMap<String, ndarray::ArrayD<F>>, LeniaError> {
+    let mut archive = zip::ZipArchive::new(reader).map_err(zip_error)?;
+    let mut arrays = BTreeMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(zip_error)?;
+        let name = file.name().trim_end_matches(".npy").to_string();
+        arrays.insert(name, read_npy(&mut file)?);
+    }
+    Ok(arrays)
+}
+
+/// Write named arrays to an `.npz` file, see `write_npz()`.
+///
+/// ### Parameters
+///
+/// * `file_path` - Path to the `.npz` file, which is overwritten if it exists.
+///
+/// * `arrays` - The arrays to write along with their names.
+///
+/// * `compressed` - Whether to compress the arrays.
+pub fn save_npz<F: LeniaFloat, P: AsRef<Path>>(
+    file_path: P,
+    arrays: &[(&str, &ndarray::ArrayD<F>)],
+    compressed: bool,
+) -> Result<(), LeniaError> {
+    write_npz(
+        BufWriter::new(std::fs::File::create(file_path)?),
+        arrays,
+        compressed,
+    )
+}
+
+/// Read every array of an `.npz` file, see `read_npz()`.
+///
+/// ### Parameters
+///
+/// * `file_path` - Path to the `.npz` file.
+pub fn load_npz<F: LeniaFloat, P: AsRef<Path>>(
+    file_path: P,
+) -> Result<BTreeMap<String, ndarray::ArrayD<F>>, LeniaError> {
+    read_npz(BufReader::new(std::fs::File::open(file_path)?))
+}
+
+/// Element type of a `.npy` array.
+struct DataType {
+    kind: char,
+    size: usize,
+    big_endian: bool,
+}
+
+impl DataType {
+    /// Parse a type description such as `'<f8'`.
+    fn parse(descr: &str) -> Result<Self, LeniaError> {
+        let invalid = || {
+            LeniaError::Format(format!(
+                "Unsupported element type {} of the .npy array, only floating-point, integer and boolean arrays can be read.",
+                descr
+            ))
+        };
+        let descr = descr.trim_matches(|c| c == '\'' || c == '"');
+        let mut chars = descr.chars();
+        let big_endian = match chars.next() {
+            Some('<') => false,
+            Some('>') => true,
+            Some('|') | Some('=') => cfg!(target_endian = "big"),
+            _ => return Err(invalid()),
+        };
+        let kind = chars.next().ok_or_else(invalid)?;
+        let size: usize = chars.as_str().parse().map_err(|_| invalid())?;
+        match (kind, size) {
+            ('f', 4)
+            | ('f', 8)
+            | ('i', 1)
+            | ('i', 2)
+            | ('i', 4)
+            | ('i', 8)
+            | ('u', 1)
+            | ('u', 2)
+            | ('u', 4)
+            | ('u', 8)
+            | ('b', 1) => Ok(DataType {
+                kind,
+                size,
+                big_endian,
+            }),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Convert a single element to `F`.
+    fn value<F: LeniaFloat>(&self, bytes: &[u8]) -> F {
+        let mut buffer = [0; 8];
+        buffer[..self.size].copy_from_slice(bytes);
+        if self.big_endian {
+            buffer[..self.size].reverse();
+        }
+        let value: Option<F> = match (self.kind, self.size) {
+            ('f', 4) => F::from(f32::from_le_bytes([
+                buffer[0], buffer[1], buffer[2], buffer[3],
+            ])),
+            ('f', _) => F::from(f64::from_le_bytes(buffer)),
+            ('i', size) => {
+                // Shift the value to the top of the buffer and back, to extend its sign.
+                let shift = 64 - 8 * size as u32;
+                F::from((i64::from_le_bytes(buffer) << shift) >> shift)
+            }
+            (_, _) => F::from(u64::from_le_bytes(buffer)),
+        };
+        value.unwrap_or_else(F::nan)
+    }
+}
+
+/// Find the value of `key` in the python dictionary of a `.npy` header.
+fn header_value<'a>(header: &'a str, key: &str) -> Result<&'a str, LeniaError> {
+    let missing = || LeniaError::Format(format!("The .npy header has no {}.", key));
+    let start = [format!("'{}'", key), format!("\"{}\"", key)]
+        .iter()
+        .find_map(|quoted| {
+            header
+                .find(quoted.as_str())
+                .map(|start| start + quoted.len())
+        })
+        .ok_or_else(missing)?;
+    let value = header[start..].trim_start();
+    let value = value.strip_prefix(':').ok_or_else(missing)?.trim_start();
+    let end = if value.starts_with('(') {
+        value.find(')').map(|end| end + 1)
+    } else {
+        value.find(|c| c == ',' || c == '}')
+    };
+    Ok(value[..end.ok_or_else(missing)?].trim())
+}
+
+/// Parse a shape written as a python tuple, such as `(3, 4)`.
+fn parse_shape(shape: &str) -> Result<Vec<usize>, LeniaError> {
+    shape
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .split(',')
+        .map(|len| len.trim())
+        .filter(|len| !len.is_empty())
+        .map(|len| {
+            len.trim_end_matches('L').parse().map_err(|_| {
+                LeniaError::Format(format!("Invalid shape {} in the .npy header.", shape))
+            })
+        })
+        .collect()
+}
+
+fn read_exact<R: Read>(reader: &mut R, bytes: &mut [u8]) -> Result<(), LeniaError> {
+    reader.read_exact(bytes).map_err(|error| {
+        if error.kind() == std::io::ErrorKind::UnexpectedEof {
+            truncated()
+        } else {
+            LeniaError::Io(error)
+        }
+    })
+}
+
+fn truncated() -> LeniaError {
+    LeniaError::Format("The .npy array ends unexpectedly, it may be truncated.".to_string())
+}
+
+fn zip_error(error: zip::result::ZipError) -> LeniaError {
+    match error {
+        zip::result::ZipError::Io(error) => LeniaError::Io(error),
+        error => LeniaError::Format(format!("Invalid .npz archive: {}.", error)),
+    }
+}
+
+impl<L: Lenia> Simulator<L> {
+    /// Write a snapshot of the simulation to an `.npz` file, for analysis with `numpy.load()`.
+    ///
+    /// The snapshot holds the arrays `channel_{i}` and `deltas_{i}` of each channel, `kernel_{i}` (the kernel base),
+    /// `convolved_{i}` and `activated_{i}` of each convolution channel, and the scalar `time`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the `.npz` file, which is overwritten if it exists.
+    ///
+    /// * `compressed` - Whether to compress the arrays.
+    pub fn export_npz<P: AsRef<Path>>(
+        &self,
+        file_path: P,
+        compressed: bool,
+    ) -> Result<(), LeniaError> {
+        let mut names = Vec::new();
+        let mut arrays = Vec::new();
+        for i in 0..self.channels() {
+            names.push(format!("channel_{}", i));
+            arrays.push(self.get_channel_as_ref(i));
+            names.push(format!("deltas_{}", i));
+            arrays.push(self.get_deltas_as_ref(i));
+        }
+        for i in 0..self.convolution_channels() {
+            names.push(format!("kernel_{}", i));
+            arrays.push(&self.get_kernel_as_ref(i).base);
+            names.push(format!("convolved_{}", i));
+            arrays.push(self.get_convolved_as_ref(i));
+            names.push(format!("activated_{}", i));
+            arrays.push(self.get_activated_as_ref(i));
+        }
+        let time = ndarray::arr0(self.time()).into_dyn();
+        names.push("time".to_string());
+        arrays.push(&time);
+        let named: Vec<(&str, &ndarray::ArrayD<L::Float>)> =
+            names.iter().map(|name| name.as_str()).zip(arrays).collect();
+        save_npz(file_path, &named, compressed)
+    }
+
+    /// Fill the channels of the simulation from the `channel_{i}` arrays of an `.npz` file, such as one written
+    /// by `export_npz()` or by `numpy.savez()`.
+    ///
+    /// Also sets the time of the simulation if the file holds a scalar `time`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the `.npz` file.
+    ///
+    /// ### Errors
+    ///
+    /// * `LeniaError::Format` if the file does not hold an array for every channel.
+    ///
+    /// * `LeniaError::ShapeMismatch` if an array does not have the shape of the channels.
+    pub fn import_npz<P: AsRef<Path>>(&mut self, file_path: P) -> Result<(), LeniaError> {
+        let arrays = load_npz::<L::Float, _>(file_path)?;
+        for i in 0..self.channels() {
+            let name = format!("channel_{}", i);
+            let channel = arrays.get(&name).ok_or_else(|| {
+                LeniaError::Format(format!("The .npz file has no array {}.", name))
+            })?;
+            self.try_fill_channel(channel, i)?;
+        }
+        if let Some(time) = arrays.get("time") {
+            if time.ndim() == 0 {
+                self.set_time(time[[]]);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::ExpandedLenia;
+    use std::io::Cursor;
+
+    /// A version 1.0 `.npy` array with the given header dictionary and raw data.
+    fn npy(header: &str, data: &[u8]) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend_from_slice(&[1, 0]);
+        bytes.extend_from_slice(&(header.len() as u16 + 1).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.push(b'\n');
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    fn round_trip<F: LeniaFloat>(array: &ndarray::ArrayD<F>) {
+        let mut bytes = Vec::new();
+        write_npy(&mut bytes, array).unwrap();
+        let data_len = array.len() * std::mem::size_of::<F>();
+        let header_end = bytes.len() - data_len;
+        assert_eq!(header_end % HEADER_ALIGNMENT, 0);
+        assert_eq!(bytes[header_end - 1], b'\n');
+        let read: ndarray::ArrayD<F> = read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(&read, array);
+    }
+
+    #[test]
+    fn arrays_round_trip_through_npy() {
+        round_trip(&ndarray::arr0(0.5_f64).into_dyn());
+        round_trip(&ndarray::Array::linspace(-1.0_f32, 1.0, 7).into_dyn());
+        round_trip(&ndarray::ArrayD::from_shape_fn(vec![3, 4, 5], |index| {
+            (index[0] * 20 + index[1] * 5 + index[2]) as f64 / 7.0
+        }));
+        round_trip(&ndarray::ArrayD::from_shape_fn(vec![2, 3, 4, 5], |index| {
+            (index[0] * 60 + index[1] * 20 + index[2] * 5 + index[3]) as f32 / 3.0
+        }));
+    }
+
+    #[test]
+    fn reads_fortran_order() {
+        let data: Vec<u8> = [1.0_f64, 4.0, 2.0, 5.0, 3.0, 6.0]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let bytes = npy(
+            "{'descr': '<f8', 'fortran_order': True, 'shape': (2, 3), }",
+            &data,
+        );
+        let array: ndarray::ArrayD<f64> = read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(
+            array,
+            ndarray::arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]).into_dyn()
+        );
+    }
+
+    #[test]
+    fn reads_big_endian_and_integer_arrays() {
+        let data: Vec<u8> = [1.5_f32, -2.0]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        let bytes = npy(
+            "{'descr': '>f4', 'fortran_order': False, 'shape': (2,), }",
+            &data,
+        );
+        let array: ndarray::ArrayD<f64> = read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(array.as_slice().unwrap(), [1.5, -2.0]);
+
+        let data: Vec<u8> = [-300_i16, 7]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+        let bytes = npy(
+            "{'descr': '>i2', 'fortran_order': False, 'shape': (2,), }",
+            &data,
+        );
+        let array: ndarray::ArrayD<f64> = read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(array.as_slice().unwrap(), [-300.0, 7.0]);
+
+        let data: Vec<u8> = [-5_i64, 1 << 40]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let bytes = npy(
+            "{'descr': '<i8', 'fortran_order': False, 'shape': (2,), }",
+            &data,
+        );
+        let array: ndarray::ArrayD<f64> = read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(array.as_slice().unwrap(), [-5.0, (1_u64 << 40) as f64]);
+
+        let bytes = npy(
+            "{'descr': '|u1', 'fortran_order': False, 'shape': (2, 2), }",
+            &[0, 255, 128, 1],
+        );
+        let array: ndarray::ArrayD<f32> = read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(
+            array,
+            ndarray::arr2(&[[0.0, 255.0], [128.0, 1.0]]).into_dyn()
+        );
+
+        let bytes = npy(
+            "{'descr': '|b1', 'fortran_order': False, 'shape': (3,), }",
+            &[1, 0, 1],
+        );
+        let array: ndarray::ArrayD<f64> = read_npy(&mut bytes.as_slice()).unwrap();
+        assert_eq!(array.as_slice().unwrap(), [1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn invalid_arrays_are_an_error() {
+        let header = "{'descr': '<f8', 'fortran_order': False, 'shape': (2,), }";
+        for bytes in [
+            npy(header, &[0; 15]),
+            npy(header, &[])[..20].to_vec(),
+            npy(
+                "{'descr': '<c16', 'fortran_order': False, 'shape': (2,), }",
+                &[0; 32],
+            ),
+            npy("{'descr': '<f8', 'shape': (2,), }", &[0; 16]),
+            b"PK\x03\x04".to_vec(),
+        ] {
+            assert!(matches!(
+                read_npy::<f64, _>(&mut bytes.as_slice()),
+                Err(LeniaError::Format(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn arrays_round_trip_through_npz() {
+        let channel = ndarray::ArrayD::from_shape_fn(vec![6, 7], |index| {
+            (index[0] * 7 + index[1]) as f64 / 42.0
+        });
+        let kernel = ndarray::ArrayD::from_elem(vec![3, 3, 3], 0.25);
+        let time = ndarray::arr0(1.5).into_dyn();
+        for compressed in [false, true] {
+            let mut archive = Cursor::new(Vec::new());
+            write_npz(
+                &mut archive,
+                &[("channel", &channel), ("kernel", &kernel), ("time", &time)],
+                compressed,
+            )
+            .unwrap();
+            archive.set_position(0);
+            let arrays = read_npz::<f64, _>(archive).unwrap();
+            assert_eq!(
+                arrays.keys().collect::<Vec<&String>>(),
+                ["channel", "kernel", "time"]
+            );
+            assert_eq!(arrays["channel"], channel);
+            assert_eq!(arrays["kernel"], kernel);
+            assert_eq!(arrays["time"], time);
+        }
+    }
+
+    #[test]
+    fn simulations_round_trip_through_npz() {
+        let mut simulator = Simulator::<ExpandedLenia>::new(&[16, 16]);
+        simulator.set_channels(2);
+        for channel in 0..2 {
+            let seed = ndarray::ArrayD::from_shape_fn(vec![16, 16], |index| {
+                ((index[0] * 5 + index[1] * 3 + channel) % 7) as f64 / 7.0
+            });
+            simulator.fill_channel(&seed, channel);
+        }
+        simulator.iterate();
+        let path = std::env::temp_dir().join(format!("lenia_snapshot_{}.npz", std::process::id()));
+        simulator.export_npz(&path, true).unwrap();
+        let arrays = load_npz::<f64, _>(&path).unwrap();
+        assert_eq!(&arrays["deltas_1"], simulator.get_deltas_as_ref(1));
+        assert_eq!(&arrays["convolved_0"], simulator.get_convolved_as_ref(0));
+        assert_eq!(&arrays["kernel_0"], &simulator.get_kernel_as_ref(0).base);
+
+        let mut imported = Simulator::<ExpandedLenia>::new(&[16, 16]);
+        imported.set_channels(2);
+        imported.import_npz(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(imported.time(), simulator.time());
+        for channel in 0..2 {
+            assert_eq!(
+                imported.get_channel_as_ref(channel),
+                simulator.get_channel_as_ref(channel)
+            );
+        }
+    }
+}
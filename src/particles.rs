@@ -0,0 +1,656 @@
+//! Particle Lenia, where the Lenia system is made of point particles in continuous space instead of a grid of cells.
+
+use super::*;
+use rayon::prelude::*;
+
+/// Number of steps used for numerically integrating the kernel over space when normalizing it.
+const KERNEL_INTEGRATION_STEPS: usize = 1000;
+
+/// Field of a `ParticleLenia` to rasterize onto a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleRaster {
+    /// Number of particles in each cell, each particle being spread onto the nearest cells with linear weights.
+    Density,
+    /// The kernel field `U`, the sum of the kernels of all particles.
+    Potential,
+    /// The growth function applied onto the kernel field, `G(U)`.
+    Growth,
+    /// The energy `E = R - G(U)`, where `R` is the repulsion field.
+    Energy,
+}
+
+/// Particle Lenia
+///
+/// `ParticleLenia` struct simulates point particles in continuous n-dimensional space (usually 2d or 3d).
+/// Each particle emits a radially symmetric gaussian "donut" kernel (see `kernels::multi_gaussian_donut_profile()`),
+/// normalized such that the kernel of a single particle integrates to `1.0` over the space. The sum of the
+/// kernels of all particles makes up the kernel field `U`.
+///
+/// Each particle sits in an energy landscape `E = R - G(U)`, where `G` is the growth function and `R` is the
+/// repulsion field `R = strength / 2 * sum(max(1 - r / radius, 0)^2)` over all particles at a distance `r`.
+/// Every iteration, each particle moves down the gradient of the energy, `p -= dt * grad(E)`.
+///
+/// Unlike the grid-based Lenia types, `ParticleLenia` is not used through the `Simulator`. Use `rasterize()`
+/// to sample the particles or their fields onto a grid, which can then be used with `get_frame()` and
+/// `export_frame_as_png()`.
+///
+/// The defaults are:
+/// * kernel radius `8.0`, with a single donut at `0.5` of the radius with a standard deviation of `0.0884`.
+/// * `growth_functions::StandardLenia` with mu of `0.6` and sigma of `0.106`.
+/// * repulsion strength of `2.0` and radius of `1.0`.
+/// * dt of `0.05`.
+///
+/// ### Example of initializing a `ParticleLenia`.
+///
+/// ```
+/// # use road_to_lenia::get_frame;
+/// # use road_to_lenia::particles::{ParticleLenia, ParticleRaster};
+/// # fn display(_: &ndarray::Array2<f64>) {}
+/// let starting_positions = ndarray::Array2::<f64>::zeros((100, 2)); // fill with your data, one row per particle
+/// let mut lenia = ParticleLenia::<f64>::new(2);
+/// lenia.set_particles(&starting_positions);
+/// let mut frame = ndarray::Array2::zeros((128, 128));
+/// for _ in 0..10 {
+///     lenia.iterate();
+///     let raster = lenia.rasterize(ParticleRaster::Density, &[128, 128], &[-32.0, -32.0], 0.5);
+///     get_frame(&raster, &mut frame, &[0, 1], &[]);
+///     display(&frame);
+/// }
+/// ```
+pub struct ParticleLenia<F: LeniaFloat = f64> {
+    dimensions: usize,
+    // One row per particle, one column per axis of the space.
+    positions: ndarray::Array2<F>,
+    kernel_radius: F,
+    kernel_means: Vec<F>,
+    kernel_peaks: Vec<F>,
+    kernel_stddevs: Vec<F>,
+    // Scales the kernel such that it integrates to 1 over the space.
+    kernel_normalizer: F,
+    growth_function: Arc<dyn GrowthFunction<F>>,
+    repulsion_strength: F,
+    repulsion_radius: F,
+    dt: F,
+}
+
+impl<F: LeniaFloat> ParticleLenia<F> {
+    /// Creates a new `ParticleLenia` without any particles.
+    ///
+    /// ### Parameters
+    ///
+    /// * `dimensions` - Number of axes of the space the particles move in.
+    ///
+    /// ### Panics
+    ///
+    /// If `dimensions` is `0`.
+    pub fn new(dimensions: usize) -> Self {
+        match Self::try_new(dimensions) {
+            Ok(lenia) => lenia,
+            Err(error) => panic!("ParticleLenia::new() - {}", error),
+        }
+    }
+
+    /// Same as `new()`, but returns an error instead of panicking.
+    pub fn try_new(dimensions: usize) -> Result<Self, LeniaError> {
+        if dimensions == 0 {
+            return Err(LeniaError::InvalidShape(
+                "The space must have at least 1 dimension!".to_string(),
+            ));
+        }
+        let mut lenia = ParticleLenia {
+            dimensions,
+            positions: ndarray::Array2::zeros((0, dimensions)),
+            kernel_radius: float(8.0),
+            kernel_means: vec![float(0.5)],
+            kernel_peaks: vec![F::one()],
+            kernel_stddevs: vec![float(0.0884)],
+            kernel_normalizer: F::one(),
+            growth_function: Arc::new(growth_functions::StandardLenia::new(
+                float(0.6),
+                float(0.106),
+            )),
+            repulsion_strength: float(2.0),
+            repulsion_radius: F::one(),
+            dt: float(0.05),
+        };
+        lenia.update_kernel_normalizer();
+        Ok(lenia)
+    }
+
+    /// Returns the number of axes of the space the particles move in.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    /// Returns the number of particles.
+    pub fn particle_count(&self) -> usize {
+        self.positions.nrows()
+    }
+
+    /// Replaces all particles.
+    ///
+    /// ### Parameters
+    ///
+    /// * `positions` - Positions of the particles, one row per particle and one column per axis of the space.
+    ///
+    /// ### Panics
+    ///
+    /// If the number of columns in `positions` does not match the number of dimensions.
+    pub fn set_particles(&mut self, positions: &ndarray::Array2<F>) {
+        if let Err(error) = self.try_set_particles(positions) {
+            panic!("ParticleLenia::set_particles() - {}", error);
+        }
+    }
+
+    /// Same as `set_particles()`, but returns an error instead of panicking.
+    pub fn try_set_particles(&mut self, positions: &ndarray::Array2<F>) -> Result<(), LeniaError> {
+        if positions.ncols() != self.dimensions {
+            return Err(LeniaError::DimensionalityMismatch {
+                expected: self.dimensions,
+                found: positions.ncols(),
+            });
+        }
+        self.positions = positions.as_standard_layout().into_owned();
+        Ok(())
+    }
+
+    /// Adds a single particle.
+    ///
+    /// ### Panics
+    ///
+    /// If the length of `position` does not match the number of dimensions.
+    pub fn add_particle(&mut self, position: &[F]) {
+        if let Err(error) = self.try_add_particle(position) {
+            panic!("ParticleLenia::add_particle() - {}", error);
+        }
+    }
+
+    /// Same as `add_particle()`, but returns an error instead of panicking.
+    pub fn try_add_particle(&mut self, position: &[F]) -> Result<(), LeniaError> {
+        if position.len() != self.dimensions {
+            return Err(LeniaError::DimensionalityMismatch {
+                expected: self.dimensions,
+                found: position.len(),
+            });
+        }
+        self.positions
+            .push_row(ndarray::ArrayView1::from(position))
+            .unwrap();
+        Ok(())
+    }
+
+    /// Returns a reference to the positions of the particles, one row per particle.
+    pub fn particles(&self) -> &ndarray::Array2<F> {
+        &self.positions
+    }
+
+    /// Returns the average position of all particles, or the origin if there are no particles.
+    pub fn center(&self) -> Vec<F> {
+        match self.positions.mean_axis(Axis(0)) {
+            Some(center) => center.to_vec(),
+            None => vec![F::zero(); self.dimensions],
+        }
+    }
+
+    /// Sets the kernel of the particles, made of one or more gaussian "donuts".
+    ///
+    /// A single donut with the mean at `0.5` has the same shape as `kernels::gaussian_donut_nd()`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `radius` - Radius of the kernel, beyond which particles do not sense each other.
+    ///
+    /// * `means` - The placement of the peak values of individual donuts, in range `[0.0..1.0]` of the radius.
+    ///
+    /// * `peaks` - The maximum value that each individual donut can create.
+    ///
+    /// * `stddevs` - The standard deviations of each individual donut, relative to the radius.
+    ///
+    /// ### Panics
+    ///
+    /// * If `radius` is not positive.
+    ///
+    /// * If `means`, `peaks` and `stddevs` are not of equal length.
+    pub fn set_kernel(&mut self, radius: F, means: &[F], peaks: &[F], stddevs: &[F]) {
+        if let Err(error) = self.try_set_kernel(radius, means, peaks, stddevs) {
+            panic!("ParticleLenia::set_kernel() - {}", error);
+        }
+    }
+
+    /// Same as `set_kernel()`, but returns an error instead of panicking.
+    pub fn try_set_kernel(
+        &mut self,
+        radius: F,
+        means: &[F],
+        peaks: &[F],
+        stddevs: &[F],
+    ) -> Result<(), LeniaError> {
+        if radius <= F::zero() {
+            return Err(LeniaError::InvalidParameter(
+                "The kernel radius must be positive!".to_string(),
+            ));
+        }
+        if means.len() != peaks.len() || means.len() != stddevs.len() {
+            return Err(LeniaError::InvalidParameter(
+                "Each mean must be accompanied by a peak and a stddev!".to_string(),
+            ));
+        }
+        self.kernel_radius = radius;
+        self.kernel_means = means.to_vec();
+        self.kernel_peaks = peaks.to_vec();
+        self.kernel_stddevs = stddevs.to_vec();
+        self.update_kernel_normalizer();
+        Ok(())
+    }
+
+    /// Returns the radius of the kernel.
+    pub fn kernel_radius(&self) -> F {
+        self.kernel_radius
+    }
+
+    /// Sets the growth function applied onto the kernel field.
+    ///
+    /// ### Parameters
+    ///
+    /// * `growth_function` - Growth function to use, such as any of the typed growth functions in `growth_functions`.
+    ///
+    /// ### Panics
+    ///
+    /// If the parameters of `growth_function` are invalid.
+    pub fn set_growth_function<G: GrowthFunction<F> + 'static>(&mut self, growth_function: G) {
+        if let Err(error) = self.try_set_growth_function(growth_function) {
+            panic!("ParticleLenia::set_growth_function() - {}", error);
+        }
+    }
+
+    /// Same as `set_growth_function()`, but returns an error instead of panicking.
+    pub fn try_set_growth_function<G: GrowthFunction<F> + 'static>(
+        &mut self,
+        growth_function: G,
+    ) -> Result<(), LeniaError> {
+        check_growth_function(&growth_function)?;
+        self.growth_function = Arc::new(growth_function);
+        Ok(())
+    }
+
+    /// Sets the repulsion between particles, keeping them from collapsing onto each other.
+    ///
+    /// ### Parameters
+    ///
+    /// * `strength` - Strength of the repulsion.
+    ///
+    /// * `radius` - Distance beyond which particles do not repel each other.
+    ///
+    /// ### Panics
+    ///
+    /// If `radius` is not positive.
+    pub fn set_repulsion(&mut self, strength: F, radius: F) {
+        if let Err(error) = self.try_set_repulsion(strength, radius) {
+            panic!("ParticleLenia::set_repulsion() - {}", error);
+        }
+    }
+
+    /// Same as `set_repulsion()`, but returns an error instead of panicking.
+    pub fn try_set_repulsion(&mut self, strength: F, radius: F) -> Result<(), LeniaError> {
+        if radius <= F::zero() {
+            return Err(LeniaError::InvalidParameter(
+                "The repulsion radius must be positive!".to_string(),
+            ));
+        }
+        self.repulsion_strength = strength;
+        self.repulsion_radius = radius;
+        Ok(())
+    }
+
+    /// Returns the strength and the radius of the repulsion.
+    pub fn repulsion(&self) -> (F, F) {
+        (self.repulsion_strength, self.repulsion_radius)
+    }
+
+    /// Sets the integration step.
+    ///
+    /// ### Panics
+    ///
+    /// If `dt` is not positive and finite.
+    pub fn set_dt(&mut self, dt: F) {
+        if let Err(error) = self.try_set_dt(dt) {
+            panic!("ParticleLenia::set_dt() - {}", error);
+        }
+    }
+
+    /// Same as `set_dt()`, but returns an error instead of panicking.
+    pub fn try_set_dt(&mut self, dt: F) -> Result<(), LeniaError> {
+        check_dt(dt)?;
+        self.dt = dt;
+        Ok(())
+    }
+
+    /// Returns the integration step.
+    pub fn dt(&self) -> F {
+        self.dt
+    }
+
+    /// Moves every particle by one gradient descent step down the energy landscape.
+    pub fn iterate(&mut self) {
+        let mut steps = ndarray::Array2::zeros(self.positions.raw_dim());
+        ndarray::Zip::from(steps.rows_mut())
+            .and(self.positions.rows())
+            .par_for_each(|mut step, position| {
+                let gradient = self.energy_gradient(position.as_slice().unwrap());
+                for (step, gradient) in step.iter_mut().zip(gradient) {
+                    *step = -self.dt * gradient;
+                }
+            });
+        self.positions += &steps;
+    }
+
+    /// Returns the kernel field `U` at `point`.
+    pub fn potential(&self, point: &[F]) -> F {
+        self.fields(point).0
+    }
+
+    /// Returns the energy `E = R - G(U)` at `point`.
+    pub fn energy(&self, point: &[F]) -> F {
+        let (potential, repulsion) = self.fields(point);
+        repulsion - self.growth_function.growth(potential)
+    }
+
+    /// Samples the particles or one of their fields onto a grid.
+    ///
+    /// Cell `[i, j, ...]` covers the space from `origin + [i, j, ...] * cell_size` to
+    /// `origin + [i + 1, j + 1, ...] * cell_size`, and fields are sampled at the cell centers.
+    /// The result can be passed to `get_frame()` and `export_frame_as_png()` like a channel.
+    ///
+    /// ### Parameters
+    ///
+    /// * `raster` - What to rasterize.
+    ///
+    /// * `shape` - Shape of the resulting grid.
+    ///
+    /// * `origin` - Position of the corner of the first cell of the grid.
+    ///
+    /// * `cell_size` - Length of each cell along every axis.
+    ///
+    /// ### Panics
+    ///
+    /// * If the lengths of `shape` and `origin` do not match the number of dimensions.
+    ///
+    /// * If `cell_size` is not positive.
+    pub fn rasterize(
+        &self,
+        raster: ParticleRaster,
+        shape: &[usize],
+        origin: &[F],
+        cell_size: F,
+    ) -> ndarray::ArrayD<F> {
+        match self.try_rasterize(raster, shape, origin, cell_size) {
+            Ok(out) => out,
+            Err(error) => panic!("ParticleLenia::rasterize() - {}", error),
+        }
+    }
+
+    /// Same as `rasterize()`, but returns an error instead of panicking.
+    pub fn try_rasterize(
+        &self,
+        raster: ParticleRaster,
+        shape: &[usize],
+        origin: &[F],
+        cell_size: F,
+    ) -> Result<ndarray::ArrayD<F>, LeniaError> {
+        if shape.len() != self.dimensions || origin.len() != self.dimensions {
+            return Err(LeniaError::DimensionalityMismatch {
+                expected: self.dimensions,
+                found: if shape.len() != self.dimensions {
+                    shape.len()
+                } else {
+                    origin.len()
+                },
+            });
+        }
+        if cell_size <= F::zero() {
+            return Err(LeniaError::InvalidParameter(
+                "The cell size must be positive!".to_string(),
+            ));
+        }
+        let mut out = ndarray::ArrayD::zeros(shape);
+        if raster == ParticleRaster::Density {
+            self.rasterize_density(&mut out, origin, cell_size);
+            return Ok(out);
+        }
+        let half: F = float(0.5);
+        out.as_slice_mut()
+            .unwrap()
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(index, value)| {
+                let mut point = vec![F::zero(); shape.len()];
+                let mut remainder = index;
+                for axis in (0..shape.len()).rev() {
+                    let cell: F = float((remainder % shape[axis]) as f64);
+                    point[axis] = origin[axis] + (cell + half) * cell_size;
+                    remainder /= shape[axis];
+                }
+                *value = match raster {
+                    ParticleRaster::Potential => self.potential(&point),
+                    ParticleRaster::Growth => self.growth_function.growth(self.potential(&point)),
+                    _ => self.energy(&point),
+                };
+            });
+        Ok(out)
+    }
+
+    /// Spreads every particle onto the (up to) `2^n` cells whose centers are nearest to it.
+    fn rasterize_density(&self, out: &mut ndarray::ArrayD<F>, origin: &[F], cell_size: F) {
+        let shape = out.shape().to_vec();
+        let half: F = float(0.5);
+        let mut lower = vec![0isize; self.dimensions];
+        let mut fractions = vec![F::zero(); self.dimensions];
+        for position in self.positions.rows() {
+            for axis in 0..self.dimensions {
+                let cell = (position[axis] - origin[axis]) / cell_size - half;
+                let floor = cell.floor();
+                lower[axis] = floor.to_isize().unwrap_or(isize::MIN);
+                fractions[axis] = cell - floor;
+            }
+            'corners: for corner in 0..(1usize << self.dimensions) {
+                let mut index = Vec::with_capacity(self.dimensions);
+                let mut weight = F::one();
+                for axis in 0..self.dimensions {
+                    let upper = (corner >> axis) & 1 == 1;
+                    let cell = lower[axis].saturating_add(upper as isize);
+                    if cell < 0 || cell >= shape[axis] as isize {
+                        continue 'corners;
+                    }
+                    index.push(cell as usize);
+                    weight *= if upper {
+                        fractions[axis]
+                    } else {
+                        F::one() - fractions[axis]
+                    };
+                }
+                out[index.as_slice()] += weight;
+            }
+        }
+    }
+
+    /// Returns the kernel field and the repulsion field at `point`.
+    fn fields(&self, point: &[F]) -> (F, F) {
+        let half: F = float(0.5);
+        let mut potential = F::zero();
+        let mut repulsion = F::zero();
+        for other in self.positions.rows() {
+            let distance = euclidean_dist(point, other.as_slice().unwrap());
+            if distance <= self.kernel_radius {
+                potential += self.kernel(distance).0;
+            }
+            if distance < self.repulsion_radius {
+                let overlap = F::one() - distance / self.repulsion_radius;
+                repulsion += half * self.repulsion_strength * overlap * overlap;
+            }
+        }
+        (potential, repulsion)
+    }
+
+    /// Returns the gradient of the energy at `point`.
+    fn energy_gradient(&self, point: &[F]) -> Vec<F> {
+        let mut potential = F::zero();
+        let mut potential_gradient = vec![F::zero(); self.dimensions];
+        let mut repulsion_gradient = vec![F::zero(); self.dimensions];
+        for other in self.positions.rows() {
+            let other = other.as_slice().unwrap();
+            let distance = euclidean_dist(point, other);
+            let (value, slope) = if distance <= self.kernel_radius {
+                self.kernel(distance)
+            } else {
+                (F::zero(), F::zero())
+            };
+            potential += value;
+            // The gradient of a radial field is zero at its own center.
+            if distance == F::zero() {
+                continue;
+            }
+            let repulsion_slope = if distance < self.repulsion_radius {
+                -self.repulsion_strength * (F::one() - distance / self.repulsion_radius)
+                    / self.repulsion_radius
+            } else {
+                F::zero()
+            };
+            for axis in 0..self.dimensions {
+                let direction = (point[axis] - other[axis]) / distance;
+                potential_gradient[axis] += slope * direction;
+                repulsion_gradient[axis] += repulsion_slope * direction;
+            }
+        }
+        // The growth function is differentiated numerically, so that any growth function can be used.
+        let step = F::epsilon().cbrt() * potential.abs().max(F::one());
+        let growth_slope = (self.growth_function.growth(potential + step)
+            - self.growth_function.growth(potential - step))
+            / (step + step);
+        repulsion_gradient
+            .iter()
+            .zip(&potential_gradient)
+            .map(|(repulsion, potential)| *repulsion - growth_slope * *potential)
+            .collect()
+    }
+
+    /// Returns the normalized kernel of a single particle at `distance` from it, and its slope.
+    fn kernel(&self, distance: F) -> (F, F) {
+        let (value, slope) = kernels::multi_gaussian_donut_profile(
+            distance / self.kernel_radius,
+            &self.kernel_means,
+            &self.kernel_peaks,
+            &self.kernel_stddevs,
+        );
+        (
+            value * self.kernel_normalizer,
+            slope * self.kernel_normalizer / self.kernel_radius,
+        )
+    }
+
+    /// Integrates the kernel over the space, in shells around the particle, and scales it to integrate to `1.0`.
+    fn update_kernel_normalizer(&mut self) {
+        // Surface area of the unit sphere, 2 * pi^(n/2) / gamma(n/2).
+        let half_dimensions = self.dimensions as f64 / 2.0;
+        let (mut gamma, mut x) = if self.dimensions % 2 == 0 {
+            (1.0, 1.0)
+        } else {
+            (std::f64::consts::PI.sqrt(), 0.5)
+        };
+        while x < half_dimensions {
+            gamma *= x;
+            x += 1.0;
+        }
+        let surface = 2.0 * std::f64::consts::PI.powf(half_dimensions) / gamma;
+
+        let radius = self.kernel_radius.to_f64().unwrap();
+        let step = radius / KERNEL_INTEGRATION_STEPS as f64;
+        let mut integral = 0.0;
+        for i in 0..KERNEL_INTEGRATION_STEPS {
+            let distance = (i as f64 + 0.5) * step;
+            let (value, _) = kernels::multi_gaussian_donut_profile(
+                float::<F>(distance / radius),
+                &self.kernel_means,
+                &self.kernel_peaks,
+                &self.kernel_stddevs,
+            );
+            integral += value.to_f64().unwrap()
+                * surface
+                * distance.powi(self.dimensions as i32 - 1)
+                * step;
+        }
+        self.kernel_normalizer = if integral > 0.0 {
+            float(1.0 / integral)
+        } else {
+            F::one()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha12Rng;
+
+    fn scattered(count: usize, seed: u64) -> ParticleLenia {
+        let mut rng = ChaCha12Rng::seed_from_u64(seed);
+        let positions = ndarray::Array2::from_shape_fn((count, 2), |_| rng.gen_range(-6.0..6.0));
+        let mut lenia = ParticleLenia::new(2);
+        lenia.set_particles(&positions);
+        lenia
+    }
+
+    #[test]
+    fn energy_gradient_matches_central_differences() {
+        let lenia = scattered(12, 3);
+        let mut rng = ChaCha12Rng::seed_from_u64(4);
+        let step = 1e-5;
+        for _ in 0..20 {
+            let point = [rng.gen_range(-8.0..8.0), rng.gen_range(-8.0..8.0)];
+            let gradient = lenia.energy_gradient(&point);
+            for axis in 0..2 {
+                let (mut above, mut below) = (point, point);
+                above[axis] += step;
+                below[axis] -= step;
+                let numeric = (lenia.energy(&above) - lenia.energy(&below)) / (2.0 * step);
+                assert!(
+                    (gradient[axis] - numeric).abs() <= 1e-5 * numeric.abs().max(1.0),
+                    "axis {} at {:?}: {} vs {}",
+                    axis,
+                    point,
+                    gradient[axis],
+                    numeric
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rasterized_density_sits_on_the_particles() {
+        let mut lenia = ParticleLenia::new(2);
+        lenia.add_particle(&[2.5, 4.5]);
+        lenia.add_particle(&[6.0, 1.5]);
+        let density = lenia.rasterize(ParticleRaster::Density, &[8, 8], &[0.0, 0.0], 1.0);
+        assert_eq!(density[[2, 4]], 1.0);
+        assert_eq!(density[[5, 1]], 0.5);
+        assert_eq!(density[[6, 1]], 0.5);
+        assert_eq!(density.sum(), 2.0);
+
+        let potential = lenia.rasterize(ParticleRaster::Potential, &[8, 8], &[0.0, 0.0], 1.0);
+        assert_eq!(potential[[2, 4]], lenia.potential(&[2.5, 4.5]));
+    }
+
+    #[test]
+    fn invalid_steps_are_an_error() {
+        let mut lenia = ParticleLenia::<f64>::new(2);
+        for dt in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                lenia.try_set_dt(dt),
+                Err(LeniaError::InvalidParameter(_))
+            ));
+        }
+        assert_eq!(lenia.dt(), 0.05);
+        lenia.try_set_dt(0.2).unwrap();
+        assert_eq!(lenia.dt(), 0.2);
+    }
+}
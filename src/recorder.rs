@@ -0,0 +1,548 @@
+//! Recording simulations into animated GIFs or numbered PNG sequences.
+//!
+//! A `Recorder` captures a channel of a `Simulator` every few steps and encodes the frames as they come, on the
+//! calling thread. Frames are oriented the same way as the png-s of `export_frame_as_png()`, with the first axis
+//! of the frame running along the width of the image.
+//!
+//! ```no_run
+//! # use road_to_lenia::{lenias::StandardLenia, recorder::Recorder, LeniaError, Simulator};
+//! # fn main() -> Result<(), LeniaError> {
+//! # let mut simulator = Simulator::<StandardLenia>::new(&[64, 64]);
+//! let mut recorder = Recorder::gif("orbium.gif", 0, 5)?;
+//! recorder.set_frame_rate(30.0);
+//! recorder.record(&simulator)?;
+//! for _ in 0..1000 {
+//!     simulator.iterate();
+//!     recorder.record(&simulator)?;
+//! }
+//! recorder.finish()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::*;
+use rendering::Colormap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// Records a channel of a `Simulator` into an animated GIF, or a numbered PNG sequence with a manifest.
+pub struct Recorder<F: LeniaFloat = f64> {
+    output: Output,
+    channel: usize,
+    interval: u64,
+    display_axes: [usize; 2],
+    dimensions: Vec<usize>,
+    normalization: Normalization<F>,
+    colormap: Colormap,
+    frame_rate: f64,
+    // Step of the simulation at which the last frame was captured.
+    last_step: Option<u64>,
+    frame: ndarray::Array2<F>,
+    frames: Vec<ManifestFrame>,
+}
+
+enum Output {
+    Gif {
+        // The file is handed over to the encoder once the size of the frames is known.
+        file: Option<BufWriter<File>>,
+        encoder: Option<gif::Encoder<BufWriter<File>>>,
+    },
+    Png {
+        folder: PathBuf,
+        prefix: String,
+    },
+}
+
+/// Manifest of a PNG sequence, written next to the frames by `Recorder::finish()`.
+#[derive(Serialize)]
+struct Manifest<'a> {
+    frame_rate: f64,
+    colormap: &'a str,
+    channel: usize,
+    interval: u64,
+    width: usize,
+    height: usize,
+    frames: &'a [ManifestFrame],
+}
+
+#[derive(Serialize)]
+struct ManifestFrame {
+    file: String,
+    step: u64,
+    time: f64,
+}
+
+impl<F: LeniaFloat> Recorder<F> {
+    /// Record into an animated GIF, which loops forever.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the GIF, which is overwritten if it exists.
+    ///
+    /// * `channel` - The channel to record.
+    ///
+    /// * `interval` - Number of steps of the simulation between frames.
+    pub fn gif<P: AsRef<Path>>(
+        file_path: P,
+        channel: usize,
+        interval: u64,
+    ) -> Result<Self, LeniaError> {
+        let file = BufWriter::new(File::create(file_path)?);
+        Self::with_output(
+            Output::Gif {
+                file: Some(file),
+                encoder: None,
+            },
+            channel,
+            interval,
+        )
+    }
+
+    /// Record into a sequence of 8-bit png-s, named by `prefix` followed by the number of the frame, such
+    /// as `frame_00000.png`. A manifest with the step and time of each frame, the frame rate and the colormap is
+    /// written to `{prefix}manifest.json` by `finish()`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `folder_path` - Folder to write the png-s and the manifest to, which is created if it does not exist.
+    ///
+    /// * `prefix` - Start of the file names.
+    ///
+    /// * `channel` - The channel to record.
+    ///
+    /// * `interval` - Number of steps of the simulation between frames.
+    pub fn png_sequence<P: AsRef<Path>>(
+        folder_path: P,
+        prefix: &str,
+        channel: usize,
+        interval: u64,
+    ) -> Result<Self, LeniaError> {
+        std::fs::create_dir_all(&folder_path)?;
+        Self::with_output(
+            Output::Png {
+                folder: folder_path.as_ref().to_path_buf(),
+                prefix: prefix.to_string(),
+            },
+            channel,
+            interval,
+        )
+    }
+
+    fn with_output(output: Output, channel: usize, interval: u64) -> Result<Self, LeniaError> {
+        if interval == 0 {
+            return Err(LeniaError::InvalidParameter(
+                "The interval between frames must be at least 1 step!".to_string(),
+            ));
+        }
+        Ok(Recorder {
+            output,
+            channel,
+            interval,
+            display_axes: [0, 1],
+            dimensions: Vec::new(),
+            normalization: Normalization::None,
+            colormap: Colormap::Grayscale,
+            frame_rate: 30.0,
+            last_step: None,
+            frame: ndarray::Array2::zeros((0, 0)),
+            frames: Vec::new(),
+        })
+    }
+
+    /// Set the frame rate of the recording, `30.0` frames per second by default.
+    ///
+    /// GIFs store the delay between frames in hundredths of a second, so their frame rate is rounded accordingly.
+    ///
+    /// ### Panics
+    ///
+    /// * If `frame_rate` is not positive.
+    pub fn set_frame_rate(&mut self, frame_rate: f64) {
+        if let Err(error) = self.try_set_frame_rate(frame_rate) {
+            panic!("Recorder::set_frame_rate: {}", error);
+        }
+    }
+
+    /// Same as `set_frame_rate()`, but returns an error instead of panicking.
+    pub fn try_set_frame_rate(&mut self, frame_rate: f64) -> Result<(), LeniaError> {
+        if !(frame_rate > 0.0 && frame_rate.is_finite()) {
+            return Err(LeniaError::InvalidParameter(format!(
+                "Frame rate must be a positive number, got {}!",
+                frame_rate
+            )));
+        }
+        self.frame_rate = frame_rate;
+        Ok(())
+    }
+
+    /// Set the 2d slice of the channel that is recorded, the same as the options of `get_frame()`.
+    ///
+    /// Only needed for channels of 3 or more dimensions, which are otherwise recorded along the first two axes
+    /// at index `0` of every other axis.
+    ///
+    /// ### Parameters
+    ///
+    /// * `display_axes` - Indexes of the axes to record.
+    ///
+    /// * `dimensions` - Which indexes in any other axes the 2d slice is taken from.
+    pub fn set_slice(&mut self, display_axes: [usize; 2], dimensions: &[usize]) {
+        self.display_axes = display_axes;
+        self.dimensions = dimensions.to_vec();
+    }
+
+    /// Set the way the values of the channel are mapped onto `0.0..1.0` before encoding,
+    /// `Normalization::None` by default.
+    pub fn set_normalization(&mut self, normalization: Normalization<F>) {
+        self.normalization = normalization;
+    }
+
+    /// Set the colormap of the recording, `Colormap::Grayscale` by default.
+    ///
+    /// Frames are quantized to `256` levels before being colored. PNG sequences are grayscale png-s with
+    /// `Colormap::Grayscale`, and RGB png-s with any other colormap. Must be set before the first frame of a GIF.
+    pub fn set_colormap(&mut self, colormap: Colormap) {
+        self.colormap = colormap;
+    }
+
+    /// Number of frames recorded so far.
+    pub fn frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Capture a frame of the simulation, if its number of steps is a multiple of the interval of the recorder and
+    /// the frame has not been captured yet.
+    ///
+    /// Call after every `Simulator::iterate()`, and once before the first one to also record the initial state.
+    ///
+    /// ### Returns
+    ///
+    /// Whether a frame was captured.
+    ///
+    /// ### Errors
+    ///
+    /// * If the recorded channel or 2d slice does not exist in the simulation, or its shape changed since the
+    /// first frame of a GIF.
+    ///
+    /// * If the frame could not be written.
+    pub fn record<L: Lenia<Float = F>>(
+        &mut self,
+        simulator: &Simulator<L>,
+    ) -> Result<bool, LeniaError> {
+        let step = simulator.steps();
+        if step % self.interval != 0 || self.last_step == Some(step) {
+            return Ok(false);
+        }
+        self.capture(simulator.try_get_channel_as_ref(self.channel)?)?;
+        let pixels = self.pixels();
+        let (width, height) = (self.frame.shape()[0], self.frame.shape()[1]);
+        let file = match &mut self.output {
+            Output::Gif { file, encoder } => {
+                if encoder.is_none() {
+                    if width > u16::MAX as usize || height > u16::MAX as usize {
+                        return Err(LeniaError::InvalidShape(format!(
+                            "Frames of {}x{} pixels do not fit into a GIF!",
+                            width, height
+                        )));
+                    }
+                    let palette = self.colormap.palette();
+                    let mut new_encoder = gif::Encoder::new(
+                        file.take().unwrap(),
+                        width as u16,
+                        height as u16,
+                        &palette,
+                    )?;
+                    new_encoder.set_repeat(gif::Repeat::Infinite)?;
+                    *encoder = Some(new_encoder);
+                }
+                let frame = gif::Frame {
+                    width: width as u16,
+                    height: height as u16,
+                    delay: (100.0 / self.frame_rate)
+                        .round()
+                        .max(1.0)
+                        .min(u16::MAX as f64) as u16,
+                    buffer: std::borrow::Cow::Owned(pixels),
+                    ..gif::Frame::default()
+                };
+                encoder.as_mut().unwrap().write_frame(&frame)?;
+                String::new()
+            }
+            Output::Png { folder, prefix } => {
+                let name = format!("{}{:05}.png", prefix, self.frames.len());
+                let mut encoder = png::Encoder::new(
+                    BufWriter::new(File::create(folder.join(&name))?),
+                    width as u32,
+                    height as u32,
+                );
+                encoder.set_depth(png::BitDepth::Eight);
+                if self.colormap == Colormap::Grayscale {
+                    encoder.set_color(png::ColorType::Grayscale);
+                    let mut writer = encoder.write_header()?;
+                    writer.write_image_data(&pixels)?;
+                    writer.finish()?;
+                } else {
+                    let palette = self.colormap.palette();
+                    let colors: Vec<u8> = pixels
+                        .iter()
+                        .flat_map(|level| {
+                            let start = *level as usize * 3;
+                            [palette[start], palette[start + 1], palette[start + 2]]
+                        })
+                        .collect();
+                    encoder.set_color(png::ColorType::Rgb);
+                    let mut writer = encoder.write_header()?;
+                    writer.write_image_data(&colors)?;
+                    writer.finish()?;
+                }
+                name
+            }
+        };
+        self.frames.push(ManifestFrame {
+            file,
+            step,
+            time: simulator.time().to_f64().unwrap(),
+        });
+        self.last_step = Some(step);
+        Ok(true)
+    }
+
+    /// Finish the recording, writing the end of the GIF or the manifest of the PNG sequence.
+    pub fn finish(self) -> Result<(), LeniaError> {
+        match self.output {
+            Output::Gif { file, encoder } => {
+                if let Some(encoder) = encoder {
+                    encoder.into_inner()?.flush()?;
+                } else if let Some(mut file) = file {
+                    file.flush()?;
+                }
+            }
+            Output::Png { folder, prefix } => {
+                let manifest = Manifest {
+                    frame_rate: self.frame_rate,
+                    colormap: self.colormap.name(),
+                    channel: self.channel,
+                    interval: self.interval,
+                    width: self.frame.shape()[0],
+                    height: self.frame.shape()[1],
+                    frames: &self.frames,
+                };
+                let text = serde_json::to_string_pretty(&manifest).map_err(|error| {
+                    LeniaError::Format(format!("Could not write the manifest: {}.", error))
+                })?;
+                std::fs::write(folder.join(format!("{}manifest.json", prefix)), text)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Extract the recorded 2d slice of `channel` into the frame buffer and normalize it.
+    fn capture(&mut self, channel: &ndarray::ArrayD<F>) -> Result<(), LeniaError> {
+        let shape = channel.shape();
+        let frame_shape = if shape.len() == 1 {
+            (shape[0], 1)
+        } else {
+            let [first, second] = self.display_axes;
+            if first == second || first >= shape.len() || second >= shape.len() {
+                return Err(LeniaError::InvalidParameter(format!(
+                    "Display axes {:?} are not two different axes of a {}-dimensional channel!",
+                    self.display_axes,
+                    shape.len()
+                )));
+            }
+            if shape.len() > 2 {
+                if self.dimensions.is_empty() {
+                    self.dimensions = vec![0; shape.len()];
+                }
+                let outside = self.dimensions.len() != shape.len()
+                    || (0..shape.len()).any(|axis| {
+                        axis != first && axis != second && self.dimensions[axis] >= shape[axis]
+                    });
+                if outside {
+                    return Err(LeniaError::InvalidParameter(format!(
+                        "Slice {:?} is outside of the channel of shape {:?}!",
+                        self.dimensions, shape
+                    )));
+                }
+            }
+            (shape[first], shape[second])
+        };
+        let first_frame = self.frames.is_empty();
+        if self.frame.dim() != frame_shape {
+            if !first_frame && matches!(self.output, Output::Gif { .. }) {
+                return Err(LeniaError::ShapeMismatch {
+                    expected: self.frame.shape().to_vec(),
+                    found: vec![frame_shape.0, frame_shape.1],
+                });
+            }
+            self.frame = ndarray::Array2::zeros(frame_shape);
+        }
+        if shape.len() == 1 {
+            self.frame
+                .assign(&channel.view().into_shape(frame_shape).unwrap());
+            self.normalization.normalize(&mut self.frame);
+        } else {
+            get_frame_normalized(
+                channel,
+                &mut self.frame,
+                &self.display_axes,
+                &self.dimensions,
+                self.normalization,
+            );
+        }
+        Ok(())
+    }
+
+    /// Levels of the frame buffer out of `255`, row by row.
+    fn pixels(&self) -> Vec<u8> {
+        let (width, height) = self.frame.dim();
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                pixels.push(rendering::level(self.frame[[x, y]].to_f64().unwrap()));
+            }
+        }
+        pixels
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::StandardLenia;
+
+    fn simulator() -> Simulator<StandardLenia> {
+        let mut simulator = Simulator::<StandardLenia>::new(&[64, 48]);
+        let channel = ndarray::ArrayD::from_shape_fn(vec![64, 48], |index| {
+            (index[0] as f64 + index[1] as f64) / 110.0
+        });
+        simulator.fill_channel(&channel, 0);
+        simulator
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("lenia_recorder_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn gif_frames_round_trip() {
+        let path = temp_path("round_trip.gif");
+        let mut simulator = simulator();
+        let mut recorder = Recorder::gif(&path, 0, 2).unwrap();
+        recorder.record(&simulator).unwrap();
+        let expected: Vec<u8> = recorder.pixels();
+        for _ in 0..6 {
+            simulator.iterate();
+            recorder.record(&simulator).unwrap();
+        }
+        assert_eq!(recorder.frames(), 4);
+        recorder.finish().unwrap();
+
+        let mut options = gif::DecodeOptions::new();
+        options.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = options.read_info(File::open(&path).unwrap()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (64, 48));
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push(frame.buffer.to_vec());
+        }
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0], expected);
+    }
+
+    #[test]
+    fn interval_skips_steps_in_between() {
+        let path = temp_path("interval.gif");
+        let mut simulator = simulator();
+        let mut recorder = Recorder::gif(&path, 0, 3).unwrap();
+        let mut captured = vec![recorder.record(&simulator).unwrap()];
+        assert!(!recorder.record(&simulator).unwrap());
+        for _ in 0..7 {
+            simulator.iterate();
+            captured.push(recorder.record(&simulator).unwrap());
+        }
+        recorder.finish().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            captured,
+            [true, false, false, true, false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn png_sequence_names_frames_and_writes_a_manifest() {
+        let folder = temp_path("sequence");
+        let mut simulator = simulator();
+        let mut recorder = Recorder::png_sequence(&folder, "frame_", 0, 2).unwrap();
+        recorder.set_colormap(Colormap::Viridis);
+        recorder.set_frame_rate(12.0);
+        recorder.record(&simulator).unwrap();
+        for _ in 0..4 {
+            simulator.iterate();
+            recorder.record(&simulator).unwrap();
+        }
+        let time = simulator.time();
+        recorder.finish().unwrap();
+
+        let mut names: Vec<String> = std::fs::read_dir(&folder)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "frame_00000.png",
+                "frame_00001.png",
+                "frame_00002.png",
+                "frame_manifest.json"
+            ]
+        );
+        let decoder = png::Decoder::new(File::open(folder.join("frame_00002.png")).unwrap());
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (64, 48));
+        assert_eq!(reader.info().color_type, png::ColorType::Rgb);
+
+        let manifest: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(folder.join("frame_manifest.json")).unwrap(),
+        )
+        .unwrap();
+        std::fs::remove_dir_all(&folder).unwrap();
+        assert_eq!(manifest["frame_rate"], 12.0);
+        assert_eq!(manifest["colormap"], "viridis");
+        assert_eq!(manifest["channel"], 0);
+        assert_eq!(manifest["interval"], 2);
+        assert_eq!(
+            (manifest["width"].clone(), manifest["height"].clone()),
+            (64.into(), 48.into())
+        );
+        let frames = manifest["frames"].as_array().unwrap();
+        assert_eq!(frames.len(), 3);
+        for (n, frame) in frames.iter().enumerate() {
+            assert_eq!(frame["file"], format!("frame_{:05}.png", n));
+            assert_eq!(frame["step"], 2 * n as u64);
+        }
+        assert_eq!(frames[2]["time"], time);
+    }
+
+    #[test]
+    fn levels_are_rounded_like_the_colormaps() {
+        let folder = temp_path("levels");
+        let mut simulator = simulator();
+        simulator.fill_channel(&ndarray::ArrayD::from_elem(vec![64, 48], 0.999), 0);
+        let mut recorder = Recorder::png_sequence(&folder, "", 0, 1).unwrap();
+        recorder.record(&simulator).unwrap();
+        recorder.finish().unwrap();
+
+        let decoder = png::Decoder::new(File::open(folder.join("00000.png")).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut buffer).unwrap();
+        std::fs::remove_dir_all(&folder).unwrap();
+        assert_eq!(reader.info().color_type, png::ColorType::Grayscale);
+        assert!(buffer
+            .iter()
+            .all(|&level| level == Colormap::Grayscale.color(0.999)[0]));
+        assert_eq!(buffer[0], 255);
+    }
+}
@@ -0,0 +1,441 @@
+//! Rendering of frames into RGBA images, with colormaps and composites of several channels.
+//!
+//! Frames are 2d slices of channels, such as those extracted by `get_frame()`, and are oriented the same way as
+//! the png-s of `export_frame_as_png()`, with the first axis of the frame running along the width of the image.
+//! Render `frame.t()` to run the first axis along the height instead. Values are mapped onto `0.0..1.0` with a
+//! `Normalization` before being colored, for channels with a `ValueRange` other than `0.0..1.0`.
+//!
+//! The resulting `Image` can be exported as an RGB or RGBA png, or drawn directly from its RGBA buffer.
+
+use super::*;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// Mapping of values in range `0.0..1.0` onto colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Colormap {
+    /// From black to white.
+    #[default]
+    Grayscale,
+    /// Perceptually uniform, from dark purple through blue and green to yellow.
+    Viridis,
+    /// Perceptually uniform, from black through purple and orange to pale yellow.
+    Magma,
+    /// The jet colormap of the original Lenia, from dark blue through cyan, yellow and red to dark red.
+    Jet,
+}
+
+/// Viridis sampled at every eighth of its range.
+const VIRIDIS: [[u8; 3]; 9] = [
+    [68, 1, 84],
+    [71, 45, 123],
+    [59, 82, 139],
+    [44, 114, 142],
+    [33, 145, 140],
+    [40, 174, 128],
+    [94, 201, 98],
+    [173, 220, 48],
+    [253, 231, 37],
+];
+
+/// Magma sampled at every eighth of its range.
+const MAGMA: [[u8; 3]; 9] = [
+    [0, 0, 4],
+    [28, 16, 68],
+    [79, 18, 123],
+    [129, 37, 129],
+    [181, 54, 122],
+    [229, 80, 100],
+    [251, 135, 97],
+    [254, 194, 135],
+    [252, 253, 191],
+];
+
+/// The jet colormap of the original Lenia, which is given in eighths of the full intensity.
+const JET: [[u8; 3]; 9] = [
+    [0, 0, 128],
+    [0, 0, 255],
+    [0, 128, 255],
+    [0, 255, 255],
+    [128, 255, 128],
+    [255, 255, 0],
+    [255, 128, 0],
+    [255, 0, 0],
+    [128, 0, 0],
+];
+
+impl Colormap {
+    /// Name of the colormap, such as `"viridis"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colormap::Grayscale => "grayscale",
+            Colormap::Viridis => "viridis",
+            Colormap::Magma => "magma",
+            Colormap::Jet => "jet",
+        }
+    }
+
+    /// Color of `value`, which is clamped to `0.0..1.0`.
+    pub fn color(&self, value: f64) -> [u8; 3] {
+        let value = clamp(value);
+        let stops = match self {
+            Colormap::Grayscale => {
+                let level = level(value);
+                return [level, level, level];
+            }
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+            Colormap::Jet => &JET,
+        };
+        let position = value * (stops.len() - 1) as f64;
+        let index = (position.floor() as usize).min(stops.len() - 2);
+        let fraction = position - index as f64;
+        let mut color = [0; 3];
+        for (i, component) in color.iter_mut().enumerate() {
+            let (from, to) = (stops[index][i] as f64, stops[index + 1][i] as f64);
+            *component = (from + (to - from) * fraction).round() as u8;
+        }
+        color
+    }
+
+    /// Colors of the `256` levels of an 8-bit frame, as consecutive RGB triples.
+    pub fn palette(&self) -> Vec<u8> {
+        (0..=255)
+            .flat_map(|level| self.color(level as f64 / 255.0))
+            .collect()
+    }
+}
+
+/// Level of `value` out of `255`, rounded to the nearest one, with `value` clamped to `0.0..1.0`.
+pub fn level(value: f64) -> u8 {
+    (clamp(value) * 255.0).round() as u8
+}
+
+/// Clamp `value` to `0.0..1.0`, mapping `NaN` to `0.0`.
+fn clamp(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.max(0.0).min(1.0)
+    }
+}
+
+/// The way the alpha channel of a composite is set.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Alpha {
+    /// Every pixel is opaque, the same as drawing the composite over black.
+    #[default]
+    Opaque,
+    /// The alpha of a pixel is the largest of the values of the channels there, such that empty space is
+    /// transparent. Drawing the composite over black gives the same result as `Opaque`.
+    Coverage,
+}
+
+/// An RGBA image with 8 bits per component.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Width of the image in pixels.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the image in pixels.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The RGBA components of the pixels, row by row.
+    pub fn rgba(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Same as `rgba()`, but takes ownership of the buffer.
+    pub fn into_rgba(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// The RGB components of the pixels, row by row, leaving out the alpha channel.
+    pub fn rgb(&self) -> Vec<u8> {
+        self.pixels
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect()
+    }
+
+    /// Export the image as an 8-bit png.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the png, which is overwritten if it exists.
+    ///
+    /// * `alpha` - Whether to export RGBA, or RGB without the alpha channel.
+    pub fn save_png<P: AsRef<Path>>(&self, file_path: P, alpha: bool) -> Result<(), LeniaError> {
+        let file = BufWriter::new(std::fs::File::create(file_path)?);
+        let mut encoder = png::Encoder::new(file, self.width as u32, self.height as u32);
+        encoder.set_depth(png::BitDepth::Eight);
+        if alpha {
+            encoder.set_color(png::ColorType::Rgba);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.pixels)?;
+            writer.finish()?;
+        } else {
+            encoder.set_color(png::ColorType::Rgb);
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&self.rgb())?;
+            writer.finish()?;
+        }
+        Ok(())
+    }
+}
+
+/// Render a frame with a colormap.
+///
+/// ### Parameters
+///
+/// * `frame` - The frame to render, its first axis running along the width of the image.
+///
+/// * `colormap` - Colors of the values of the frame.
+///
+/// * `normalization` - The way the values are mapped onto `0.0..1.0`.
+pub fn render<F: LeniaFloat>(
+    frame: ndarray::ArrayView2<F>,
+    colormap: Colormap,
+    normalization: Normalization<F>,
+) -> Image {
+    let mut frame = frame.to_owned();
+    normalization.normalize(&mut frame);
+    let (width, height) = frame.dim();
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let [red, green, blue] = colormap.color(frame[[x, y]].to_f64().unwrap());
+            pixels.extend_from_slice(&[red, green, blue, 255]);
+        }
+    }
+    Image {
+        width,
+        height,
+        pixels,
+    }
+}
+
+/// Render several frames into one image, each in its own color, such as the channels of an `ExpandedLenia`.
+///
+/// The color of a pixel is the sum of the colors of the frames, each scaled by the value of the frame there.
+///
+/// ### Parameters
+///
+/// * `frames` - The frames to render, all of the same shape, their first axis running along the width of the image.
+///
+/// * `colors` - The RGB color of each frame, with components in range `0.0..1.0`.
+///
+/// * `normalization` - The way the values of each frame are mapped onto `0.0..1.0`. With `Normalization::MinMax`
+/// every frame is normalized by its own smallest and largest values.
+///
+/// * `alpha` - The way the alpha channel of the image is set.
+///
+/// ### Errors
+///
+/// * If there are no frames, the number of colors differs from the number of frames or the frames differ in shape.
+pub fn composite<F: LeniaFloat>(
+    frames: &[ndarray::ArrayView2<F>],
+    colors: &[[f64; 3]],
+    normalization: Normalization<F>,
+    alpha: Alpha,
+) -> Result<Image, LeniaError> {
+    if frames.is_empty() || frames.len() != colors.len() {
+        return Err(LeniaError::InvalidParameter(format!(
+            "A composite needs a color for every frame, got {} frames and {} colors!",
+            frames.len(),
+            colors.len()
+        )));
+    }
+    let (width, height) = frames[0].dim();
+    let mut normalized = Vec::with_capacity(frames.len());
+    for frame in frames {
+        if frame.dim() != (width, height) {
+            return Err(LeniaError::ShapeMismatch {
+                expected: vec![width, height],
+                found: frame.shape().to_vec(),
+            });
+        }
+        let mut frame = frame.to_owned();
+        normalization.normalize(&mut frame);
+        normalized.push(frame);
+    }
+    let mut pixels = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        for x in 0..width {
+            let mut color = [0.0; 3];
+            let mut coverage: f64 = 0.0;
+            for (frame, frame_color) in normalized.iter().zip(colors) {
+                let value = frame[[x, y]].to_f64().unwrap();
+                for i in 0..3 {
+                    color[i] += value * frame_color[i];
+                }
+                coverage = coverage.max(value);
+            }
+            let coverage = coverage.max(0.0).min(1.0);
+            let scale = match alpha {
+                Alpha::Opaque => 1.0,
+                Alpha::Coverage if coverage > 0.0 => 1.0 / coverage,
+                Alpha::Coverage => 0.0,
+            };
+            for component in color {
+                pixels.push(((component * scale).max(0.0).min(1.0) * 255.0).round() as u8);
+            }
+            pixels.push(match alpha {
+                Alpha::Opaque => 255,
+                Alpha::Coverage => (coverage * 255.0).round() as u8,
+            });
+        }
+    }
+    Ok(Image {
+        width,
+        height,
+        pixels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("lenia_rendering_{}_{}", std::process::id(), name))
+    }
+
+    fn read_png(path: &Path) -> (png::OutputInfo, Vec<u8>) {
+        let decoder = png::Decoder::new(std::fs::File::open(path).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer).unwrap();
+        std::fs::remove_file(path).unwrap();
+        buffer.truncate(info.buffer_size());
+        (info, buffer)
+    }
+
+    #[test]
+    fn colormaps_hit_their_stops() {
+        assert_eq!(Colormap::Grayscale.color(0.0), [0, 0, 0]);
+        assert_eq!(Colormap::Grayscale.color(0.5), [128, 128, 128]);
+        assert_eq!(Colormap::Grayscale.color(1.0), [255, 255, 255]);
+        for (colormap, stops) in [
+            (Colormap::Viridis, &VIRIDIS),
+            (Colormap::Magma, &MAGMA),
+            (Colormap::Jet, &JET),
+        ] {
+            assert_eq!(colormap.color(0.0), stops[0]);
+            assert_eq!(colormap.color(0.5), stops[4]);
+            assert_eq!(colormap.color(1.0), stops[8]);
+            assert_eq!(colormap.color(-1.0), stops[0]);
+            assert_eq!(colormap.color(f64::NAN), stops[0]);
+            assert_eq!(colormap.color(2.0), stops[8]);
+        }
+        assert_eq!(level(0.999), 255);
+        assert_eq!(level(0.5 / 255.0 - 1e-9), 0);
+    }
+
+    #[test]
+    fn composites_blend_colors_and_alpha() {
+        let red = ndarray::arr2(&[[0.5], [0.0]]);
+        let green = ndarray::arr2(&[[0.25], [0.0]]);
+        let frames = [red.view(), green.view()];
+        let colors = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+
+        let opaque = composite(&frames, &colors, Normalization::None, Alpha::Opaque).unwrap();
+        assert_eq!((opaque.width(), opaque.height()), (2, 1));
+        assert_eq!(opaque.rgba(), [128, 64, 0, 255, 0, 0, 0, 255]);
+
+        let coverage = composite(&frames, &colors, Normalization::None, Alpha::Coverage).unwrap();
+        assert_eq!(coverage.rgba(), [255, 128, 0, 128, 0, 0, 0, 0]);
+
+        assert!(composite(&frames, &colors[..1], Normalization::None, Alpha::Opaque).is_err());
+        let wide = ndarray::Array2::<f64>::zeros((3, 1));
+        assert!(matches!(
+            composite(
+                &[red.view(), wide.view()],
+                &colors,
+                Normalization::None,
+                Alpha::Opaque
+            ),
+            Err(LeniaError::ShapeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn saved_pngs_keep_their_dimensions() {
+        let frame = ndarray::Array2::from_shape_fn((5, 3), |(x, y)| (x + y) as f64 / 6.0);
+        let image = render(frame.view(), Colormap::Magma, Normalization::None);
+        let path = temp_path("image.png");
+
+        image.save_png(&path, true).unwrap();
+        let (info, rgba) = read_png(&path);
+        assert_eq!((info.width, info.height), (5, 3));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(rgba, image.rgba());
+
+        image.save_png(&path, false).unwrap();
+        let (info, rgb) = read_png(&path);
+        assert_eq!((info.width, info.height), (5, 3));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        assert_eq!(rgb, image.rgb());
+    }
+
+    #[test]
+    fn exported_frames_use_the_colormap() {
+        let frame =
+            ndarray::Array2::from_shape_fn((5, 3), |(x, y)| (x + y) as f64 / 6.0).into_dyn();
+        let folder = temp_path("export");
+        std::fs::create_dir_all(&folder).unwrap();
+        let folder_path = folder.to_str().unwrap();
+
+        write_frame_as_png(
+            png::BitDepth::Eight,
+            &frame,
+            &Colormap::Viridis,
+            Normalization::None,
+            "viridis",
+            folder_path,
+        )
+        .unwrap();
+        let (info, rgb) = read_png(&folder.join("viridis.png"));
+        let expected = render(
+            frame.view().into_dimensionality().unwrap(),
+            Colormap::Viridis,
+            Normalization::None,
+        );
+        assert_eq!((info.width, info.height), (5, 3));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        assert_eq!(rgb, expected.rgb());
+
+        write_frame_as_png(
+            png::BitDepth::Eight,
+            &frame,
+            &Colormap::Grayscale,
+            Normalization::None,
+            "grayscale",
+            folder_path,
+        )
+        .unwrap();
+        let (info, levels) = read_png(&folder.join("grayscale.png"));
+        std::fs::remove_dir(&folder).unwrap();
+        assert_eq!(info.color_type, png::ColorType::Grayscale);
+        let gray = render(
+            frame.view().into_dimensionality().unwrap(),
+            Colormap::Grayscale,
+            Normalization::None,
+        );
+        assert_eq!(
+            levels,
+            gray.rgb().into_iter().step_by(3).collect::<Vec<_>>()
+        );
+    }
+}
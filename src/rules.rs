@@ -0,0 +1,803 @@
+//! Declarative descriptions of Lenia rulesets, readable from and writable to JSON or TOML files.
+
+use super::*;
+use num_traits::ToPrimitive;
+use std::collections::BTreeMap;
+use std::convert::TryInto;
+use std::path::Path;
+
+/// A complete ruleset of an `ExpandedLenia` simulation.
+///
+/// Describes everything that is usually set up in code by `set_channels()`, `set_convolution_channels()`,
+/// `set_convolution_channel_source()`, `set_kernel()`, `set_growth_function()` and `set_weights()` calls.
+/// Kernels and growth functions are referred to by name, and made by a `Registry`.
+///
+/// Value ranges, stochasticity, growth parameter maps and the adaptive integration step are not part of the
+/// ruleset, and are left at their defaults when a `Simulator` is made from `Rules`.
+///
+/// ### Example of a rules file.
+/// Orbium unicaudatus in JSON, the same as the defaults of `StandardLenia`.
+///
+/// ```json
+/// {
+///     "shape": [256, 256],
+///     "dt": 0.1,
+///     "channels": [{ "weights": [1.0] }],
+///     "convolution_channels": [{
+///         "source": 0,
+///         "kernel": { "generator": "gaussian_donut_2d", "radius": 13, "parameters": [0.15] },
+///         "growth_function": { "name": "standard_lenia", "parameters": [0.15, 0.017] }
+///     }]
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rules {
+    /// The shape of the channels.
+    pub shape: Vec<usize>,
+    /// The integration step (a.k.a. timestep) parameter `dt`.
+    pub dt: f64,
+    /// Boundary condition of each axis of the channels. Periodic along every axis if left empty.
+    #[serde(default)]
+    pub boundaries: Vec<BoundaryCondition>,
+    /// The scheme used to advance the channels by one integration step.
+    #[serde(default)]
+    pub integrator: Integrator,
+    /// The channels, at least 1.
+    pub channels: Vec<ChannelRules>,
+    /// The convolution channels, at least 1.
+    pub convolution_channels: Vec<ConvolutionChannelRules>,
+}
+
+/// Rules of a single channel.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChannelRules {
+    /// Weight of each convolution channel in the weighted sum of the channel, see `Simulator::set_weights()`.
+    pub weights: Vec<f64>,
+    /// The way the weighted sum of the convolution channels updates the channel.
+    #[serde(default)]
+    pub update_mode: UpdateMode,
+}
+
+/// Rules of a single convolution channel.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConvolutionChannelRules {
+    /// Index of the channel that the convolution channel convolutes.
+    pub source: usize,
+    /// The way the convolution is computed.
+    #[serde(default)]
+    pub backend: ConvolutionBackend,
+    /// The kernel of the convolution channel.
+    pub kernel: KernelRules,
+    /// The growth function of the convolution channel.
+    pub growth_function: GrowthFunctionRules,
+}
+
+/// A kernel, either made by a named generator or given by its values.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KernelRules {
+    /// A kernel made by a kernel generator of the `Registry`.
+    Generated {
+        /// Name of the kernel generator, such as `"gaussian_donut_2d"`.
+        generator: String,
+        /// The radius of the kernel.
+        #[serde(default)]
+        radius: usize,
+        /// Parameters of the kernel generator, see `Registry::new()` for their order.
+        #[serde(default)]
+        parameters: Vec<f64>,
+    },
+    /// A kernel given by its values, such as one set with `Simulator::set_kernel()`.
+    Values {
+        /// The shape of the kernel.
+        shape: Vec<usize>,
+        /// The values of the kernel, in row-major order.
+        values: Vec<f64>,
+    },
+}
+
+/// A growth function of the `Registry`, along with its parameters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GrowthFunctionRules {
+    /// Name of the growth function, such as `"standard_lenia"`, the same as `GrowthFunction::name()`.
+    pub name: String,
+    /// Parameters of the growth function, in the order of `GrowthFunction::parameter_names()`.
+    #[serde(default)]
+    pub parameters: Vec<f64>,
+}
+
+impl Rules {
+    /// Parse rules from JSON.
+    pub fn from_json(json: &str) -> Result<Self, LeniaError> {
+        serde_json::from_str(json)
+            .map_err(|error| LeniaError::Format(format!("Invalid rules: {}.", error)))
+    }
+
+    /// Write the rules as JSON.
+    pub fn to_json(&self) -> Result<String, LeniaError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|error| LeniaError::Format(format!("Could not write rules: {}.", error)))
+    }
+
+    /// Parse rules from TOML.
+    pub fn from_toml(toml: &str) -> Result<Self, LeniaError> {
+        toml::from_str(toml)
+            .map_err(|error| LeniaError::Format(format!("Invalid rules: {}.", error)))
+    }
+
+    /// Write the rules as TOML.
+    pub fn to_toml(&self) -> Result<String, LeniaError> {
+        toml::to_string_pretty(self)
+            .map_err(|error| LeniaError::Format(format!("Could not write rules: {}.", error)))
+    }
+
+    /// Read rules from a file, as TOML if the file has the `.toml` extension, as JSON otherwise.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the rules file.
+    pub fn load<P: AsRef<Path>>(file_path: P) -> Result<Self, LeniaError> {
+        let text = std::fs::read_to_string(&file_path)?;
+        if is_toml(file_path.as_ref()) {
+            Self::from_toml(&text)
+        } else {
+            Self::from_json(&text)
+        }
+    }
+
+    /// Write the rules to a file, as TOML if the file has the `.toml` extension, as JSON otherwise.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the rules file, which is overwritten if it exists.
+    pub fn save<P: AsRef<Path>>(&self, file_path: P) -> Result<(), LeniaError> {
+        let text = if is_toml(file_path.as_ref()) {
+            self.to_toml()?
+        } else {
+            self.to_json()?
+        };
+        std::fs::write(file_path, text)?;
+        Ok(())
+    }
+}
+
+fn is_toml(file_path: &Path) -> bool {
+    file_path
+        .extension()
+        .map_or(false, |extension| extension.eq_ignore_ascii_case("toml"))
+}
+
+/// Makes a kernel from its radius, dimensionality and parameters.
+type KernelGenerator<F> =
+    Box<dyn Fn(usize, usize, &[F]) -> Result<ndarray::ArrayD<F>, LeniaError> + Send + Sync>;
+
+/// Makes a growth function from its parameters.
+type GrowthFunctionConstructor<F> =
+    Box<dyn Fn(&[F]) -> Result<Arc<dyn GrowthFunction<F>>, LeniaError> + Send + Sync>;
+
+/// Kernel generators and growth functions by name, used for turning `Rules` into a simulation.
+pub struct Registry<F: LeniaFloat = f64> {
+    kernels: BTreeMap<String, KernelGenerator<F>>,
+    growth_functions: BTreeMap<String, GrowthFunctionConstructor<F>>,
+}
+
+impl<F: LeniaFloat> Registry<F> {
+    /// Registry of every kernel generator in the `kernels` module and every growth function in the
+    /// `growth_functions` module.
+    ///
+    /// Kernel generators are registered by their function names and take these parameters, the dimensionality
+    /// of the kernel being that of the channels:
+    ///
+    /// * `gaussian_donut_2d`, `gaussian_donut_nd` - `[stddev]`
+    ///
+    /// * `multi_gaussian_donut_2d`, `multi_gaussian_donut_nd` - `[mean, peak, stddev]` of each ring, one after another.
+    ///
+    /// * `precalculated_linear`, `polynomial_nd` - The same as the `params` of the kernel generator.
+    ///
+    /// * `smoothlife` - `[width_ratio]`
+    ///
+    /// * `conway_game_of_life`, `pass` - No parameters, and the radius is ignored.
+    ///
+    /// Growth functions are registered by `GrowthFunction::name()` and take the parameters of their
+    /// `from_parameters()`.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register_kernel("gaussian_donut_2d", |radius, dimensions, parameters| {
+            let [stddev] = kernel_parameters("gaussian_donut_2d", parameters)?;
+            Ok(kernels::gaussian_donut_2d(radius, stddev))
+        });
+        registry.register_kernel("gaussian_donut_nd", |radius, dimensions, parameters| {
+            let [stddev] = kernel_parameters("gaussian_donut_nd", parameters)?;
+            Ok(kernels::gaussian_donut_nd(radius, dimensions, stddev))
+        });
+        registry.register_kernel(
+            "multi_gaussian_donut_2d",
+            |radius, dimensions, parameters| {
+                let (means, peaks, stddevs) = rings("multi_gaussian_donut_2d", parameters)?;
+                kernels::try_multi_gaussian_donut_2d(radius, &means, &peaks, &stddevs)
+            },
+        );
+        registry.register_kernel(
+            "multi_gaussian_donut_nd",
+            |radius, dimensions, parameters| {
+                let (means, peaks, stddevs) = rings("multi_gaussian_donut_nd", parameters)?;
+                Ok(kernels::multi_gaussian_donut_nd(
+                    radius, dimensions, &means, &peaks, &stddevs,
+                ))
+            },
+        );
+        registry.register_kernel("precalculated_linear", |radius, dimensions, parameters| {
+            if parameters.is_empty() {
+                return Err(LeniaError::InvalidParameter(
+                    "Kernel generator \"precalculated_linear\" expects at least 1 parameter."
+                        .to_string(),
+                ));
+            }
+            Ok(kernels::precalculated_linear(
+                radius, dimensions, parameters,
+            ))
+        });
+        registry.register_kernel("polynomial_nd", |radius, dimensions, parameters| {
+            if parameters.len() < 2 {
+                return Err(LeniaError::InvalidParameter("Kernel generator \"polynomial_nd\" expects a polynomial power and at least 1 peak height.".to_string()));
+            }
+            Ok(kernels::polynomial_nd(radius, dimensions, parameters))
+        });
+        registry.register_kernel("smoothlife", |radius, dimensions, parameters| {
+            let [width_ratio] = kernel_parameters("smoothlife", parameters)?;
+            Ok(kernels::smoothlife(radius, dimensions, width_ratio))
+        });
+        registry.register_kernel("conway_game_of_life", |radius, dimensions, parameters| {
+            let [] = kernel_parameters("conway_game_of_life", parameters)?;
+            Ok(kernels::conway_game_of_life())
+        });
+        registry.register_kernel("pass", |radius, dimensions, parameters| {
+            let [] = kernel_parameters("pass", parameters)?;
+            Ok(kernels::pass(dimensions))
+        });
+
+        registry.register_growth_function("standard_lenia", |parameters| {
+            shared(
+                "standard_lenia",
+                growth_functions::StandardLenia::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("standard_lenia_target", |parameters| {
+            shared(
+                "standard_lenia_target",
+                growth_functions::StandardLeniaTarget::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("multimodal_normal", |parameters| {
+            shared(
+                "multimodal_normal",
+                growth_functions::MultimodalNormal::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("multimodal_normal_target", |parameters| {
+            shared(
+                "multimodal_normal_target",
+                growth_functions::MultimodalNormalTarget::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("polynomial", |parameters| {
+            shared(
+                "polynomial",
+                growth_functions::Polynomial::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("polynomial_target", |parameters| {
+            shared(
+                "polynomial_target",
+                growth_functions::PolynomialTarget::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("precalculated_linear", |parameters| {
+            shared(
+                "precalculated_linear",
+                growth_functions::PrecalculatedLinear::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("smooth_life", |parameters| {
+            shared(
+                "smooth_life",
+                growth_functions::SmoothLife::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("smooth_life_sigmoid_smoothed", |parameters| {
+            shared(
+                "smooth_life_sigmoid_smoothed",
+                growth_functions::SmoothLifeSigmoidSmoothed::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("conway_game_of_life", |parameters| {
+            shared(
+                "conway_game_of_life",
+                growth_functions::ConwayGameOfLife::from_parameters(parameters),
+            )
+        });
+        registry.register_growth_function("pass", |parameters| {
+            shared("pass", growth_functions::Pass::from_parameters(parameters))
+        });
+        registry
+    }
+
+    /// Registry without any kernel generators or growth functions.
+    pub fn empty() -> Self {
+        Registry {
+            kernels: BTreeMap::new(),
+            growth_functions: BTreeMap::new(),
+        }
+    }
+
+    /// Register a kernel generator, replacing any kernel generator of the same name.
+    ///
+    /// ### Parameters
+    ///
+    /// * `name` - The name that `KernelRules::Generated` refers to the kernel generator by.
+    ///
+    /// * `generator` - Makes the kernel from its radius, dimensionality and parameters.
+    pub fn register_kernel<G>(&mut self, name: &str, generator: G)
+    where
+        G: Fn(usize, usize, &[F]) -> Result<ndarray::ArrayD<F>, LeniaError> + Send + Sync + 'static,
+    {
+        self.kernels.insert(name.to_string(), Box::new(generator));
+    }
+
+    /// Register a growth function, replacing any growth function of the same name.
+    ///
+    /// ### Parameters
+    ///
+    /// * `name` - The name that `GrowthFunctionRules` refers to the growth function by, which should be the same
+    /// as its `GrowthFunction::name()` for the growth function to be saved as rules.
+    ///
+    /// * `constructor` - Makes the growth function from its parameters.
+    pub fn register_growth_function<C>(&mut self, name: &str, constructor: C)
+    where
+        C: Fn(&[F]) -> Result<Arc<dyn GrowthFunction<F>>, LeniaError> + Send + Sync + 'static,
+    {
+        self.growth_functions
+            .insert(name.to_string(), Box::new(constructor));
+    }
+
+    /// Get the names of the registered kernel generators, in alphabetical order.
+    pub fn kernel_names(&self) -> Vec<String> {
+        self.kernels.keys().cloned().collect()
+    }
+
+    /// Get the names of the registered growth functions, in alphabetical order.
+    pub fn growth_function_names(&self) -> Vec<String> {
+        self.growth_functions.keys().cloned().collect()
+    }
+
+    /// Make the kernel described by `rules`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `rules` - The kernel to make.
+    ///
+    /// * `dimensions` - Dimensionality of the kernel, used by the kernel generators that are not fixed to 2d.
+    pub fn make_kernel(
+        &self,
+        rules: &KernelRules,
+        dimensions: usize,
+    ) -> Result<ndarray::ArrayD<F>, LeniaError> {
+        match rules {
+            KernelRules::Generated {
+                generator,
+                radius,
+                parameters,
+            } => {
+                let make = self
+                    .kernels
+                    .get(generator)
+                    .ok_or_else(|| LeniaError::UnknownName {
+                        kind: "kernel generator".to_string(),
+                        name: generator.clone(),
+                        available: self.kernel_names(),
+                    })?;
+                let parameters: Vec<F> = parameters.iter().map(|value| float(*value)).collect();
+                make(*radius, dimensions, &parameters)
+            }
+            KernelRules::Values { shape, values } => ndarray::ArrayD::from_shape_vec(
+                shape.as_slice(),
+                values.iter().map(|value| float(*value)).collect(),
+            )
+            .map_err(|_| LeniaError::ShapeMismatch {
+                expected: shape.clone(),
+                found: vec![values.len()],
+            }),
+        }
+    }
+
+    /// Make the growth function described by `rules`.
+    pub fn make_growth_function(
+        &self,
+        rules: &GrowthFunctionRules,
+    ) -> Result<Arc<dyn GrowthFunction<F>>, LeniaError> {
+        let make =
+            self.growth_functions
+                .get(&rules.name)
+                .ok_or_else(|| LeniaError::UnknownName {
+                    kind: "growth function".to_string(),
+                    name: rules.name.clone(),
+                    available: self.growth_function_names(),
+                })?;
+        let parameters: Vec<F> = rules.parameters.iter().map(|value| float(*value)).collect();
+        make(&parameters)
+    }
+}
+
+impl<F: LeniaFloat> Default for Registry<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Checks that a kernel generator got exactly `N` parameters.
+fn kernel_parameters<F: LeniaFloat, const N: usize>(
+    generator: &str,
+    parameters: &[F],
+) -> Result<[F; N], LeniaError> {
+    parameters.try_into().map_err(|_| {
+        LeniaError::InvalidParameter(format!(
+            "Kernel generator \"{}\" expects {} parameters, got {}.",
+            generator,
+            N,
+            parameters.len()
+        ))
+    })
+}
+
+/// Means, peaks and standard deviations of the rings of a gaussian donut kernel.
+type Rings<F> = (Vec<F>, Vec<F>, Vec<F>);
+
+/// Splits the flattened `[mean, peak, stddev]` of each ring of the gaussian donut kernels.
+fn rings<F: LeniaFloat>(generator: &str, parameters: &[F]) -> Result<Rings<F>, LeniaError> {
+    if parameters.is_empty() || parameters.len() % 3 != 0 {
+        return Err(LeniaError::InvalidParameter(format!(
+            "Kernel generator \"{}\" expects a mean, peak and stddev for each ring, got {} parameters.",
+            generator,
+            parameters.len()
+        )));
+    }
+    let ring = |offset: usize| parameters.iter().skip(offset).step_by(3).copied().collect();
+    Ok((ring(0), ring(1), ring(2)))
+}
+
+/// Shares a growth function made by `from_parameters()`.
+fn shared<F: LeniaFloat, G: GrowthFunction<F> + 'static>(
+    name: &str,
+    growth_function: Result<G, String>,
+) -> Result<Arc<dyn GrowthFunction<F>>, LeniaError> {
+    match growth_function {
+        Ok(growth_function) => Ok(Arc::new(growth_function)),
+        Err(error) => Err(LeniaError::InvalidParameter(format!(
+            "Invalid parameters for growth function \"{}\": {}.",
+            name, error
+        ))),
+    }
+}
+
+impl<L: NewLenia> Simulator<L> {
+    /// Initialize a Lenia simulator from a ruleset.
+    ///
+    /// Only the parts of the ruleset that differ from the defaults of the `Lenia` instance are set, so
+    /// that a `StandardLenia` can be made from rules with a single channel and convolution channel.
+    ///
+    /// ### Parameters
+    ///
+    /// * `rules` - The ruleset to simulate.
+    ///
+    /// * `registry` - Kernel generators and growth functions that the ruleset refers to.
+    pub fn from_rules(rules: &Rules, registry: &Registry<L::Float>) -> Result<Self, LeniaError> {
+        let mut simulator = Self::try_new(&rules.shape)?;
+        simulator.try_set_channels(rules.channels.len())?;
+        simulator.try_set_convolution_channels(rules.convolution_channels.len())?;
+        if !rules.boundaries.is_empty() && rules.boundaries != simulator.boundary_conditions() {
+            simulator.try_set_boundary_conditions(&rules.boundaries)?;
+        }
+        if rules.integrator != simulator.integrator() {
+            simulator.try_set_integrator(rules.integrator)?;
+        }
+        simulator.try_set_dt(float(rules.dt))?;
+        for (i, convolution_channel) in rules.convolution_channels.iter().enumerate() {
+            if convolution_channel.source != simulator.try_convolution_channel_source(i)? {
+                simulator.try_set_convolution_channel_source(i, convolution_channel.source)?;
+            }
+            if convolution_channel.backend != simulator.try_convolution_backend(i)? {
+                simulator.try_set_convolution_backend(convolution_channel.backend, i)?;
+            }
+            // Check the size of a generated kernel before making it, a corrupted radius must not exhaust memory.
+            if let KernelRules::Generated { radius, .. } = convolution_channel.kernel {
+                let diameter = radius.checked_mul(2);
+                if diameter.map_or(true, |diameter| {
+                    rules.shape.iter().any(|axis| diameter > *axis)
+                }) {
+                    return Err(LeniaError::InvalidShape(format!(
+                        "The kernel of convolution channel {} (radius {}) is larger than the channels {:?}!",
+                        i, radius, rules.shape
+                    )));
+                }
+            }
+            let kernel = registry.make_kernel(&convolution_channel.kernel, rules.shape.len())?;
+            simulator.try_set_kernel(kernel, i)?;
+            simulator.kernel_rules.resize(i + 1, None);
+            simulator.kernel_rules[i] = Some(convolution_channel.kernel.clone());
+            let growth_function =
+                registry.make_growth_function(&convolution_channel.growth_function)?;
+            simulator.try_set_shared_growth_function(growth_function, i)?;
+        }
+        for (i, channel) in rules.channels.iter().enumerate() {
+            if channel.update_mode != simulator.try_update_mode(i)? {
+                simulator.try_set_update_mode(i, channel.update_mode)?;
+            }
+            if channel.weights.len() != rules.convolution_channels.len() {
+                return Err(LeniaError::InvalidParameter(format!(
+                    "Channel {} expects {} weights, one for each convolution channel, got {}.",
+                    i,
+                    rules.convolution_channels.len(),
+                    channel.weights.len()
+                )));
+            }
+            let weights: Vec<L::Float> = channel
+                .weights
+                .iter()
+                .map(|weight| float(*weight))
+                .collect();
+            if weights != simulator.try_weights(i)? {
+                simulator.try_set_weights(i, &weights)?;
+            }
+        }
+        Ok(simulator)
+    }
+
+    /// Initialize a Lenia simulator from a rules file, see `Rules::load()`.
+    ///
+    /// The kernels and growth functions are made by `Registry::new()`, use `from_rules()` for any others.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the rules file.
+    pub fn load_rules<P: AsRef<Path>>(file_path: P) -> Result<Self, LeniaError> {
+        Self::from_rules(&Rules::load(file_path)?, &Registry::new())
+    }
+}
+
+impl<L: Lenia> Simulator<L> {
+    /// Get the ruleset of the simulation.
+    ///
+    /// Kernels made from rules keep their generator, while kernels set with `set_kernel()` are described
+    /// by their values.
+    ///
+    /// ### Parameters
+    ///
+    /// * `registry` - The registry the ruleset is going to be read with.
+    ///
+    /// ### Errors
+    ///
+    /// If a growth function is not in `registry`, such as a closure.
+    pub fn rules(&self, registry: &Registry<L::Float>) -> Result<Rules, LeniaError> {
+        let channels = (0..self.channels())
+            .map(|i| ChannelRules {
+                weights: self
+                    .weights(i)
+                    .iter()
+                    .map(|weight| weight.to_f64().unwrap())
+                    .collect(),
+                update_mode: self.update_mode(i),
+            })
+            .collect();
+        let mut convolution_channels = Vec::with_capacity(self.convolution_channels());
+        for i in 0..self.convolution_channels() {
+            let growth_function = self.growth_function(i);
+            if !registry
+                .growth_functions
+                .contains_key(growth_function.name())
+            {
+                return Err(LeniaError::Unsupported(format!("Growth function \"{}\" of convolution channel {} is not in the registry, and can not be described by rules!", growth_function.name(), i)));
+            }
+            let kernel = match self.kernel_rules.get(i) {
+                Some(Some(kernel)) => kernel.clone(),
+                _ => {
+                    let base = &self.get_kernel_as_ref(i).base;
+                    KernelRules::Values {
+                        shape: base.shape().to_vec(),
+                        values: base.iter().map(|value| value.to_f64().unwrap()).collect(),
+                    }
+                }
+            };
+            convolution_channels.push(ConvolutionChannelRules {
+                source: self.convolution_channel_source(i),
+                backend: self.convolution_backend(i),
+                kernel,
+                growth_function: GrowthFunctionRules {
+                    name: growth_function.name().to_string(),
+                    parameters: growth_function
+                        .parameters()
+                        .iter()
+                        .map(|value| value.to_f64().unwrap())
+                        .collect(),
+                },
+            });
+        }
+        Ok(Rules {
+            shape: self.shape().to_vec(),
+            dt: self.dt().to_f64().unwrap(),
+            boundaries: self.boundary_conditions().to_vec(),
+            integrator: self.integrator(),
+            channels,
+            convolution_channels,
+        })
+    }
+
+    /// Write the ruleset of the simulation to a rules file, see `Rules::save()`.
+    ///
+    /// ### Parameters
+    ///
+    /// * `file_path` - Path to the rules file, which is overwritten if it exists.
+    ///
+    /// ### Errors
+    ///
+    /// If a growth function is not in `Registry::new()`, such as a closure.
+    pub fn save_rules<P: AsRef<Path>>(&self, file_path: P) -> Result<(), LeniaError> {
+        self.rules(&Registry::new())?.save(file_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lenias::ExpandedLenia;
+
+    /// Two channels, one kernel made by a generator and one given by its values.
+    fn simulator() -> Simulator<ExpandedLenia> {
+        let rules = Rules::from_json(
+            r#"{
+                "shape": [24, 20],
+                "dt": 0.2,
+                "boundaries": ["zero", "periodic"],
+                "integrator": "heun",
+                "channels": [{ "weights": [1.0] }, { "weights": [0.25] }],
+                "convolution_channels": [{
+                    "source": 0,
+                    "kernel": { "generator": "gaussian_donut_2d", "radius": 6, "parameters": [0.15] },
+                    "growth_function": { "name": "standard_lenia", "parameters": [0.15, 0.017] }
+                }]
+            }"#,
+        )
+        .unwrap();
+        let mut simulator =
+            Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()).unwrap();
+        simulator.set_convolution_channels(2);
+        simulator.set_convolution_channel_source(1, 1);
+        simulator.set_kernel(
+            ndarray::ArrayD::from_shape_fn(vec![3, 5], |index| (index[0] + index[1] + 1) as f64),
+            1,
+        );
+        simulator.set_growth_function(growth_functions::Polynomial::new(0.3, 0.05, 4.0), 1);
+        simulator.set_weights(0, &[1.0, 0.5]);
+        simulator.set_weights(1, &[0.25, 1.0]);
+        simulator
+    }
+
+    fn fill(simulator: &mut Simulator<ExpandedLenia>) {
+        for channel in 0..2 {
+            let seed = ndarray::ArrayD::from_shape_fn(vec![24, 20], |index| {
+                ((index[0] * 7 + index[1] * 3 + channel * 5) % 11) as f64 / 11.0
+            });
+            simulator.fill_channel(&seed, channel);
+        }
+    }
+
+    fn assert_steps_identically(rules: &Rules, rebuilt: &Rules) {
+        assert_eq!(rules, rebuilt);
+        let mut original = simulator();
+        let mut rebuilt =
+            Simulator::<ExpandedLenia>::from_rules(rebuilt, &Registry::new()).unwrap();
+        fill(&mut original);
+        fill(&mut rebuilt);
+        for _ in 0..5 {
+            original.iterate();
+            rebuilt.iterate();
+        }
+        for channel in 0..2 {
+            assert_eq!(
+                original.get_channel_as_ref(channel),
+                rebuilt.get_channel_as_ref(channel)
+            );
+        }
+    }
+
+    #[test]
+    fn rules_round_trip_through_json() {
+        let rules = simulator().rules(&Registry::new()).unwrap();
+        let rebuilt = Rules::from_json(&rules.to_json().unwrap()).unwrap();
+        assert_steps_identically(&rules, &rebuilt);
+    }
+
+    #[test]
+    fn rules_round_trip_through_toml() {
+        let rules = simulator().rules(&Registry::new()).unwrap();
+        let rebuilt = Rules::from_toml(&rules.to_toml().unwrap()).unwrap();
+        assert_steps_identically(&rules, &rebuilt);
+    }
+
+    #[test]
+    fn unknown_names_are_an_error() {
+        let mut rules = simulator().rules(&Registry::new()).unwrap();
+        rules.convolution_channels[0].kernel = KernelRules::Generated {
+            generator: "gaussian_donut_3d".to_string(),
+            radius: 6,
+            parameters: vec![0.15],
+        };
+        assert!(matches!(
+            Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()),
+            Err(LeniaError::UnknownName { kind, name, .. })
+                if kind == "kernel generator" && name == "gaussian_donut_3d"
+        ));
+
+        let mut rules = simulator().rules(&Registry::new()).unwrap();
+        rules.convolution_channels[1].growth_function.name = "lenia".to_string();
+        assert!(matches!(
+            Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()),
+            Err(LeniaError::UnknownName { kind, name, .. })
+                if kind == "growth function" && name == "lenia"
+        ));
+    }
+
+    #[test]
+    fn wrong_parameter_counts_are_an_error() {
+        let mut rules = simulator().rules(&Registry::new()).unwrap();
+        rules.convolution_channels[0].kernel = KernelRules::Generated {
+            generator: "gaussian_donut_2d".to_string(),
+            radius: 6,
+            parameters: vec![0.15, 0.3],
+        };
+        assert!(matches!(
+            Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()),
+            Err(LeniaError::InvalidParameter(_))
+        ));
+
+        let mut rules = simulator().rules(&Registry::new()).unwrap();
+        rules.convolution_channels[0].growth_function.parameters = vec![0.15];
+        assert!(matches!(
+            Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()),
+            Err(LeniaError::InvalidParameter(_))
+        ));
+
+        for weights in [vec![1.0], vec![1.0, 0.5, 0.25]] {
+            let mut rules = simulator().rules(&Registry::new()).unwrap();
+            rules.channels[0].weights = weights;
+            assert!(matches!(
+                Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()),
+                Err(LeniaError::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_steps_and_oversized_kernels_are_an_error() {
+        for dt in [f64::NAN, 0.0, -0.1, f64::INFINITY] {
+            let mut rules = simulator().rules(&Registry::new()).unwrap();
+            rules.dt = dt;
+            assert!(matches!(
+                Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()),
+                Err(LeniaError::InvalidParameter(_))
+            ));
+        }
+
+        for radius in [33, usize::MAX / 2, usize::MAX] {
+            let mut rules = simulator().rules(&Registry::new()).unwrap();
+            rules.convolution_channels[0].kernel = KernelRules::Generated {
+                generator: "gaussian_donut_2d".to_string(),
+                radius,
+                parameters: vec![0.15],
+            };
+            assert!(matches!(
+                Simulator::<ExpandedLenia>::from_rules(&rules, &Registry::new()),
+                Err(LeniaError::InvalidShape(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Helpers shared by the tests of several modules.
+
+/// Deterministic field of pseudo-random values, the same for every run with the same `seed`.
+///
+/// Every cell is `value` of the next number of a xorshift generator.
+pub(crate) fn field_with(
+    shape: &[usize],
+    seed: u64,
+    mut value: impl FnMut(u64) -> f64,
+) -> ndarray::ArrayD<f64> {
+    let mut state = seed.wrapping_mul(0x9e3779b97f4a7c15) | 1;
+    ndarray::ArrayD::from_shape_simple_fn(shape, || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        value(state)
+    })
+}
+
+/// Deterministic field with values in `0.25..1.0`, with about a quarter of the cells empty.
+pub(crate) fn field(shape: &[usize], seed: u64) -> ndarray::ArrayD<f64> {
+    field_with(shape, seed, |state| {
+        let value = (state % 1000) as f64 / 1000.0;
+        if value < 0.25 {
+            0.0
+        } else {
+            value
+        }
+    })
+}